@@ -19,6 +19,13 @@ impl BasicToolSet {
 
         BasicToolSet { tools: result }
     }
+
+    ///
+    /// Creates a toolset from a list of tools that have already been named
+    ///
+    pub fn from_named_tools(tools: Vec<(String, Box<Tool>)>) -> BasicToolSet {
+        BasicToolSet { tools: tools }
+    }
 }
 
 impl ToolSet for BasicToolSet {
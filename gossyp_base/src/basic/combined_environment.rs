@@ -24,6 +24,39 @@ impl<'a> EnvironmentCollection<'a> {
     }
 }
 
+///
+/// Describes an environment whose list-tools tool did not produce a list of tools
+///
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct ListToolsFailure {
+    /// The index of the environment that failed
+    pub environment: usize,
+
+    /// The error returned by list-tools, or the value it returned if it was not a list of tools
+    pub error: Value
+}
+
+///
+/// The combined list of tools along with any environments that failed to list their tools
+///
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct ListToolsDiagnostics {
+    /// The merged list of tools from all of the environments that succeeded
+    pub tools: ListToolsResult,
+
+    /// The environments that failed
+    pub failures: Vec<ListToolsFailure>
+}
+
+///
+/// Describes which of the environments in a combined environment provides a tool
+///
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct ToolOrigin {
+    /// The index of the environment that the tool was found in
+    pub environment: usize
+}
+
 #[derive(Clone)]
 pub struct CombinedEnvironment<'a> {
     collection: Arc<Mutex<EnvironmentCollection<'a>>>
@@ -45,40 +78,86 @@ impl<'a> CombinedEnvironment<'a> {
     /// Combines the results of listing the tools across all of the environments
     ///
     pub fn list_tools(&self) -> ListToolsResult {
+        self.list_tools_with_diagnostics().tools
+    }
+
+    ///
+    /// Combines the results of listing the tools across all of the environments, reporting any
+    /// environments whose list-tools tool failed or returned something that was not a tool list
+    ///
+    /// Environments without a list-tools tool are not considered to have failed.
+    ///
+    pub fn list_tools_with_diagnostics(&self) -> ListToolsDiagnostics {
         let collection = self.collection.lock().unwrap();
 
         // List all of the tools in all the environments
-        let tools: Vec<Box<Tool>> = collection.environments.iter()
+        let tools: Vec<(usize, Box<Tool>)> = collection.environments.iter()
             .map(|env| env.get_json_tool(super::tool_name::LIST_TOOLS))
-            .filter(|tool| tool.is_ok())
-            .map(|tool| tool.unwrap())
+            .enumerate()
+            .filter(|&(_, ref tool)| tool.is_ok())
+            .map(|(index, tool)| (index, tool.unwrap()))
             .collect();
-        
-        CombinedEnvironment::collect_tool_list(tools.iter()) 
+
+        CombinedEnvironment::collect_tool_list_with_diagnostics(tools.iter().map(|&(index, ref tool)| (index, tool)))
+    }
+
+    ///
+    /// Retrieves a tool along with the index of the environment that it was found in
+    ///
+    pub fn get_json_tool_with_origin(&self, name: &str) -> Result<(usize, Box<Tool>), RetrieveToolError> {
+        // Return the first item in the collection that implements the specified tool name
+        let collection  = self.collection.lock().unwrap();
+        let item        = collection.environments.iter()
+            .map(|env| env.get_json_tool(name).ok())
+            .enumerate()
+            .find(|&(_, ref tool)| tool.is_some())
+            .map(|(index, tool)| (index, tool.unwrap()));
+
+        item.ok_or(RetrieveToolError::not_found())
+    }
+
+    ///
+    /// Finds the index of the environment that provides the tool with the specified name
+    ///
+    pub fn tool_origin(&self, name: &str) -> Option<ToolOrigin> {
+        self.get_json_tool_with_origin(name)
+            .ok()
+            .map(|(index, _)| ToolOrigin { environment: index })
     }
 
     ///
     /// Given a collection of list-tools implementations, returns the result of combining them all
     ///
     fn collect_tool_list<'b, T: Iterator<Item=&'b Box<Tool>>>(tools: T) -> ListToolsResult {
-        let results = tools.map(|tool| tool.invoke_json(Value::Null, &EmptyEnvironment::new()))
-            .filter(|result| result.is_ok())
-            .map(|result| result.unwrap());
+        CombinedEnvironment::collect_tool_list_with_diagnostics(tools.enumerate()).tools
+    }
 
-        let decoded = results.map(|result| from_value::<ListToolsResult>(result).unwrap_or(ListToolsResult::with_names(vec![])).names);
+    ///
+    /// Given a collection of list-tools implementations (along with the index of the environment
+    /// they came from), returns the result of combining them all and any failures that occurred
+    ///
+    fn collect_tool_list_with_diagnostics<'b, T: Iterator<Item=(usize, &'b Box<Tool>)>>(tools: T) -> ListToolsDiagnostics {
+        let mut final_result    = vec![];
+        let mut failures        = vec![];
+
+        for (index, tool) in tools {
+            let result = tool.invoke_json(Value::Null, &EmptyEnvironment::new())
+                .and_then(|result| from_value::<ListToolsResult>(result.clone()).map_err(|_| result));
 
-        // Concatentate the results
-        let mut final_result = vec![];
-        for list in decoded {
-            for name in list {
-                final_result.push(name.clone());
+            match result {
+                Ok(list)    => final_result.extend(list.names),
+                Err(error)  => failures.push(ListToolsFailure { environment: index, error: error })
             }
         }
 
+        // Concatenate the results
         final_result.sort();
         final_result.dedup();
 
-        ListToolsResult::with_name_strings(final_result)
+        ListToolsDiagnostics {
+            tools:      ListToolsResult::with_name_strings(final_result),
+            failures:   failures
+        }
     }
 }
 
@@ -97,23 +176,33 @@ impl<'a> Environment for CombinedEnvironment<'a> {
             let list_tools = make_pure_tool(move |_: ()| CombinedEnvironment::collect_tool_list(tools.iter()));
 
             Ok(Box::new(list_tools))
+        } else if name == super::tool_name::TOOL_ORIGIN {
+            // Tools can't refer back to this environment, so the origin is found from the environment the tool is invoked in
+            let tool_origin = make_dynamic_tool(|name: String, environment: &Environment| {
+                environment.tool_origin_index(&name)
+                    .map(|index| ToolOrigin { environment: index })
+                    .ok_or_else(|| json![{
+                        "error":        "Tool not found",
+                        "tool_name":    name
+                    }])
+            });
+
+            Ok(Box::new(tool_origin))
         } else {
-            // Return the first item in the collection that implements the specified tool name
-            let collection  = self.collection.lock().unwrap();
-            let item        = collection.environments.iter()
-                .map(|env| env.get_json_tool(name).ok())
-                .find(|env| env.is_some())
-                .map(|env| env.unwrap());
-
-            item.ok_or(RetrieveToolError::not_found())
+            self.get_json_tool_with_origin(name).map(|(_, tool)| tool)
         }
     }
+
+    fn tool_origin_index(&self, name: &str) -> Option<usize> {
+        self.tool_origin(name).map(|origin| origin.environment)
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
     use super::super::dynamic_environment::*;
+    use super::super::static_environment::*;
 
     #[test]
     fn can_find_tool_in_first_environment() {
@@ -184,4 +273,75 @@ mod test {
         assert!(combined.get_json_tool("list-tools").is_ok());
         assert!(combined.get_typed_tool("list-tools").unwrap().invoke((), &combined) == Ok(ListToolsResult::with_names(vec![ "define-tool", "list-tools", "tool", "undefine-tool" ])));
     }
+
+
+    #[test]
+    fn can_report_environments_that_fail_to_list_tools() {
+        let first   = DynamicEnvironment::new();
+        let second  = DynamicEnvironment::new();
+        let third   = DynamicEnvironment::new();
+
+        first.define("first-tool", Box::new(make_pure_tool(|x:i32| x+1)));
+        second.define("list-tools", Box::new(make_pure_tool(|_: ()| 42)));
+        third.define("list-tools", Box::new(make_tool(|_: ()| -> Result<(), &'static str> { Err("Failed") })));
+
+        let combined    = CombinedEnvironment::from_environments(vec![ &first, &second, &third ]);
+        let diagnostics = combined.list_tools_with_diagnostics();
+
+        assert!(diagnostics.tools == ListToolsResult::with_names(vec![ "define-tool", "first-tool", "list-tools", "undefine-tool" ]));
+        assert!(diagnostics.failures == vec![
+            ListToolsFailure { environment: 1, error: json![42] },
+            ListToolsFailure { environment: 2, error: json!["Failed"] }
+        ]);
+
+        assert!(combined.list_tools() == diagnostics.tools);
+    }
+
+    #[test]
+    fn reports_origin_of_tool_in_second_environment() {
+        let first   = DynamicEnvironment::new();
+        let second  = DynamicEnvironment::new();
+
+        first.define("first-tool", Box::new(make_pure_tool(|x:i32| x+1)));
+        second.define("second-tool", Box::new(make_pure_tool(|x:i32| x+2)));
+
+        let combined = CombinedEnvironment::from_environments(vec![ &first, &second ]);
+
+        assert!(combined.tool_origin("first-tool") == Some(ToolOrigin { environment: 0 }));
+        assert!(combined.tool_origin("second-tool") == Some(ToolOrigin { environment: 1 }));
+        assert!(combined.tool_origin("missing-tool") == None);
+        assert!(combined.get_json_tool_with_origin("second-tool").unwrap().1.invoke_json(json![2], &combined) == Ok(json![4]));
+    }
+
+    #[test]
+    fn tool_origin_tool_reports_first_environment_to_provide_tool() {
+        let first   = DynamicEnvironment::new();
+        let second  = DynamicEnvironment::new();
+
+        first.define("tool", Box::new(make_pure_tool(|x:i32| x+1)));
+        second.define("tool", Box::new(make_pure_tool(|x:i32| x+2)));
+        second.define("second-tool", Box::new(make_pure_tool(|x:i32| x+2)));
+
+        let combined    = CombinedEnvironment::from_environments(vec![ &first, &second ]);
+        let tool_origin = combined.get_json_tool("tool-origin").unwrap();
+
+        assert!(tool_origin.invoke_json(json!["tool"], &combined) == Ok(json![{ "environment": 0 }]));
+        assert!(tool_origin.invoke_json(json!["second-tool"], &combined) == Ok(json![{ "environment": 1 }]));
+        assert!(tool_origin.invoke_json(json!["missing-tool"], &combined).is_err());
+    }
+
+    #[test]
+    fn tool_origin_tool_finds_tools_in_environments_that_cannot_list_them() {
+        let first   = StaticEnvironment::from_tool("tool", Box::new(make_pure_tool(|x:i32| x+1)));
+        let second  = DynamicEnvironment::new();
+
+        second.define("tool", Box::new(make_pure_tool(|x:i32| x+2)));
+
+        let combined    = CombinedEnvironment::from_environments(vec![ &first, &second ]);
+        let tool_origin = combined.get_json_tool("tool-origin").unwrap();
+
+        // The static environment has no list-tools tool but still overrides the tool in the dynamic environment
+        assert!(combined.get_typed_tool("tool").unwrap().invoke(1, &combined) == Ok(2));
+        assert!(tool_origin.invoke_json(json!["tool"], &combined) == Ok(json![{ "environment": 0 }]));
+    }
 }
@@ -65,6 +65,10 @@ impl Tool for DynamicTool {
     fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
         self.tool.invoke_json(input, environment)
     }
+
+    fn describe(&self) -> Option<Value> {
+        self.tool.describe()
+    }
 }
 
 impl DynamicTool {
@@ -123,10 +127,14 @@ impl DynamicEnvironment {
     /// Imports a ToolSet into this environment
     ///
     pub fn import<TToolSet: ToolSet>(&self, toolset: TToolSet) {
-        for tool_and_name in toolset.create_tools(self) {
-            let (name, tool) = tool_and_name;
+        for (names, tool) in toolset.create_tools_with_aliases(self) {
+            // Every alias shares the same tool
+            let tool    = DynamicTool::new(tool);
+            let mut map = self.tools.lock().unwrap();
 
-            self.define(&name, tool);
+            for name in names {
+                map.tools.insert(name, tool.clone());
+            }
         }
     }
 
@@ -572,4 +580,50 @@ mod test {
 
         assert!(dynamic_env.list_tools() == ListToolsResult::with_names(vec![ "list-tools", "undefine-tool" ]))
     }
+
+    #[test]
+    fn aliases_share_the_same_tool() {
+        use std::sync::atomic::*;
+
+        struct CounterToolSet { }
+
+        impl ToolSet for CounterToolSet {
+            fn create_tools(self, environment: &Environment) -> Vec<(String, Box<Tool>)> {
+                expand_tool_aliases(self.create_tools_with_aliases(environment))
+            }
+
+            fn create_tools_with_aliases(self, _environment: &Environment) -> Vec<(Vec<String>, Box<Tool>)> {
+                let counter = AtomicUsize::new(0);
+
+                vec![
+                    (vec![ String::from("count"), String::from("tally") ], Box::new(make_pure_tool(move |_: ()| counter.fetch_add(1, Ordering::SeqCst)+1)))
+                ]
+            }
+        }
+
+        let dynamic_env = DynamicEnvironment::new();
+        dynamic_env.import(CounterToolSet { });
+
+        let count = dynamic_env.get_typed_tool::<(), usize>("count").unwrap();
+        let tally = dynamic_env.get_typed_tool::<(), usize>("tally").unwrap();
+
+        // Both names refer to the one counter
+        assert!(count.invoke((), &dynamic_env) == Ok(1));
+        assert!(tally.invoke((), &dynamic_env) == Ok(2));
+        assert!(count.invoke((), &dynamic_env) == Ok(3));
+
+        // Static environments and expanded toolsets also share the tool
+        let static_env  = StaticEnvironment::from_toolset(CounterToolSet { }, &dynamic_env);
+        let count       = static_env.get_typed_tool::<(), usize>("count").unwrap();
+        let tally       = static_env.get_typed_tool::<(), usize>("tally").unwrap();
+
+        assert!(tally.invoke((), &static_env) == Ok(1));
+        assert!(count.invoke((), &static_env) == Ok(2));
+
+        let expanded    = CounterToolSet { }.create_tools(&dynamic_env);
+
+        assert!(expanded.len() == 2);
+        assert!(expanded[1].1.invoke_json(Value::Null, &dynamic_env) == Ok(json![1]));
+        assert!(expanded[0].1.invoke_json(Value::Null, &dynamic_env) == Ok(json![2]));
+    }
 }
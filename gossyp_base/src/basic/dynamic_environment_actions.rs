@@ -10,19 +10,22 @@ use super::functional_tool::*;
 use super::dynamic_environment::*;
 use super::static_environment::*;
 
+///
+/// Generates the JSON error for a tool that couldn't be retrieved from an environment
+///
+pub fn tool_not_found_error(tool_name: &str, retrieve_error: &RetrieveToolError) -> Value {
+    json![{
+        "error":        "Tool not found",
+        "tool_name":    tool_name,
+        "description":  retrieve_error.message()
+    }]
+}
+
 ///
 /// Retrieves a tool, or returns a JSON error indicating it doesn't exist
 ///
-#[inline]
-fn get_tool(env: &Environment, tool_name: &str) -> Result<Box<Tool>, Value> {
-    match env.get_json_tool(tool_name) {
-        Ok(tool)            => Ok(tool),
-        Err(retrieve_error) => Err(json![{
-            "error":        "Tool not found",
-            "tool_name":    tool_name,
-            "description":  retrieve_error.message()
-        }])
-    }
+pub fn get_tool_or_error(env: &Environment, tool_name: &str) -> Result<Box<Tool>, Value> {
+    env.get_json_tool(tool_name).map_err(|retrieve_error| tool_not_found_error(tool_name, &retrieve_error))
 }
 
 ///
@@ -30,7 +33,7 @@ fn get_tool(env: &Environment, tool_name: &str) -> Result<Box<Tool>, Value> {
 ///
 pub fn define_new_tool(environment: &Environment, new_tool_name: &str, tool: Box<Tool>) -> Result<(), Value> {
     // Fetch the define tool for the currnet environment
-    let define_tool         = TypedTool::<DefineToolInput, ()>::from(get_tool(environment, DEFINE_TOOL)?);
+    let define_tool         = TypedTool::<DefineToolInput, ()>::from(get_tool_or_error(environment, DEFINE_TOOL)?);
 
     // Put the tool we want to define in its own environment so we can pass it to the define tool
     let source_environment  = StaticEnvironment::from_tool(new_tool_name, tool);
@@ -46,7 +49,7 @@ pub fn define_new_tool(environment: &Environment, new_tool_name: &str, tool: Box
 ///
 pub fn alias_tool(environment: &Environment, old_tool_name: &str, new_tool_name: &str) -> Result<(), Value> {
     // Fetch the define tool for the currnet environment
-    let define_tool = TypedTool::<DefineToolInput, ()>::from(get_tool(environment, DEFINE_TOOL)?);
+    let define_tool = TypedTool::<DefineToolInput, ()>::from(get_tool_or_error(environment, DEFINE_TOOL)?);
 
     // Perform aliasing
     define_tool.invoke(DefineToolInput::new(old_tool_name, Some(new_tool_name)), environment)?;
@@ -59,7 +62,7 @@ pub fn alias_tool(environment: &Environment, old_tool_name: &str, new_tool_name:
 ///
 pub fn undefine_tool(environment: &Environment, old_tool_name: &str) -> Result<bool, Value> {
     // Fetch the define tool for the currnet environment
-    let undefine_tool = TypedTool::<UndefineToolInput, bool>::from(get_tool(environment, UNDEFINE_TOOL)?);
+    let undefine_tool = TypedTool::<UndefineToolInput, bool>::from(get_tool_or_error(environment, UNDEFINE_TOOL)?);
 
     // Remove the tool
     Ok(undefine_tool.invoke(UndefineToolInput::new(old_tool_name), environment)?)
@@ -85,6 +88,19 @@ where for<'de> TIn: Deserialize<'de> {
 mod test {
     use super::*;
 
+    #[test]
+    fn missing_tool_is_json_error() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "test", |x: i32| x+1).is_ok());
+
+        assert!(get_tool_or_error(&env, "test").is_ok());
+        assert!(get_tool_or_error(&env, "missing").err() == Some(json![{
+            "error":        "Tool not found",
+            "tool_name":    "missing",
+            "description":  "Tool not found"
+        }]));
+    }
+
     #[test]
     fn can_define_new_tool_using_convenience_function() {
         let new_tool = make_pure_tool(|x: i32| x+1);
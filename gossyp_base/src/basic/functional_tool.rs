@@ -1,5 +1,6 @@
 use std::result::Result;
 use std::marker::PhantomData;
+use std::any::type_name;
 use std::error::Error;
 use serde::*;
 use serde_json::*;
@@ -12,7 +13,10 @@ use super::super::environment::*;
 ///
 pub struct FnTool<TIn, TOut: Serialize, TErr: Serialize> 
 where for<'de> TIn: Deserialize<'de> {
-    function: Box<Fn(TIn, &Environment) -> Result<TOut, TErr> + Send + Sync>
+    function: Box<Fn(TIn, &Environment) -> Result<TOut, TErr> + Send + Sync>,
+
+    /// For strict tools, finds the first field in the input that was not used when decoding it
+    find_unknown_field: Option<Box<Fn(&Value, &TIn) -> Option<String> + Send + Sync>>
 }
 
 ///
@@ -20,7 +24,7 @@ where for<'de> TIn: Deserialize<'de> {
 ///
 pub fn make_dynamic_tool<TIn, TOut: Serialize, TErr: Serialize, F: 'static+Send+Sync+Fn(TIn, &Environment) -> Result<TOut, TErr>>(function: F) -> FnTool<TIn, TOut, TErr>
 where for<'de> TIn: Deserialize<'de> {
-    FnTool { function: Box::new(function) }
+    FnTool { function: Box::new(function), find_unknown_field: None }
 }
 
 ///
@@ -39,12 +43,85 @@ where for<'de> TIn: Deserialize<'de> {
     make_dynamic_tool(move |input, _| Ok(function(input)))
 }
 
+///
+/// Creates a Tool from a function that can produce an error, and which rejects any input with fields it does not use
+///
+pub fn make_strict_tool<TIn: 'static+Serialize, TOut: Serialize, TErr: Serialize, F: 'static+Send+Sync+Fn(TIn) -> Result<TOut, TErr>>(function: F) -> FnTool<TIn, TOut, TErr> 
+where for<'de> TIn: Deserialize<'de> {
+    make_tool(function).strict()
+}
+
+///
+/// Finds the first field in an input value that does not appear in the value it decoded to
+///
+fn find_unknown_field(input: &Value, decoded: &Value) -> Option<String> {
+    match (input, decoded) {
+        (&Value::Object(ref input), &Value::Object(ref decoded)) => {
+            for (key, value) in input.iter() {
+                match decoded.get(key) {
+                    None                => return Some(key.clone()),
+                    Some(decoded_value) => if let Some(field) = find_unknown_field(value, decoded_value) {
+                        return Some(format!("{}.{}", key, field));
+                    }
+                }
+            }
+
+            None
+        },
+
+        (&Value::Array(ref input), &Value::Array(ref decoded)) => {
+            input.iter().zip(decoded.iter())
+                .enumerate()
+                .filter_map(|(index, (value, decoded_value))| find_unknown_field(value, decoded_value).map(|field| format!("{}.{}", index, field)))
+                .nth(0)
+        },
+
+        _ => None
+    }
+}
+
+impl<TIn, TOut, TErr> FnTool<TIn, TOut, TErr>
+where for<'de> TIn: Deserialize<'de>, TIn: 'static+Serialize, TOut: Serialize, TErr: Serialize {
+    ///
+    /// Converts this tool into one that rejects input containing fields that are not used when it is decoded
+    ///
+    /// Normally, fields that are not part of the input type are ignored, so a misspelled field name
+    /// goes unnoticed. The check works by comparing the input to the result of encoding the decoded
+    /// value again, so types that don't encode all of their fields will be rejected too.
+    ///
+    pub fn strict(self) -> FnTool<TIn, TOut, TErr> {
+        FnTool {
+            function:           self.function,
+            find_unknown_field: Some(Box::new(|input, decoded| {
+                to_value(decoded).ok().and_then(|decoded| find_unknown_field(input, &decoded))
+            }))
+        }
+    }
+}
+
 impl<TIn, TOut, TErr> Tool for FnTool<TIn, TOut, TErr> 
 where for<'de> TIn: Deserialize<'de>, TOut: Serialize, TErr: Serialize {
     fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        // Strict tools need the original input to check for unknown fields
+        let original_input = if self.find_unknown_field.is_some() { Some(input.clone()) } else { None };
+
         // Decode
         let input_decoded = from_value::<TIn>(input);
 
+        // Reject fields that aren't used by the input type for strict tools
+        let unknown_field = match (&self.find_unknown_field, &original_input, &input_decoded) {
+            (&Some(ref find_unknown_field), &Some(ref original_input), &Ok(ref input_decoded))  => find_unknown_field(original_input, input_decoded),
+            _                                                                                   => None
+        };
+
+        if let Some(unknown_field) = unknown_field {
+            return Err(json![{
+                "error":        "JSON input decode failed",
+                "description":  format!("unknown field `{}`", unknown_field),
+                "field":        unknown_field
+            }]);
+        }
+
         // Chain into the tool itself
         match input_decoded {
             Ok(input_decoded) => {
@@ -60,7 +137,9 @@ where for<'de> TIn: Deserialize<'de>, TOut: Serialize, TErr: Serialize {
                             Ok(final_value) => Ok(final_value),
                             Err(erm)        => Err(json![{
                                 "error":        "JSON encode failed",
-                                "description":  erm.description()
+                                "description":  erm.description(),
+                                "type":         type_name::<TOut>(),
+                                "message":      erm.to_string()
                             }])
                         }
                     },
@@ -71,7 +150,9 @@ where for<'de> TIn: Deserialize<'de>, TOut: Serialize, TErr: Serialize {
                             Ok(final_value) => Err(final_value),
                             Err(erm)        => Err(json![{
                                 "error":        "Error encode failed",
-                                "description":  erm.description()
+                                "description":  erm.description(),
+                                "type":         type_name::<TErr>(),
+                                "message":      erm.to_string()
                             }])
                         }
                     }
@@ -229,4 +310,76 @@ mod test {
         let should_be_error = tool.invoke_json(json![ 1 ], &environment);
         assert!(should_be_error == Err(json!["Oops"]));
     }
+
+    #[test]
+    fn encode_failure_reports_output_type() {
+        use std::collections::BTreeMap;
+
+        // Maps with non-string keys can't be represented as JSON
+        let bad_output = make_pure_tool(|_: ()| {
+            let mut map = BTreeMap::new();
+            map.insert(vec![ 1 ], 1);
+            map
+        });
+        let result = bad_output.invoke_json(Value::Null, &EmptyEnvironment::new());
+
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert!(error["error"] == json!["JSON encode failed"]);
+        assert!(error["type"].as_str().unwrap().contains("BTreeMap"));
+        assert!(error["message"].as_str().unwrap().len() > 0);
+    }
+
+    #[test]
+    fn error_encode_failure_reports_error_type() {
+        use std::collections::BTreeMap;
+
+        let bad_error = make_tool(|_: ()| -> Result<(), BTreeMap<Vec<i32>, i32>> {
+            let mut map = BTreeMap::new();
+            map.insert(vec![ 1 ], 1);
+            Err(map)
+        });
+        let result = bad_error.invoke_json(Value::Null, &EmptyEnvironment::new());
+
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert!(error["error"] == json!["Error encode failed"]);
+        assert!(error["type"].as_str().unwrap().contains("BTreeMap"));
+    }
+
+
+    #[test]
+    fn strict_tool_rejects_unknown_fields() {
+        let tool        = make_strict_tool(test_tool);
+        let environment = EmptyEnvironment::new();
+
+        let result      = tool.invoke_json(json![{ "input": 4, "inptu": 5 }], &environment);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err()["field"] == json!["inptu"]);
+    }
+
+    #[test]
+    fn strict_tool_accepts_known_fields() {
+        let tool        = make_strict_tool(test_tool);
+        let environment = EmptyEnvironment::new();
+
+        assert!(tool.invoke_json(json![{ "input": 4 }], &environment) == Ok(json![{ "output": 5 }]));
+    }
+
+    #[test]
+    fn strict_tool_finds_nested_unknown_fields() {
+        let tool        = make_pure_tool(|x: Vec<TestIn>| x.len()).strict();
+        let environment = EmptyEnvironment::new();
+
+        assert!(tool.invoke_json(json![[ { "input": 1 }, { "input": 2, "extra": 3 } ]], &environment).unwrap_err()["field"] == json!["1.extra"]);
+    }
+
+    #[test]
+    fn non_strict_tool_ignores_unknown_fields() {
+        let tool        = make_tool(test_tool);
+        let environment = EmptyEnvironment::new();
+
+        assert!(tool.invoke_json(json![{ "input": 4, "inptu": 5 }], &environment) == Ok(json![{ "output": 5 }]));
+    }
 }
@@ -0,0 +1,118 @@
+//!
+//! Map input environment
+//!
+//! This wraps another environment and transforms the input of every tool before it is invoked.
+//!
+
+use std::result::Result;
+use std::sync::*;
+use serde_json::*;
+
+use super::super::tool::*;
+use super::super::environment::*;
+
+///
+/// Environment that transforms the input of every tool retrieved from another environment
+///
+/// The transformation is passed the name of the tool as well as its input, so different tools can
+/// be transformed in different ways. The output of the tools is passed through unchanged.
+///
+pub struct MapInputEnvironment<'a, TFn> {
+    environment:    &'a Environment,
+    transform:      Arc<TFn>
+}
+
+///
+/// Tool that transforms its input before passing it on to another tool
+///
+struct MapInputTool<TFn> {
+    name:       String,
+    tool:       Box<Tool>,
+    transform:  Arc<TFn>
+}
+
+impl<'a, TFn> MapInputEnvironment<'a, TFn>
+where TFn: 'static+Send+Sync+Fn(&str, Value) -> Value {
+    ///
+    /// Creates a new environment that applies a transformation to the input of the tools in another environment
+    ///
+    pub fn new(environment: &'a Environment, transform: TFn) -> MapInputEnvironment<'a, TFn> {
+        MapInputEnvironment { environment: environment, transform: Arc::new(transform) }
+    }
+}
+
+impl<TFn> Tool for MapInputTool<TFn>
+where TFn: 'static+Send+Sync+Fn(&str, Value) -> Value {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let transformed = (self.transform)(&self.name, input);
+        self.tool.invoke_json(transformed, environment)
+    }
+}
+
+impl<'a, TFn> Environment for MapInputEnvironment<'a, TFn>
+where TFn: 'static+Send+Sync+Fn(&str, Value) -> Value {
+    fn get_json_tool(&self, name: &str) -> Result<Box<Tool>, RetrieveToolError> {
+        let tool = self.environment.get_json_tool(name)?;
+
+        Ok(Box::new(MapInputTool {
+            name:       String::from(name),
+            tool:       tool,
+            transform:  self.transform.clone()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::dynamic_environment::*;
+    use super::super::functional_tool::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct Greeting {
+        name: String,
+        greeting: String
+    }
+
+    fn add_default_greeting(name: &str, input: Value) -> Value {
+        match (name, input) {
+            ("greet", Value::Object(mut fields)) => {
+                if !fields.contains_key("greeting") {
+                    fields.insert(String::from("greeting"), Value::String(String::from("Hello")));
+                }
+                Value::Object(fields)
+            },
+
+            (_, other) => other
+        }
+    }
+
+    #[test]
+    fn transform_is_applied_to_input() {
+        let base = DynamicEnvironment::new();
+        base.define("greet", Box::new(make_pure_tool(|greeting: Greeting| format!("{}, {}", greeting.greeting, greeting.name))));
+
+        let env = MapInputEnvironment::new(&base, add_default_greeting);
+
+        assert!(env.get_typed_tool("greet").unwrap().invoke(json![{ "name": "World" }], &env) == Ok(String::from("Hello, World")));
+        assert!(env.get_typed_tool("greet").unwrap().invoke(json![{ "name": "World", "greeting": "Hi" }], &env) == Ok(String::from("Hi, World")));
+    }
+
+    #[test]
+    fn output_is_unchanged() {
+        let base = DynamicEnvironment::new();
+        base.define("add-one", Box::new(make_pure_tool(|x: i32| x+1)));
+
+        let env = MapInputEnvironment::new(&base, |_: &str, input: Value| json![input.as_i64().unwrap()*10]);
+
+        assert!(env.get_typed_tool("add-one").unwrap().invoke(2, &env) == Ok(21));
+    }
+
+    #[test]
+    fn missing_tools_are_not_found() {
+        let base    = DynamicEnvironment::new();
+        let env     = MapInputEnvironment::new(&base, |_: &str, input: Value| input);
+
+        assert!(env.get_json_tool("missing").is_err());
+    }
+}
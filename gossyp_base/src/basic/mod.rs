@@ -6,8 +6,14 @@ pub mod empty_environment;
 pub mod static_environment;
 pub mod dynamic_environment;
 pub mod combined_environment;
+pub mod map_input_environment;
+pub mod object_only;
+pub mod pipe;
+pub mod timeout;
+pub mod tools_iter;
 pub mod tool_name;
 pub mod list_tools;
+pub mod truthy;
 pub mod dynamic_environment_actions;
 
 pub use self::functional_tool::*;
@@ -19,4 +25,10 @@ pub use self::static_environment::*;
 pub use self::dynamic_environment::*;
 pub use self::list_tools::*;
 pub use self::combined_environment::*;
+pub use self::map_input_environment::*;
+pub use self::object_only::*;
+pub use self::pipe::*;
+pub use self::timeout::*;
+pub use self::tools_iter::*;
+pub use self::truthy::*;
 pub use self::dynamic_environment_actions::*;
@@ -0,0 +1,108 @@
+//!
+//! Object-only tools
+//!
+//! This wraps another tool and rejects any input (and optionally any output) that isn't a JSON object.
+//!
+
+use std::result::Result;
+use serde_json::*;
+
+use super::super::tool::*;
+use super::super::environment::*;
+
+///
+/// Tool that only accepts objects as input
+///
+/// Most tools take an object containing their parameters: passing something else usually results
+/// in an error from the JSON decoder that is hard to understand. This produces a clearer error
+/// without needing a full description of what the input should be.
+///
+pub struct ObjectOnlyTool<TTool: Tool> {
+    tool:           TTool,
+    check_output:   bool
+}
+
+///
+/// Creates a tool that only accepts objects as input before passing them on to another tool
+///
+pub fn object_only<TTool: Tool>(tool: TTool) -> ObjectOnlyTool<TTool> {
+    ObjectOnlyTool { tool: tool, check_output: false }
+}
+
+///
+/// Returns a short description of the type of a JSON value (used in error messages)
+///
+fn value_type(value: &Value) -> &'static str {
+    match value {
+        &Value::Null        => "null",
+        &Value::Bool(_)     => "boolean",
+        &Value::Number(_)   => "number",
+        &Value::String(_)   => "string",
+        &Value::Array(_)    => "array",
+        &Value::Object(_)   => "object"
+    }
+}
+
+impl<TTool: Tool> ObjectOnlyTool<TTool> {
+    ///
+    /// Converts this tool into one that also generates an error if the wrapped tool doesn't return an object
+    ///
+    pub fn with_object_output(self) -> ObjectOnlyTool<TTool> {
+        ObjectOnlyTool { tool: self.tool, check_output: true }
+    }
+}
+
+impl<TTool: Tool> Tool for ObjectOnlyTool<TTool> {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        if !input.is_object() {
+            return Err(json![{
+                "error":    "Expected object input",
+                "found":    value_type(&input)
+            }]);
+        }
+
+        let output = self.tool.invoke_json(input, environment)?;
+
+        if self.check_output && !output.is_object() {
+            Err(json![{
+                "error":    "Expected object output",
+                "found":    value_type(&output)
+            }])
+        } else {
+            Ok(output)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::functional_tool::*;
+    use super::super::empty_environment::*;
+
+    #[test]
+    fn accepts_object_input() {
+        let environment = EmptyEnvironment::new();
+        let tool        = object_only(make_pure_tool(|x: Value| x));
+
+        assert!(tool.invoke_json(json![{ "a": 1 }], &environment) == Ok(json![{ "a": 1 }]));
+    }
+
+    #[test]
+    fn rejects_scalar_input() {
+        let environment = EmptyEnvironment::new();
+        let tool        = object_only(make_pure_tool(|x: Value| x));
+
+        assert!(tool.invoke_json(json![ 1 ], &environment) == Err(json![{ "error": "Expected object input", "found": "number" }]));
+    }
+
+    #[test]
+    fn output_is_only_checked_if_requested() {
+        let environment = EmptyEnvironment::new();
+        let unchecked   = object_only(make_pure_tool(|_: Value| 42));
+        let checked     = object_only(make_pure_tool(|_: Value| 42)).with_object_output();
+
+        assert!(unchecked.invoke_json(json![{}], &environment) == Ok(json![ 42 ]));
+        assert!(checked.invoke_json(json![{}], &environment) == Err(json![{ "error": "Expected object output", "found": "number" }]));
+    }
+}
@@ -0,0 +1,70 @@
+//!
+//! Pipe tools
+//!
+//! A pipe passes its input through a series of tools, where the output of each tool is the input of the next.
+//!
+
+use std::result::Result;
+use serde_json::*;
+
+use super::super::tool::*;
+use super::super::environment::*;
+
+///
+/// Tool that passes its input through a series of tools
+///
+/// The result is the output of the last tool. If any of the tools produce an error, the later
+/// tools aren't invoked and the error is the result. A pipe with no tools returns its input.
+///
+pub struct PipeTool {
+    stages: Vec<Box<Tool>>
+}
+
+///
+/// Creates a tool that passes its input through a series of tools
+///
+pub fn pipe(stages: Vec<Box<Tool>>) -> PipeTool {
+    PipeTool { stages: stages }
+}
+
+impl Tool for PipeTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let mut value = input;
+
+        for stage in self.stages.iter() {
+            value = stage.invoke_json(value, environment)?;
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::functional_tool::*;
+    use super::super::empty_environment::*;
+
+    #[test]
+    fn stages_run_in_order() {
+        let environment = EmptyEnvironment::new();
+        let tool        = pipe(vec![ Box::new(make_pure_tool(|x: i32| x+1)), Box::new(make_pure_tool(|x: i32| x*2)) ]);
+
+        assert!(tool.invoke_json(json![ 3 ], &environment) == Ok(json![ 8 ]));
+    }
+
+    #[test]
+    fn error_stops_pipe() {
+        let environment = EmptyEnvironment::new();
+        let tool        = pipe(vec![ Box::new(make_tool(|_: i32| -> Result<i32, &'static str> { Err("Failed") })), Box::new(make_pure_tool(|x: i32| x*2)) ]);
+
+        assert!(tool.invoke_json(json![ 3 ], &environment) == Err(json![ "Failed" ]));
+    }
+
+    #[test]
+    fn empty_pipe_returns_input() {
+        let environment = EmptyEnvironment::new();
+
+        assert!(pipe(vec![]).invoke_json(json![ 3 ], &environment) == Ok(json![ 3 ]));
+    }
+}
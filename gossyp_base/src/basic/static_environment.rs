@@ -31,6 +31,10 @@ impl Tool for StaticEnvironmentTool {
     fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
         (**self.tool).invoke_json(input, environment)
     }
+
+    fn describe(&self) -> Option<Value> {
+        (**self.tool).describe()
+    }
 }
 
 impl Environment for StaticEnvironment {
@@ -52,13 +56,15 @@ impl StaticEnvironment {
     /// Creates a new static environment from a toolset
     ///
     pub fn from_toolset<T: ToolSet>(set: T, environment: &Environment) -> StaticEnvironment {
-        let tools           = set.create_tools(environment);
+        let tools           = set.create_tools_with_aliases(environment);
         let mut tool_hash   = HashMap::new();
 
-        for tool_and_name in tools {
-            let (name, tool) = tool_and_name;
-            
-            tool_hash.insert(name, Arc::new(tool));
+        for (names, tool) in tools {
+            let tool = Arc::new(tool);
+
+            for name in names {
+                tool_hash.insert(name, tool.clone());
+            }
         }
 
         StaticEnvironment { tools: tool_hash }
@@ -120,4 +126,15 @@ mod test {
         assert!(add1.invoke_json(json![ 2 ], &environment) == Ok(json![ 3 ]));
         assert!(add2.invoke_json(json![ 2 ], &environment) == Ok(json![ 4 ]));
     }
+
+    #[test]
+    fn has_tool_reports_defined_tools() {
+        let toolset = BasicToolSet::from(vec![
+            ("add-1", make_pure_tool(|x: i32| { x+1 }))
+        ]);
+        let environment = StaticEnvironment::from_toolset(toolset, &EmptyEnvironment::new());
+
+        assert!(environment.has_tool("add-1"));
+        assert!(!environment.has_tool("add-2"));
+    }
 }
@@ -0,0 +1,201 @@
+//!
+//! Timeouts
+//!
+//! This provides a tool that gives up on another tool if it takes too long, and an environment
+//! that applies a timeout to every tool it contains.
+//!
+
+use std::thread;
+use std::sync::*;
+use std::sync::mpsc::*;
+use std::time::Duration;
+use std::result::Result;
+use std::collections::HashMap;
+use serde_json::*;
+
+use super::super::tool::*;
+use super::super::environment::*;
+
+///
+/// Tool that produces an error if another tool doesn't finish within a certain time
+///
+/// The tool is run on a separate thread. Rust provides no way to stop a thread, so a tool that
+/// times out carries on running in the background and its result is discarded when it finishes:
+/// any side effects that it has will still happen. As the tool can outlive the call, it can't
+/// use the environment it's invoked in: instead, it is always invoked with the environment
+/// supplied when the timeout tool was created.
+///
+pub struct TimeoutTool {
+    tool:           Arc<Box<Tool>>,
+    timeout:        Duration,
+    environment:    Arc<Environment>
+}
+
+///
+/// Creates a tool that produces an error if another tool doesn't finish within a certain time
+///
+pub fn with_timeout(tool: Box<Tool>, timeout: Duration, environment: Arc<Environment>) -> TimeoutTool {
+    TimeoutTool { tool: Arc::new(tool), timeout: timeout, environment: environment }
+}
+
+impl TimeoutTool {
+    ///
+    /// The timeout for this tool in milliseconds
+    ///
+    fn millis(&self) -> u64 {
+        self.timeout.as_secs()*1000 + (self.timeout.subsec_nanos()/1_000_000) as u64
+    }
+}
+
+impl Tool for TimeoutTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let (send_result, receive_result) = channel();
+
+        // Run the tool in the background
+        let tool        = self.tool.clone();
+        let environment = self.environment.clone();
+
+        thread::spawn(move || {
+            // The receiver will have gone away if the tool timed out
+            send_result.send(tool.invoke_json(input, &*environment)).ok();
+        });
+
+        // Wait for it to finish
+        match receive_result.recv_timeout(self.timeout) {
+            Ok(result)  => result,
+            Err(_)      => Err(json![{
+                "error":    "Tool timed out",
+                "millis":   self.millis()
+            }])
+        }
+    }
+
+    ///
+    /// The description of a timeout tool has the timeout and the description of the tool it wraps
+    ///
+    fn describe(&self) -> Option<Value> {
+        Some(json![{
+            "description":  format!("Gives up if the tool takes longer than {}ms", self.millis()),
+            "millis":       self.millis(),
+            "wraps":        self.tool.describe()
+        }])
+    }
+}
+
+///
+/// Environment that applies a timeout to every tool in another environment
+///
+/// The tools are created using `with_timeout`, so they are invoked in the base environment and
+/// keep running in the background if they time out.
+///
+pub struct TimeoutEnvironment {
+    base:               Arc<Environment>,
+    default_timeout:    Duration,
+    tool_timeouts:      HashMap<String, Duration>
+}
+
+impl TimeoutEnvironment {
+    ///
+    /// Creates a new environment that applies a timeout to the tools in another environment
+    ///
+    pub fn new(base: Arc<Environment>, default_timeout: Duration) -> TimeoutEnvironment {
+        TimeoutEnvironment { base: base, default_timeout: default_timeout, tool_timeouts: HashMap::new() }
+    }
+
+    ///
+    /// Uses different timeouts for some tools (the map goes from tool names to their timeouts)
+    ///
+    pub fn with_tool_timeouts(self, tool_timeouts: HashMap<String, Duration>) -> TimeoutEnvironment {
+        TimeoutEnvironment { base: self.base, default_timeout: self.default_timeout, tool_timeouts: tool_timeouts }
+    }
+}
+
+impl Environment for TimeoutEnvironment {
+    fn get_json_tool(&self, name: &str) -> Result<Box<Tool>, RetrieveToolError> {
+        let tool    = self.base.get_json_tool(name)?;
+        let timeout = self.tool_timeouts.get(name).cloned().unwrap_or(self.default_timeout);
+
+        Ok(Box::new(with_timeout(tool, timeout, self.base.clone())))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::functional_tool::*;
+    use super::super::dynamic_environment::*;
+
+    ///
+    /// A tool that has a description
+    ///
+    struct DescribedTool;
+
+    impl Tool for DescribedTool {
+        fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+            Ok(input)
+        }
+
+        fn describe(&self) -> Option<Value> {
+            Some(json![{ "description": "Returns its input" }])
+        }
+    }
+
+    fn test_environment() -> Arc<Environment> {
+        let env = DynamicEnvironment::new();
+
+        env.define("fast", Box::new(make_pure_tool(|x: i32| x+1)));
+        env.define("slow", Box::new(make_pure_tool(|x: i32| { thread::sleep(Duration::from_millis(500)); x+1 })));
+
+        Arc::new(env)
+    }
+
+    #[test]
+    fn fast_tool_succeeds() {
+        let environment = TimeoutEnvironment::new(test_environment(), Duration::from_millis(200));
+        let tool        = environment.get_json_tool("fast").unwrap();
+
+        assert!(tool.invoke_json(json![ 1 ], &environment) == Ok(json![ 2 ]));
+    }
+
+    #[test]
+    fn slow_tool_times_out() {
+        let environment = TimeoutEnvironment::new(test_environment(), Duration::from_millis(50));
+        let tool        = environment.get_json_tool("slow").unwrap();
+
+        assert!(tool.invoke_json(json![ 1 ], &environment) == Err(json![{ "error": "Tool timed out", "millis": 50 }]));
+    }
+
+    #[test]
+    fn can_override_timeout_for_tool() {
+        let mut timeouts = HashMap::new();
+        timeouts.insert(String::from("slow"), Duration::from_millis(5000));
+
+        let environment = TimeoutEnvironment::new(test_environment(), Duration::from_millis(50)).with_tool_timeouts(timeouts);
+        let tool        = environment.get_json_tool("slow").unwrap();
+
+        assert!(tool.invoke_json(json![ 1 ], &environment) == Ok(json![ 2 ]));
+    }
+
+    #[test]
+    fn description_includes_wrapped_tool() {
+        let tool = with_timeout(Box::new(DescribedTool), Duration::from_millis(50), test_environment());
+
+        assert!(tool.describe() == Some(json![{
+            "description":  "Gives up if the tool takes longer than 50ms",
+            "millis":       50,
+            "wraps":        { "description": "Returns its input" }
+        }]));
+    }
+
+    #[test]
+    fn environment_forwards_descriptions() {
+        let base = DynamicEnvironment::new();
+        base.define("described", Box::new(DescribedTool));
+
+        let environment = TimeoutEnvironment::new(Arc::new(base), Duration::from_millis(50));
+        let description = environment.get_json_tool("described").unwrap().describe().unwrap();
+
+        assert!(description["millis"] == json![ 50 ]);
+        assert!(description["wraps"] == json![{ "description": "Returns its input" }]);
+    }
+}
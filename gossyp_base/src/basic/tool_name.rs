@@ -6,3 +6,6 @@ pub const DEFINE_TOOL: &'static str = "define-tool";
 
 /// Tool that removes a tool from the source environment
 pub const UNDEFINE_TOOL: &'static str = "undefine-tool";
+
+/// Tool that reports which of the environments in a combined environment provides a tool
+pub const TOOL_ORIGIN: &'static str = "tool-origin";
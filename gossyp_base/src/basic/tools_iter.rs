@@ -0,0 +1,122 @@
+//!
+//! Iterating over the tools in an environment
+//!
+
+use std::vec;
+use serde_json::*;
+
+use super::tool_name::*;
+use super::list_tools::*;
+use super::super::tool::*;
+use super::super::environment::*;
+
+///
+/// Iterator that retrieves the tools in an environment one at a time
+///
+/// The names of the tools are fetched from `list-tools` when the iterator is created, but the
+/// tools themselves are only retrieved as the iterator reaches them. Names that can no longer be
+/// retrieved by then (because the tool has been undefined, for example) are skipped.
+///
+pub struct EnvironmentTools<'a> {
+    environment:    &'a Environment,
+    names:          vec::IntoIter<String>
+}
+
+///
+/// Extension trait for environments that makes it possible to iterate over the tools they contain
+///
+pub trait EnvironmentToolsIter {
+    ///
+    /// Returns an iterator over the names and tools in this environment
+    ///
+    /// This is empty if the environment doesn't have a `list-tools` tool.
+    ///
+    fn tools_iter<'a>(&'a self) -> EnvironmentTools<'a>;
+}
+
+impl<'a> EnvironmentTools<'a> {
+    ///
+    /// Creates an iterator over the tools currently listed by an environment
+    ///
+    fn new(environment: &'a Environment) -> EnvironmentTools<'a> {
+        let names = environment.get_json_tool(LIST_TOOLS).ok()
+            .and_then(|list_tools| list_tools.invoke_json(Value::Null, environment).ok())
+            .and_then(|names| from_value::<ListToolsResult>(names).ok())
+            .map(|list| list.names)
+            .unwrap_or_else(|| vec![]);
+
+        EnvironmentTools { environment, names: names.into_iter() }
+    }
+}
+
+impl<TEnv> EnvironmentToolsIter for TEnv where TEnv: Environment {
+    fn tools_iter<'a>(&'a self) -> EnvironmentTools<'a> {
+        EnvironmentTools::new(self)
+    }
+}
+
+impl<'b> EnvironmentToolsIter for Environment+'b {
+    fn tools_iter<'a>(&'a self) -> EnvironmentTools<'a> {
+        EnvironmentTools::new(self)
+    }
+}
+
+impl<'a> Iterator for EnvironmentTools<'a> {
+    type Item = (String, Box<Tool>);
+
+    fn next(&mut self) -> Option<(String, Box<Tool>)> {
+        while let Some(name) = self.names.next() {
+            if let Ok(tool) = self.environment.get_json_tool(&name) {
+                return Some((name, tool));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::functional_tool::*;
+    use super::super::dynamic_environment::*;
+    use super::super::empty_environment::*;
+
+    #[test]
+    fn yields_each_tool_once() {
+        let env = DynamicEnvironment::new();
+        env.define("add-one", Box::new(make_pure_tool(|x: i32| x+1)));
+        env.define("double", Box::new(make_pure_tool(|x: i32| x*2)));
+
+        let mut names: Vec<String> = env.tools_iter().map(|(name, _tool)| name).collect();
+        names.sort();
+
+        assert!(names == vec![ "add-one", "define-tool", "double", "list-tools", "undefine-tool" ]);
+    }
+
+    #[test]
+    fn tools_can_be_invoked() {
+        let env = DynamicEnvironment::new();
+        env.define("add-one", Box::new(make_pure_tool(|x: i32| x+1)));
+
+        let (_, add_one) = env.tools_iter().find(|&(ref name, _)| name == "add-one").unwrap();
+
+        assert!(add_one.invoke_json(json![ 1 ], &env) == Ok(json![ 2 ]));
+    }
+
+    #[test]
+    fn environment_without_list_tools_is_empty() {
+        let env = EmptyEnvironment::new();
+
+        assert!(env.tools_iter().count() == 0);
+    }
+
+    #[test]
+    fn can_iterate_over_environment_reference() {
+        let env                         = DynamicEnvironment::new();
+        env.define("add-one", Box::new(make_pure_tool(|x: i32| x+1)));
+        let environment: &Environment   = &env;
+
+        assert!(environment.tools_iter().any(|(name, _tool)| name == "add-one"));
+    }
+}
@@ -3,6 +3,7 @@
 //! initialising new environments.
 //!
 
+use std::sync::Arc;
 use std::result::Result;
 use serde_json::*;
 
@@ -27,6 +28,62 @@ pub trait ToolSet {
     /// Creates the tools in this toolset
     ///
     fn create_tools(self, environment: &Environment) -> Vec<(String, Box<Tool>)>;
+
+    ///
+    /// Creates the tools in this toolset, where each tool can be given several names
+    ///
+    /// By default each tool just has the name returned by `create_tools`
+    ///
+    fn create_tools_with_aliases(self, environment: &Environment) -> Vec<(Vec<String>, Box<Tool>)> where Self: Sized {
+        self.create_tools(environment)
+            .into_iter()
+            .map(|(name, tool)| (vec![name], tool))
+            .collect()
+    }
+}
+
+///
+/// Tool that is shared between several names
+///
+struct SharedTool {
+    tool: Arc<Box<Tool>>
+}
+
+impl Tool for SharedTool {
+    #[inline]
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        self.tool.invoke_json(input, environment)
+    }
+
+    #[inline]
+    fn describe(&self) -> Option<Value> {
+        self.tool.describe()
+    }
+}
+
+///
+/// Converts a list of tools with aliases into a list with a single name per entry
+///
+/// Tools with several names are shared rather than duplicated. This is useful for implementing
+/// `create_tools` for a toolset that overrides `create_tools_with_aliases`.
+///
+pub fn expand_tool_aliases(tools: Vec<(Vec<String>, Box<Tool>)>) -> Vec<(String, Box<Tool>)> {
+    let mut result: Vec<(String, Box<Tool>)> = vec![];
+
+    for (mut names, tool) in tools {
+        if names.len() == 1 {
+            // Only one name, so there's no need to share the tool
+            result.push((names.pop().unwrap(), tool));
+        } else {
+            let shared = Arc::new(tool);
+
+            for name in names {
+                result.push((name, Box::new(SharedTool { tool: shared.clone() })));
+            }
+        }
+    }
+
+    result
 }
 
 impl<'a, T: Tool> Tool for (&'a str, T) {
@@ -35,6 +92,12 @@ impl<'a, T: Tool> Tool for (&'a str, T) {
         let (_, ref tool) = *self;
         tool.invoke_json(input, environment)
     }
+
+    #[inline]
+    fn describe(&self) -> Option<Value> {
+        let (_, ref tool) = *self;
+        tool.describe()
+    }
 }
 
 impl<'a, T: Tool> NamedTool for (&'a str, T) {
@@ -51,6 +114,12 @@ impl<T: Tool> Tool for (String, T) {
         let (_, ref tool) = *self;
         tool.invoke_json(input, environment)
     }
+
+    #[inline]
+    fn describe(&self) -> Option<Value> {
+        let (_, ref tool) = *self;
+        tool.describe()
+    }
 }
 
 impl<T: Tool> NamedTool for (String, T) {
@@ -0,0 +1,48 @@
+//!
+//! Deciding whether or not a JSON value counts as true
+//!
+
+use serde_json::*;
+
+///
+/// Returns true if a value counts as true when used as a condition
+///
+/// `false`, `null`, `0`, `""` and `[]` are false, and every other value is true. Scripts and
+/// tools that make decisions based on the result of another tool should use this so they
+/// agree about what a condition means.
+///
+pub fn is_truthy(value: &Value) -> bool {
+    match value {
+        &Value::Null                => false,
+        &Value::Bool(value)         => value,
+        &Value::Number(ref num)     => num.as_f64() != Some(0.0),
+        &Value::String(ref string)  => !string.is_empty(),
+        &Value::Array(ref array)    => !array.is_empty(),
+        &Value::Object(_)           => true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn empty_values_are_false() {
+        assert!(!is_truthy(&json![ null ]));
+        assert!(!is_truthy(&json![ false ]));
+        assert!(!is_truthy(&json![ 0 ]));
+        assert!(!is_truthy(&json![ 0.0 ]));
+        assert!(!is_truthy(&json![ "" ]));
+        assert!(!is_truthy(&json![[]]));
+    }
+
+    #[test]
+    fn other_values_are_true() {
+        assert!(is_truthy(&json![ true ]));
+        assert!(is_truthy(&json![ 1 ]));
+        assert!(is_truthy(&json![ -0.5 ]));
+        assert!(is_truthy(&json![ "no" ]));
+        assert!(is_truthy(&json![[ 0 ]]));
+        assert!(is_truthy(&json![{}]));
+    }
+}
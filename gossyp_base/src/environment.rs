@@ -12,6 +12,23 @@ pub trait Environment : Send+Sync {
     /// Retrieves a tool using a JSON interface by name
     ///
     fn get_json_tool(&self, name: &str) -> Result<Box<Tool>, RetrieveToolError>;
+
+    ///
+    /// Returns true if a tool with the specified name can be retrieved from this environment
+    ///
+    fn has_tool(&self, name: &str) -> bool {
+        self.get_json_tool(name).is_ok()
+    }
+
+    ///
+    /// For environments made up of several other environments, returns the index of the one that
+    /// resolves a tool name
+    ///
+    /// Other environments return `None`.
+    ///
+    fn tool_origin_index(&self, _name: &str) -> Option<usize> {
+        None
+    }
 }
 
 ///
@@ -30,4 +30,14 @@ pub trait Tool : Send+Sync {
     /// Invokes this tool with its input and output specified using JSON
     ///
     fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value>;
+
+    ///
+    /// Returns a JSON object describing this tool, if it has a description
+    ///
+    /// Tools that wrap another tool should include the description of the tool that they wrap,
+    /// so that the whole chain of tools can be reported.
+    ///
+    fn describe(&self) -> Option<Value> {
+        None
+    }
 }
@@ -7,6 +7,7 @@ use std::error::Error;
 use std::char;
 use std::iter::*;
 use std::sync::*;
+use std::ops::Range;
 
 use serde_json::*;
 
@@ -36,9 +37,17 @@ pub struct LexToolSymbol {
     pub symbol_name:    String,
 
     /// The rule that will be matched against this symbol
-    pub match_rule:     String
+    pub match_rule:     String,
+
+    /// True if letters in the rule should match both their upper and lower case forms
+    pub case_insensitive: Option<bool>
 }
 
+///
+/// The token generated for any part of the input that doesn't match a lexer rule
+///
+pub const UNMATCHED_TOKEN: &'static str = "<error>";
+
 ///
 /// Represents a lexer match
 ///
@@ -54,7 +63,54 @@ pub struct LexerMatch {
     pub start:      i32,
 
     /// End of the match
-    pub end:        i32
+    pub end:        i32,
+
+    /// The line that the match starts on (starting at 1, or 0 if the match was serialized without it)
+    #[serde(default)]
+    pub line:       i32,
+
+    /// The column that the match starts at (starting at 1 and counting characters rather than bytes, or 0 if the match was serialized without it)
+    #[serde(default)]
+    pub column:     i32
+}
+
+///
+/// Tracks the line and column of a position in a string as it's lexed
+///
+struct LinePosition {
+    /// The byte offset that the line and column are for
+    offset: usize,
+
+    /// The current line
+    line:   i32,
+
+    /// The current column
+    column: i32
+}
+
+impl LinePosition {
+    ///
+    /// Creates a position at the start of a string
+    ///
+    fn new() -> LinePosition {
+        LinePosition { offset: 0, line: 1, column: 1 }
+    }
+
+    ///
+    /// Moves forward to a later byte offset in a string
+    ///
+    fn advance_to(&mut self, string: &str, offset: usize) {
+        for chr in string[self.offset..offset].chars() {
+            if chr == '\n' {
+                self.line   += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+
+        self.offset = offset;
+    }
 }
 
 ///
@@ -71,6 +127,26 @@ impl LexTool {
         LexTool { }
     }
 
+    ///
+    /// Returns a lexer regex that matches a literal string
+    ///
+    /// Characters that have a special meaning in a pattern are escaped with a '\'
+    ///
+    pub fn escape(literal: &str) -> String {
+        let mut result = String::new();
+
+        for chr in literal.chars() {
+            match chr {
+                '\\' | '.' | '*' | '+' | '?' | '{' | '[' | ']' | '|' | '(' | ')' => result.push('\\'),
+                _                                                                   => ()
+            }
+
+            result.push(chr);
+        }
+
+        result
+    }
+
     ///
     /// Converts a string containing a lexer regex into a concordance pattern
     ///
@@ -82,6 +158,74 @@ impl LexTool {
         LexTool::pattern_for_chars(&regex_chars)
     }
 
+    ///
+    /// Converts the rule for a lexer symbol into a concordance pattern
+    ///
+    pub fn pattern_for_symbol(symbol: &LexToolSymbol) -> Pattern<char> {
+        let regex_chars: Vec<char> = symbol.match_rule.chars().collect();
+
+        LexTool::pattern_for_chars_with_case(&regex_chars, symbol.case_insensitive.unwrap_or(false))
+    }
+
+    ///
+    /// Returns the characters that match a character in a case-insensitive pattern
+    ///
+    /// Only case conversions that produce a single character are used (so 'ß' doesn't match 'SS')
+    ///
+    fn case_variants(chr: char) -> Vec<char> {
+        let mut variants = vec![chr];
+
+        for converted in [chr.to_lowercase().collect::<Vec<_>>(), chr.to_uppercase().collect::<Vec<_>>()].iter() {
+            if converted.len() == 1 && !variants.contains(&converted[0]) {
+                variants.push(converted[0]);
+            }
+        }
+
+        variants
+    }
+
+    ///
+    /// Returns the pattern for a single character
+    ///
+    fn char_pattern(chr: char, case_insensitive: bool) -> Pattern<char> {
+        if case_insensitive {
+            let variants = LexTool::case_variants(chr);
+
+            if variants.len() > 1 {
+                return MatchAny(variants.into_iter().map(|variant| Match(vec![variant])).collect());
+            }
+        }
+
+        Match(vec![chr])
+    }
+
+    ///
+    /// Adds the other case of any letters in a set of character ranges
+    ///
+    /// Single characters use their case variants. Longer ranges are only converted where they
+    /// cover the ASCII letters.
+    ///
+    fn add_case_variants(ranges: Vec<(char, char)>) -> Vec<(char, char)> {
+        let mut result = ranges.clone();
+
+        for (start, end) in ranges {
+            if start == end {
+                result.extend(LexTool::case_variants(start).into_iter().skip(1).map(|variant| (variant, variant)));
+            } else {
+                for &(from, to) in [(('a', 'z'), ('A', 'Z')), (('A', 'Z'), ('a', 'z'))].iter() {
+                    let (overlap_start, overlap_end) = (start.max(from.0), end.min(from.1));
+
+                    if overlap_start <= overlap_end {
+                        let shift = |chr: char| char::from_u32(chr as u32 - from.0 as u32 + to.0 as u32).unwrap();
+                        result.push((shift(overlap_start), shift(overlap_end)));
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
     ///
     /// Given a list of ranges, determines the set of ranges representing
     /// the characters that are not covered by the list
@@ -206,6 +350,97 @@ impl LexTool {
         &regex[start_pos..end_pos]
     }
 
+    ///
+    /// Reads a '{n}', '{n,}' or '{n,m}' repetition count from the index of the '{' that starts it
+    ///
+    /// Returns the minimum count, the maximum count (None if there's no maximum) and the index of
+    /// the closing '}', or None if the text isn't a valid repetition count (in which case the '{'
+    /// is matched literally). Counts where the maximum is less than the minimum or is too large to
+    /// represent aren't valid.
+    ///
+    fn get_repetition_count(regex: &[char], count_start: usize) -> Option<(usize, Option<usize>, usize)> {
+        // Reads a series of digits, returning the number and the position after it
+        let read_number = |start: usize| {
+            let end = regex[start..].iter().position(|chr| !chr.is_digit(10)).map(|len| start+len).unwrap_or(regex.len());
+            let num = regex[start..end].iter().collect::<String>().parse::<usize>().ok();
+
+            (num, end)
+        };
+
+        let (min, pos) = read_number(count_start+1);
+        let min        = min?;
+
+        match regex.get(pos) {
+            Some(&'}') => Some((min, Some(min), pos)),
+
+            Some(&',') => {
+                let (max, pos) = read_number(pos+1);
+
+                match (regex.get(pos), max) {
+                    (Some(&'}'), None)                      => Some((min, None, pos)),
+                    (Some(&'}'), Some(max)) if max >= min   => Some((min, Some(max), pos)),
+                    _                                       => None
+                }
+            },
+
+            _ => None
+        }.and_then(|(min, max, pos)| {
+            // The pattern stores the maximum as an exclusive range, so it must be possible to add one to it
+            match max {
+                Some(max)   => max.checked_add(1).map(|_| (min, Some(max), pos)),
+                None        => Some((min, None, pos))
+            }
+        })
+    }
+
+    ///
+    /// Returns true if the text from the '{' at a particular position has the form of a repetition count ('{n}', '{n,}' or '{n,m}')
+    ///
+    fn looks_like_repetition_count(regex: &[char], count_start: usize) -> bool {
+        let count_digits    = |start: usize| regex[start.min(regex.len())..].iter().take_while(|chr| chr.is_digit(10)).count();
+        let min_end         = count_start + 1 + count_digits(count_start+1);
+
+        if min_end == count_start+1 {
+            return false;
+        }
+
+        match regex.get(min_end) {
+            Some(&'}')  => true,
+            Some(&',')  => regex.get(min_end + 1 + count_digits(min_end+1)) == Some(&'}'),
+            _           => false
+        }
+    }
+
+    ///
+    /// Returns true if a regex contains a repetition count that can't be used, such as `{3,2}`
+    ///
+    /// These counts are matched literally by the pattern for the regex, so this is used to reject
+    /// the rule instead.
+    ///
+    pub fn has_invalid_repetition_count(regex: &str) -> bool {
+        let regex           = regex.chars().collect::<Vec<_>>();
+        let mut in_range    = false;
+        let mut pos         = 0;
+
+        while pos < regex.len() {
+            match regex[pos] {
+                '\\'                    => pos += 1,
+                '['                     => in_range = true,
+                ']'                     => in_range = false,
+                '{' if !in_range        => {
+                    if LexTool::looks_like_repetition_count(&regex, pos) && LexTool::get_repetition_count(&regex, pos).is_none() {
+                        return true;
+                    }
+                },
+                _                       => ()
+            }
+
+            pos += 1;
+        }
+
+        false
+    }
+
     ///
     /// Joins up any sequence of Match<x>, Match<y>
     ///
@@ -239,6 +474,13 @@ impl LexTool {
     /// Builds a pattern from a UTF-16 slice
     ///
     pub fn pattern_for_chars(regex: &[char]) -> Pattern<char> {
+        LexTool::pattern_for_chars_with_case(regex, false)
+    }
+
+    ///
+    /// Builds a pattern from a UTF-16 slice, optionally matching letters in either case
+    ///
+    fn pattern_for_chars_with_case(regex: &[char], case_insensitive: bool) -> Pattern<char> {
         // Characters to match exactly as built up so far
         let mut pattern         = vec![];
         let mut or_positions    = vec![];
@@ -253,7 +495,10 @@ impl LexTool {
                     // Quoted character
                     pos += 1;
                     if pos < regex_len {
-                        pattern.push(LexTool::special_character_pattern(regex[pos]))
+                        match regex[pos] {
+                            'w' => pattern.push(LexTool::special_character_pattern(regex[pos])),
+                            c   => pattern.push(LexTool::char_pattern(LexTool::special_character_char(c), case_insensitive))
+                        }
                     }
                 },
 
@@ -292,6 +537,19 @@ impl LexTool {
                     }
                 },
 
+                '{' if pattern.len() > 0 && LexTool::get_repetition_count(regex, pos).is_some() => {
+                    // Last item repeated a specific number of times
+                    let (min, max, end_pos) = LexTool::get_repetition_count(regex, pos).unwrap();
+                    let last                = Box::new(pattern.pop().unwrap());
+
+                    pattern.push(match max {
+                        Some(max)   => Repeat(min..max+1, last),
+                        None        => RepeatInfinite(min, last)
+                    });
+
+                    pos = end_pos;
+                },
+
                 '[' => {
                     // Character ranges
                     let mut ranges      = vec![];
@@ -328,6 +586,11 @@ impl LexTool {
                         pos += 1;
                     }
 
+                    // The other case has to be added before inverting so that '[^a]' doesn't match 'A'
+                    if case_insensitive {
+                        ranges = LexTool::add_case_variants(ranges);
+                    }
+
                     // Invert the ranges if this is a '[^' type range
                     if inverted {
                         ranges = LexTool::invert_ranges(ranges);
@@ -349,14 +612,14 @@ impl LexTool {
                 '(' => {
                     // Subpattern
                     let subpattern = LexTool::get_subpattern(regex, pos);
-                    pattern.push(LexTool::pattern_for_chars(subpattern));
+                    pattern.push(LexTool::pattern_for_chars_with_case(subpattern, case_insensitive));
 
                     pos += subpattern.len()+1;
                 },
 
                 c => {
                     // Just match this character
-                    pattern.push(Match(vec![c]));
+                    pattern.push(LexTool::char_pattern(c, case_insensitive));
                 }
             }
 
@@ -418,6 +681,15 @@ impl Tool for LexTool {
             },
 
             (Ok(lex_defn), Ok(define_tool)) => {
+                // Refuse rules with repetition counts that can't be matched
+                if let Some(symbol) = lex_defn.symbols.iter().filter(|symbol| LexTool::has_invalid_repetition_count(&symbol.match_rule)).nth(0) {
+                    return Err(json![{
+                        "error":        "Lexer rule has an invalid repetition count",
+                        "symbol_name":  symbol.symbol_name,
+                        "match_rule":   symbol.match_rule
+                    }]);
+                }
+
                 // Generate a lexer tool for this definition
                 let lexer_tool = StringLexingTool::from_lex_tool_input(&lex_defn);
 
@@ -455,7 +727,7 @@ impl StringLexingTool {
         let mut symbol_names    = vec![];
 
         for symbol in lex_defn.symbols.iter() {
-            let pattern = LexTool::pattern_for_string(&symbol.match_rule);
+            let pattern = LexTool::pattern_for_symbol(symbol);
             let token   = index;
 
             token_matcher.add_pattern(pattern, token);
@@ -470,25 +742,143 @@ impl StringLexingTool {
         StringLexingTool { matcher: Arc::new(prepared), symbol_names: Arc::new(symbol_names) }
     }
 
+    ///
+    /// Lexes a string lazily, stopping with an error if a rule matches the empty string
+    ///
+    /// The tokenizer can't move past a match that doesn't consume any characters, so it would
+    /// otherwise keep returning the same match forever. The matches up to that point are
+    /// returned, followed by an error describing the empty match.
+    ///
+    fn lex_results<'a>(&'a self, string: &'a str) -> impl Iterator<Item=Result<LexerMatch, Value>> + 'a {
+        let tokenizer = Tokenizer::new_prepared(string.read_symbols(), &self.matcher);
+
+        StringLexingTool::results_from_matches(string, &self.symbol_names, tokenizer)
+    }
+
+    ///
+    /// Converts the ranges and symbol indexes found by a tokenizer into lexer matches
+    ///
+    fn results_from_matches<'a, TMatches>(string: &'a str, symbol_names: &'a Vec<String>, mut tokenizer: TMatches) -> impl Iterator<Item=Result<LexerMatch, Value>> + 'a
+    where TMatches: 'a+Iterator<Item=(Range<usize>, usize)> {
+        let mut position    = LinePosition::new();
+        let mut pending     = None;
+        let mut finished    = false;
+
+        from_fn(move || {
+            // A match that was found after some unmatched input is returned after it
+            if let Some(pending) = pending.take() {
+                return Some(pending);
+            }
+
+            if finished {
+                return None;
+            }
+
+            match tokenizer.next() {
+                Some((range, token)) => {
+                    let token_string = symbol_names[token].clone();
+
+                    // Anything the tokenizer skipped over didn't match any rule
+                    let unmatched = if range.start > position.offset {
+                        Some(StringLexingTool::unmatched(string, &mut position, range.start))
+                    } else {
+                        None
+                    };
+
+                    let next = if range.start >= range.end {
+                        finished = true;
+
+                        Err(json![{
+                            "error":        "Lexer rule matched the empty string",
+                            "symbol_name":  token_string,
+                            "position":     range.start
+                        }])
+                    } else {
+                        position.advance_to(string, range.start);
+                        let (line, column) = (position.line, position.column);
+                        position.advance_to(string, range.end);
+
+                        Ok(LexerMatch { 
+                            token:      token_string,
+                            matched:    String::from(&string[range.clone()]),
+                            start:      range.start as i32,
+                            end:        range.end as i32,
+                            line:       line,
+                            column:     column
+                        })
+                    };
+
+                    match unmatched {
+                        Some(unmatched) => {
+                            pending = Some(next);
+                            Some(Ok(unmatched))
+                        },
+
+                        None => Some(next)
+                    }
+                },
+
+                None => {
+                    finished = true;
+
+                    // The tokenizer stops if it can't match the rest of the string
+                    if position.offset < string.len() {
+                        Some(Ok(StringLexingTool::unmatched(string, &mut position, string.len())))
+                    } else {
+                        None
+                    }
+                }
+            }
+        })
+    }
+
+    ///
+    /// Creates a match representing the part of a string from the current position that no lexer rule matched
+    ///
+    fn unmatched(string: &str, position: &mut LinePosition, end: usize) -> LexerMatch {
+        let start           = position.offset;
+        let (line, column)  = (position.line, position.column);
+
+        position.advance_to(string, end);
+
+        LexerMatch {
+            token:      String::from(UNMATCHED_TOKEN),
+            matched:    String::from(&string[start..end]),
+            start:      start as i32,
+            end:        end as i32,
+            line:       line,
+            column:     column
+        }
+    }
+
     ///
     /// Performs lexing
     ///
+    /// Lexing stops early if one of the rules matches the empty string (use `try_lex` to
+    /// find out when this happens). Any part of the string that no rule matches is returned
+    /// as a match with the token `UNMATCHED_TOKEN` rather than being skipped.
+    ///
     pub fn lex(&self, string: &str) -> Vec<LexerMatch> {
-        let mut tokenizer   = Tokenizer::new_prepared(string.read_symbols(), &self.matcher);
-        let mut result      = vec![];
-
-        while let Some((range, token)) = tokenizer.next() {
-            let token_string = self.symbol_names[token].clone();
-
-            result.push(LexerMatch { 
-                token:      token_string,
-                matched:    String::from(&string[range.clone()]),
-                start:      range.start as i32,
-                end:        range.end as i32
-            });
-        }
+        self.lex_iter(string).collect()
+    }
 
-        result
+    ///
+    /// Performs lexing lazily, generating each match as it's needed
+    ///
+    /// This produces the same matches as `lex`, so a caller that only needs the first few tokens
+    /// (or wants to stop at the first `UNMATCHED_TOKEN`) doesn't need to lex the whole string.
+    ///
+    pub fn lex_iter<'a>(&'a self, string: &'a str) -> impl Iterator<Item=LexerMatch> + 'a {
+        self.lex_results(string)
+            .take_while(|result| result.is_ok())
+            .map(|result| result.unwrap())
+    }
+
+    ///
+    /// Performs lexing, returning an error if one of the rules matches the empty string
+    ///
+    pub fn try_lex(&self, string: &str) -> Result<Vec<LexerMatch>, Value> {
+        self.lex_results(string).collect()
     }
 }
 
@@ -498,8 +888,7 @@ impl Tool for StringLexingTool {
             // Input must be a simple string
 
             // Tokenize it
-            let result = self.lex(&input);
-            Ok(to_value(result).unwrap())
+            self.try_lex(&input).map(|result| to_value(result).unwrap())
         } else {
             Err(json![{
                 "error": "Input must be a string"
@@ -557,6 +946,48 @@ mod test {
         assert!(LexTool::pattern_for_string("a?") == Repeat(0..2, Box::new(Match(vec!['a']))));
     }
 
+    #[test]
+    fn can_create_exact_repetition() {
+        assert!(LexTool::pattern_for_string("a{3}") == Repeat(3..4, Box::new(Match(vec!['a']))));
+    }
+
+    #[test]
+    fn can_create_bounded_repetition() {
+        assert!(LexTool::pattern_for_string("a{2,5}") == Repeat(2..6, Box::new(Match(vec!['a']))));
+    }
+
+    #[test]
+    fn can_create_unbounded_repetition() {
+        assert!(LexTool::pattern_for_string("a{2,}") == RepeatInfinite(2, Box::new(Match(vec!['a']))));
+    }
+
+    #[test]
+    fn repetition_applies_to_last_item() {
+        assert!(LexTool::pattern_for_string("ab{2}") == MatchAll(vec![ Match(vec!['a']), Repeat(2..3, Box::new(Match(vec!['b']))) ]));
+    }
+
+    #[test]
+    fn invalid_repetition_is_literal() {
+        assert!(LexTool::pattern_for_string("{") == Match(vec!['{']));
+        assert!(LexTool::pattern_for_string("a{x}") == Match(vec!['a', '{', 'x', '}']));
+        assert!(LexTool::pattern_for_string("a{3,2}") == Match(vec!['a', '{', '3', ',', '2', '}']));
+        assert!(LexTool::pattern_for_string("a{,2}") == Match(vec!['a', '{', ',', '2', '}']));
+        assert!(LexTool::pattern_for_string("a{0,18446744073709551615}") == Match("a{0,18446744073709551615}".chars().collect()));
+    }
+
+    #[test]
+    fn finds_invalid_repetition_counts() {
+        assert!(LexTool::has_invalid_repetition_count("a{3,2}"));
+        assert!(LexTool::has_invalid_repetition_count("a{0,18446744073709551615}"));
+        assert!(LexTool::has_invalid_repetition_count("a{99999999999999999999999}"));
+
+        assert!(!LexTool::has_invalid_repetition_count("a{2,5}"));
+        assert!(!LexTool::has_invalid_repetition_count("a{2,}"));
+        assert!(!LexTool::has_invalid_repetition_count("a{x}"));
+        assert!(!LexTool::has_invalid_repetition_count("a\\{3,2}"));
+        assert!(!LexTool::has_invalid_repetition_count("[{3,2}]"));
+    }
+
     #[test]
     fn can_create_match_one() {
         assert!(LexTool::pattern_for_string("[a]") == MatchRange('a', 'a'));
@@ -567,6 +998,12 @@ mod test {
         assert!(LexTool::pattern_for_string("\\n") == Match(vec![ '\n' ]));
     }
 
+    #[test]
+    fn escaped_strings_match_literally() {
+        assert!(LexTool::pattern_for_string(&LexTool::escape("a.b*")) == Match(vec!['a', '.', 'b', '*']));
+        assert!(LexTool::pattern_for_string(&LexTool::escape("a{2}")) == Match(vec!['a', '{', '2', '}']));
+    }
+
     #[test]
     fn can_create_match_range() {
         assert!(LexTool::pattern_for_string("[a-z]") == MatchRange('a', 'z'));
@@ -602,6 +1039,20 @@ mod test {
         assert!(LexTool::pattern_for_string("[aA-Z]") == MatchAny(vec![ MatchRange('a', 'a'), MatchRange('A', 'Z') ]));
     }
 
+    #[test]
+    fn can_decode_match_without_line_and_column() {
+        let lexer_match = from_value::<LexerMatch>(json![{ "token": "Hello", "matched": "Hello", "start": 0, "end": 5 }]);
+
+        assert!(lexer_match.ok() == Some(LexerMatch {
+            token:      String::from("Hello"),
+            matched:    String::from("Hello"),
+            start:      0,
+            end:        5,
+            line:       0,
+            column:     0
+        }));
+    }
+
     #[test]
     fn can_generate_simple_lexer() {
         let env     = DynamicEnvironment::new();
@@ -610,8 +1061,8 @@ mod test {
         let def     = LexToolInput {
             new_tool_name: String::from("sample-lexer"),
             symbols: vec![
-                LexToolSymbol { symbol_name: String::from("Hello"), match_rule: String::from("Hello") },
-                LexToolSymbol { symbol_name: String::from("Other"), match_rule: String::from("W.*") },
+                LexToolSymbol { symbol_name: String::from("Hello"), match_rule: String::from("Hello"), case_insensitive: None },
+                LexToolSymbol { symbol_name: String::from("Other"), match_rule: String::from("W.*"), case_insensitive: None },
             ]
         };
 
@@ -625,18 +1076,42 @@ mod test {
                 token:      String::from("Hello"),
                 matched:    String::from("Hello"),
                 start:      0,
-                end:        5
+                end:        5,
+                line:       1,
+                column:     1
             },
 
             LexerMatch {
                 token:      String::from("Other"),
                 matched:    String::from("World"),
                 start:      5,
-                end:        10
+                end:        10,
+                line:       1,
+                column:     6
             }
         ]);
     }
 
+    #[test]
+    fn rule_with_invalid_repetition_count_is_refused() {
+        let env     = DynamicEnvironment::new();
+        let lexer   = TypedTool::<LexToolInput, ()>::from(Box::new(LexTool::new()));
+
+        let def     = LexToolInput {
+            new_tool_name: String::from("sample-lexer"),
+            symbols: vec![
+                LexToolSymbol { symbol_name: String::from("Many"), match_rule: String::from("a{0,18446744073709551615}"), case_insensitive: None }
+            ]
+        };
+
+        assert!(lexer.invoke(def, &env) == Err(json![{
+            "error":        "Lexer rule has an invalid repetition count",
+            "symbol_name":  "Many",
+            "match_rule":   "a{0,18446744073709551615}"
+        }]));
+        assert!(!env.has_tool("sample-lexer"));
+    }
+
     #[test]
     fn earlier_items_are_disambiguated_first() {
         let env     = DynamicEnvironment::new();
@@ -645,8 +1120,8 @@ mod test {
         let def     = LexToolInput {
             new_tool_name: String::from("sample-lexer"),
             symbols: vec![
-                LexToolSymbol { symbol_name: String::from("Hello"), match_rule: String::from("Hello") },
-                LexToolSymbol { symbol_name: String::from("AAAAA"), match_rule: String::from("Hello") },
+                LexToolSymbol { symbol_name: String::from("Hello"), match_rule: String::from("Hello"), case_insensitive: None },
+                LexToolSymbol { symbol_name: String::from("AAAAA"), match_rule: String::from("Hello"), case_insensitive: None },
             ]
         };
 
@@ -660,8 +1135,167 @@ mod test {
                 token:      String::from("Hello"),
                 matched:    String::from("Hello"),
                 start:      0,
-                end:        5
+                end:        5,
+                line:       1,
+                column:     1
             }
         ]);
     }
+
+
+    #[test]
+    fn rule_matching_empty_string_does_not_loop_forever() {
+        let lexer = StringLexingTool::from_lex_tool_input(&LexToolInput {
+            new_tool_name: String::from("sample-lexer"),
+            symbols: vec![
+                LexToolSymbol { symbol_name: String::from("Empty"), match_rule: String::from("x*"), case_insensitive: None },
+                LexToolSymbol { symbol_name: String::from("Letter"), match_rule: String::from("[a-z]"), case_insensitive: None },
+            ]
+        });
+
+        let result = lexer.lex("abc");
+
+        assert!(result.iter().all(|lexer_match| lexer_match.start < lexer_match.end));
+    }
+
+    #[test]
+    fn empty_match_stops_lexing_with_error() {
+        // The tokenizer skips over rules that match the empty string, so supply the matches directly
+        let symbol_names    = vec![ String::from("Letter"), String::from("Empty") ];
+        let matches         = vec![ (0..1, 0), (1..1, 1), (1..2, 0) ];
+
+        let result          = StringLexingTool::results_from_matches("ab", &symbol_names, matches.into_iter()).collect::<Vec<_>>();
+
+        assert!(result == vec![
+            Ok(LexerMatch { token: String::from("Letter"), matched: String::from("a"), start: 0, end: 1, line: 1, column: 1 }),
+            Err(json![{ "error": "Lexer rule matched the empty string", "symbol_name": "Empty", "position": 1 }])
+        ]);
+    }
+
+    #[test]
+    fn unmatched_input_generates_error_tokens() {
+        let lexer = StringLexingTool::from_lex_tool_input(&LexToolInput {
+            new_tool_name: String::from("sample-lexer"),
+            symbols: vec![
+                LexToolSymbol { symbol_name: String::from("Word"), match_rule: String::from("[a-z]+"), case_insensitive: None },
+            ]
+        });
+
+        let result = lexer.lex("ab12cd3");
+
+        assert!(result == vec![
+            LexerMatch { token: String::from("Word"),       matched: String::from("ab"),    start: 0, end: 2, line: 1, column: 1 },
+            LexerMatch { token: String::from("<error>"),    matched: String::from("12"),    start: 2, end: 4, line: 1, column: 3 },
+            LexerMatch { token: String::from("Word"),       matched: String::from("cd"),    start: 4, end: 6, line: 1, column: 5 },
+            LexerMatch { token: String::from("<error>"),    matched: String::from("3"),     start: 6, end: 7, line: 1, column: 7 }
+        ]);
+    }
+
+    #[test]
+    fn matches_have_line_and_column() {
+        let lexer = StringLexingTool::from_lex_tool_input(&LexToolInput {
+            new_tool_name: String::from("sample-lexer"),
+            symbols: vec![
+                LexToolSymbol { symbol_name: String::from("Word"), match_rule: String::from("[a-zé]+"), case_insensitive: None },
+                LexToolSymbol { symbol_name: String::from("Whitespace"), match_rule: String::from("[ \\n]+"), case_insensitive: None },
+            ]
+        });
+
+        let result = lexer.lex("héllo world\n  foo\nbar");
+        let words  = result.iter()
+            .filter(|lexer_match| lexer_match.token == "Word")
+            .map(|lexer_match| (lexer_match.matched.as_str(), lexer_match.start, lexer_match.line, lexer_match.column))
+            .collect::<Vec<_>>();
+
+        assert!(words == vec![ ("héllo", 0, 1, 1), ("world", 7, 1, 7), ("foo", 15, 2, 3), ("bar", 19, 3, 1) ]);
+    }
+
+    #[test]
+    fn case_insensitive_rule_matches_any_case() {
+        let lexer = StringLexingTool::from_lex_tool_input(&LexToolInput {
+            new_tool_name: String::from("sample-lexer"),
+            symbols: vec![
+                LexToolSymbol { symbol_name: String::from("Hello"), match_rule: String::from("HELLO"), case_insensitive: Some(true) },
+                LexToolSymbol { symbol_name: String::from("Whitespace"), match_rule: String::from(" "), case_insensitive: None },
+            ]
+        });
+
+        let result = lexer.lex("hello Hello HELLO");
+
+        assert!(result.iter().map(|lexer_match| lexer_match.token.as_str()).collect::<Vec<_>>() == vec![ "Hello", "Whitespace", "Hello", "Whitespace", "Hello" ]);
+    }
+
+    #[test]
+    fn case_insensitive_rule_works_with_ranges_and_repeats() {
+        let symbol = LexToolSymbol { symbol_name: String::from("Rule"), match_rule: String::from("[a-c]+x{2}[^d]"), case_insensitive: Some(true) };
+        let lexer  = StringLexingTool::from_lex_tool_input(&LexToolInput { new_tool_name: String::from("sample-lexer"), symbols: vec![ symbol ] });
+
+        assert!(lexer.lex("aBcXxe")[0].token == "Rule");
+        assert!(lexer.lex("aBcXxe")[0].end == 6);
+
+        // An inverted range excludes both cases
+        assert!(lexer.lex("aBcXxD")[0].token == "<error>");
+    }
+
+    #[test]
+    fn rules_are_case_sensitive_by_default() {
+        let lexer = StringLexingTool::from_lex_tool_input(&LexToolInput {
+            new_tool_name: String::from("sample-lexer"),
+            symbols: vec![
+                LexToolSymbol { symbol_name: String::from("Hello"), match_rule: String::from("HELLO"), case_insensitive: Some(false) },
+            ]
+        });
+
+        assert!(lexer.lex("HELLO")[0].token == "Hello");
+        assert!(lexer.lex("hello")[0].token == "<error>");
+    }
+
+    #[test]
+    fn lex_iter_generates_matches_lazily() {
+        let lexer = StringLexingTool::from_lex_tool_input(&LexToolInput {
+            new_tool_name: String::from("sample-lexer"),
+            symbols: vec![
+                LexToolSymbol { symbol_name: String::from("Word"), match_rule: String::from("[a-z]+"), case_insensitive: None },
+                LexToolSymbol { symbol_name: String::from("Whitespace"), match_rule: String::from(" "), case_insensitive: None },
+            ]
+        });
+
+        let input   = "one two 3 four";
+        let matches = lexer.lex_iter(input)
+            .map(|lexer_match| (lexer_match.token, lexer_match.matched, lexer_match.start))
+            .collect::<Vec<_>>();
+
+        assert!(matches == vec![
+            (String::from("Word"), String::from("one"), 0),
+            (String::from("Whitespace"), String::from(" "), 3),
+            (String::from("Word"), String::from("two"), 4),
+            (String::from("Whitespace"), String::from(" "), 7),
+            (String::from(UNMATCHED_TOKEN), String::from("3"), 8),
+            (String::from("Whitespace"), String::from(" "), 9),
+            (String::from("Word"), String::from("four"), 10)
+        ]);
+
+        // Can stop at the first error
+        let before_error = lexer.lex_iter(input)
+            .take_while(|lexer_match| lexer_match.token != UNMATCHED_TOKEN)
+            .map(|lexer_match| lexer_match.matched)
+            .collect::<Vec<_>>();
+
+        assert!(before_error == vec![ "one", " ", "two", " " ]);
+    }
+
+    #[test]
+    fn lex_iter_stops_asking_for_matches_when_it_stops() {
+        let symbol_names    = vec![ String::from("Letter") ];
+        let matches         = vec![ (0..1, 0), (1..2, 0), (2..3, 0), (3..4, 0) ];
+        let requested       = Mutex::new(0);
+
+        let first_two       = StringLexingTool::results_from_matches("abcd", &symbol_names, matches.into_iter().inspect(|_| *requested.lock().unwrap() += 1))
+            .take(2)
+            .map(|result| result.unwrap().matched)
+            .collect::<Vec<_>>();
+
+        assert!(first_two == vec![ "a", "b" ]);
+        assert!(*requested.lock().unwrap() == 2);
+    }
 }
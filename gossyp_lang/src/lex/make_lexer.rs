@@ -0,0 +1,181 @@
+//!
+//! The make-lexer tool generates a lexer from a list of the kinds of token it should recognise
+//!
+
+use std::result::Result;
+use std::error::Error;
+
+use serde_json::*;
+use gossyp_base::*;
+
+use super::lex_tool::*;
+
+///
+/// Input for the make-lexer tool
+///
+/// Keywords and symbols generate tokens named after themselves. The other categories generate
+/// `Identifier`, `Number`, `String` and `Whitespace` tokens.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MakeLexerInput {
+    /// Name of the tool that the lexer will define
+    pub new_tool_name:  String,
+
+    /// Words that should be matched as their own token rather than as identifiers
+    pub keywords:       Option<Vec<String>>,
+
+    /// Punctuation that should be matched as its own token (eg, '(' or '+=')
+    pub symbols:        Option<Vec<String>>,
+
+    /// True if the lexer should match identifiers (a letter or '_' followed by letters, digits or '_')
+    pub identifier:     Option<bool>,
+
+    /// True if the lexer should match numbers (eg, -1.5e10)
+    pub number:         Option<bool>,
+
+    /// True if the lexer should match double-quoted strings
+    pub string:         Option<bool>,
+
+    /// True if the lexer should match whitespace
+    pub whitespace:     Option<bool>
+}
+
+impl MakeLexerInput {
+    pub fn new(new_tool_name: &str) -> MakeLexerInput {
+        MakeLexerInput {
+            new_tool_name:  String::from(new_tool_name),
+            keywords:       None,
+            symbols:        None,
+            identifier:     None,
+            number:         None,
+            string:         None,
+            whitespace:     None
+        }
+    }
+}
+
+///
+/// Tool that generates a lexer from a higher-level description than the lex tool
+///
+pub struct MakeLexerTool {
+}
+
+impl MakeLexerTool {
+    ///
+    /// Creates a new make-lexer tool
+    ///
+    pub fn new() -> MakeLexerTool {
+        MakeLexerTool { }
+    }
+
+    ///
+    /// Expands a make-lexer input into the input for the lex tool
+    ///
+    /// Where two rules match the same text, the lexer picks the earliest one, so keywords are
+    /// always generated before identifiers.
+    ///
+    pub fn expand(input: &MakeLexerInput) -> LexToolInput {
+        let mut symbols = vec![];
+
+        let literal_symbol  = |literal: &String| LexToolSymbol { symbol_name: literal.clone(), match_rule: LexTool::escape(literal), case_insensitive: None };
+        let rule_symbol     = |name: &str, rule: &str| LexToolSymbol { symbol_name: String::from(name), match_rule: String::from(rule), case_insensitive: None };
+
+        // Keywords and symbols take precedence over the more general rules
+        input.keywords.as_ref().map(|keywords| symbols.extend(keywords.iter().map(&literal_symbol)));
+        input.symbols.as_ref().map(|literals| symbols.extend(literals.iter().map(&literal_symbol)));
+
+        if input.string.unwrap_or(false) {
+            symbols.push(rule_symbol("String", "\"(([^\"])|(\\\\\"))*\""));
+        }
+
+        if input.number.unwrap_or(false) {
+            symbols.push(rule_symbol("Number", "-?[0-9]+(\\.[0-9]+)?((e|E)-?[0-9]+)?"));
+        }
+
+        if input.identifier.unwrap_or(false) {
+            symbols.push(rule_symbol("Identifier", "[A-Za-z_][A-Za-z0-9_]*"));
+        }
+
+        if input.whitespace.unwrap_or(false) {
+            symbols.push(rule_symbol("Whitespace", "\\w+"));
+        }
+
+        LexToolInput {
+            new_tool_name:  input.new_tool_name.clone(),
+            symbols:        symbols
+        }
+    }
+}
+
+impl Tool for MakeLexerTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        match from_value::<MakeLexerInput>(input) {
+            Ok(input) => {
+                // Generate the lexer using the lex tool
+                let lex_input = MakeLexerTool::expand(&input);
+                LexTool::new().invoke_json(to_value(lex_input).unwrap(), environment)
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters incorrect",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    fn tokens(matches: Vec<LexerMatch>) -> Vec<(String, String)> {
+        matches.into_iter().map(|lexer_match| (lexer_match.token, lexer_match.matched)).collect()
+    }
+
+    #[test]
+    fn keywords_are_distinct_from_identifiers() {
+        let env         = DynamicEnvironment::new();
+        let make_lexer  = TypedTool::<MakeLexerInput, ()>::from(Box::new(MakeLexerTool::new()));
+
+        let mut input   = MakeLexerInput::new("sample-lexer");
+        input.keywords      = Some(vec![ String::from("if") ]);
+        input.identifier    = Some(true);
+        input.whitespace    = Some(true);
+
+        make_lexer.invoke(input, &env).unwrap();
+
+        let lexer                       = env.get_typed_tool("sample-lexer").unwrap();
+        let result: Vec<LexerMatch>     = lexer.invoke("if iffy", &env).unwrap();
+
+        assert!(tokens(result) == vec![
+            (String::from("if"), String::from("if")),
+            (String::from("Whitespace"), String::from(" ")),
+            (String::from("Identifier"), String::from("iffy"))
+        ]);
+    }
+
+    #[test]
+    fn can_lex_symbols_numbers_and_strings() {
+        let env         = DynamicEnvironment::new();
+        let make_lexer  = TypedTool::<MakeLexerInput, ()>::from(Box::new(MakeLexerTool::new()));
+
+        let mut input   = MakeLexerInput::new("sample-lexer");
+        input.symbols   = Some(vec![ String::from("("), String::from(")"), String::from(",") ]);
+        input.number    = Some(true);
+        input.string    = Some(true);
+
+        make_lexer.invoke(input, &env).unwrap();
+
+        let lexer                       = env.get_typed_tool("sample-lexer").unwrap();
+        let result: Vec<LexerMatch>     = lexer.invoke("(1.5,\"a\")", &env).unwrap();
+
+        assert!(tokens(result) == vec![
+            (String::from("("), String::from("(")),
+            (String::from("Number"), String::from("1.5")),
+            (String::from(","), String::from(",")),
+            (String::from("String"), String::from("\"a\"")),
+            (String::from(")"), String::from(")"))
+        ]);
+    }
+}
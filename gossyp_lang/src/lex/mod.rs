@@ -1,4 +1,38 @@
-
 pub mod lex_tool;
+pub mod make_lexer;
+pub mod quote_pattern;
+pub mod validate_lexer;
+pub mod tool;
 
 pub use self::lex_tool::*;
+pub use self::make_lexer::*;
+pub use self::quote_pattern::*;
+pub use self::validate_lexer::*;
+
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+///
+/// ToolSet for building and checking lexers
+///
+pub struct LexTools {
+}
+
+impl ToolSet for LexTools {
+    fn create_tools(self, _: &Environment) -> Vec<(String, Box<Tool>)> {
+        vec![
+            (String::from(tool::MAKE_LEXER),        Box::new(MakeLexerTool::new())),
+            (String::from(tool::QUOTE_PATTERN),     Box::new(QuotePatternTool::new())),
+            (String::from(tool::VALIDATE_LEXER),    Box::new(ValidateLexerTool::new()))
+        ]
+    }
+}
+
+impl LexTools {
+    ///
+    /// Creates a new lexer toolset
+    ///
+    pub fn new() -> LexTools {
+        LexTools { }
+    }
+}
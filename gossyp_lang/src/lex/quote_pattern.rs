@@ -0,0 +1,73 @@
+//!
+//! The quote-pattern tool converts a literal string into a lexer pattern that matches it
+//!
+
+use std::result::Result;
+use std::error::Error;
+
+use serde_json::*;
+use gossyp_base::*;
+
+use super::lex_tool::*;
+
+///
+/// Tool that escapes the special characters in a string so it can be used as a lexer rule
+///
+/// For example, `"a.b"` becomes `"a\\.b"`, which matches only the text 'a.b' rather than any
+/// character between an 'a' and a 'b'.
+///
+pub struct QuotePatternTool {
+}
+
+impl QuotePatternTool {
+    ///
+    /// Creates a new quote-pattern tool
+    ///
+    pub fn new() -> QuotePatternTool {
+        QuotePatternTool { }
+    }
+}
+
+impl Tool for QuotePatternTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        match from_value::<String>(input) {
+            Ok(literal) => Ok(Value::String(LexTool::escape(&literal))),
+            Err(erm)    => Err(json![{
+                "error":        "quote-pattern must be called with a string",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn quoted_pattern_matches_literally() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<&str, String>::from(Box::new(QuotePatternTool::new()));
+
+        let pattern = tool.invoke("a.b[c]*", &env).unwrap();
+        let lexer   = StringLexingTool::from_lex_tool_input(&LexToolInput {
+            new_tool_name:  String::from("sample-lexer"),
+            symbols:        vec![
+                LexToolSymbol { symbol_name: String::from("Literal"), match_rule: pattern, case_insensitive: None },
+                LexToolSymbol { symbol_name: String::from("Other"), match_rule: String::from("."), case_insensitive: None }
+            ]
+        });
+
+        assert!(lexer.lex("a.b[c]*").iter().map(|lexer_match| lexer_match.token.clone()).collect::<Vec<_>>() == vec![ "Literal" ]);
+        assert!(lexer.lex("axbc").iter().all(|lexer_match| lexer_match.token == "Other"));
+    }
+
+    #[test]
+    fn plain_strings_are_unchanged() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<&str, String>::from(Box::new(QuotePatternTool::new()));
+
+        assert!(tool.invoke("keyword", &env) == Ok(String::from("keyword")));
+    }
+}
@@ -0,0 +1,3 @@
+pub const MAKE_LEXER: &'static str      = "make-lexer";
+pub const QUOTE_PATTERN: &'static str   = "quote-pattern";
+pub const VALIDATE_LEXER: &'static str  = "validate-lexer";
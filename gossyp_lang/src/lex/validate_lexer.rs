@@ -0,0 +1,197 @@
+//!
+//! The validate-lexer tool checks a lexer definition for problems before it is built
+//!
+
+use std::result::Result;
+use std::error::Error;
+use std::collections::HashSet;
+
+use serde_json::*;
+use concordance::*;
+use gossyp_base::*;
+
+use super::lex_tool::*;
+
+///
+/// Problems that can be found in a lexer definition
+///
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LexerProblem {
+    /// The lexer has no symbols, so it can't match anything
+    NoSymbols,
+
+    /// More than one symbol has the same name
+    DuplicateSymbolName,
+
+    /// A symbol's rule can match the empty string, which would make the lexer loop forever
+    MatchesEmptyString,
+
+    /// A symbol's rule has a repetition count whose maximum is less than its minimum or is too large
+    InvalidRepetitionCount
+}
+
+///
+/// Describes a problem with a lexer definition
+///
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct LexerDiagnostic {
+    /// The problem that was found
+    pub problem: LexerProblem,
+
+    /// The index of the symbol with the problem, if it relates to a particular symbol
+    pub symbol: Option<usize>,
+
+    /// The name of the symbol with the problem
+    pub symbol_name: Option<String>
+}
+
+///
+/// Result of the validate-lexer tool
+///
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ValidateLexerResult {
+    /// True if there were no problems with the lexer definition
+    pub valid: bool,
+
+    /// The problems that were found
+    pub problems: Vec<LexerDiagnostic>
+}
+
+///
+/// Tool that checks a `LexToolInput` for problems that would stop the lexer from working
+///
+pub struct ValidateLexerTool {
+}
+
+impl ValidateLexerTool {
+    ///
+    /// Creates a new validate-lexer tool
+    ///
+    pub fn new() -> ValidateLexerTool {
+        ValidateLexerTool { }
+    }
+
+    ///
+    /// Returns true if a pattern can match the empty string
+    ///
+    pub fn matches_empty(pattern: &Pattern<char>) -> bool {
+        match pattern {
+            &Epsilon                            => true,
+            &Match(ref chars)                   => chars.len() == 0,
+            &MatchRange(_, _)                   => false,
+            &MatchAll(ref patterns)             => patterns.iter().all(|pattern| ValidateLexerTool::matches_empty(pattern)),
+            &MatchAny(ref patterns)             => patterns.iter().any(|pattern| ValidateLexerTool::matches_empty(pattern)),
+            &Repeat(ref range, ref pattern)     => range.start == 0 || ValidateLexerTool::matches_empty(pattern),
+            &RepeatInfinite(min, ref pattern)   => min == 0 || ValidateLexerTool::matches_empty(pattern)
+        }
+    }
+
+    ///
+    /// Finds the problems in a lexer definition
+    ///
+    pub fn validate(lex_defn: &LexToolInput) -> Vec<LexerDiagnostic> {
+        let mut problems = vec![];
+
+        if lex_defn.symbols.len() == 0 {
+            problems.push(LexerDiagnostic { problem: LexerProblem::NoSymbols, symbol: None, symbol_name: None });
+        }
+
+        let mut seen_names = HashSet::new();
+
+        for (index, symbol) in lex_defn.symbols.iter().enumerate() {
+            let symbol_problem = |problem| LexerDiagnostic { problem: problem, symbol: Some(index), symbol_name: Some(symbol.symbol_name.clone()) };
+
+            // Only the first symbol with a particular name is allowed
+            if !seen_names.insert(symbol.symbol_name.clone()) {
+                problems.push(symbol_problem(LexerProblem::DuplicateSymbolName));
+            }
+
+            // Rules that match nothing would be matched forever at the same position
+            if ValidateLexerTool::matches_empty(&LexTool::pattern_for_string(&symbol.match_rule)) {
+                problems.push(symbol_problem(LexerProblem::MatchesEmptyString));
+            }
+
+            // make-lexer refuses rules with counts like '{3,2}'
+            if LexTool::has_invalid_repetition_count(&symbol.match_rule) {
+                problems.push(symbol_problem(LexerProblem::InvalidRepetitionCount));
+            }
+        }
+
+        problems
+    }
+}
+
+impl Tool for ValidateLexerTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        match from_value::<LexToolInput>(input) {
+            Ok(lex_defn) => {
+                let problems = ValidateLexerTool::validate(&lex_defn);
+                Ok(to_value(ValidateLexerResult { valid: problems.len() == 0, problems: problems }).unwrap())
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters incorrect",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    fn symbol(name: &str, rule: &str) -> LexToolSymbol {
+        LexToolSymbol { symbol_name: String::from(name), match_rule: String::from(rule), case_insensitive: None }
+    }
+
+    #[test]
+    fn valid_lexer_has_no_problems() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<LexToolInput, ValidateLexerResult>::from(Box::new(ValidateLexerTool::new()));
+
+        let result  = tool.invoke(LexToolInput { new_tool_name: String::from("lexer"), symbols: vec![ symbol("Number", "[0-9]+"), symbol("Whitespace", "\\w+") ] }, &env);
+
+        assert!(result == Ok(ValidateLexerResult { valid: true, problems: vec![] }));
+    }
+
+    #[test]
+    fn detects_rule_matching_empty_string() {
+        let result = ValidateLexerTool::validate(&LexToolInput { new_tool_name: String::from("lexer"), symbols: vec![ symbol("Number", "[0-9]+"), symbol("Whitespace", "\\w*") ] });
+
+        assert!(result == vec![ LexerDiagnostic { problem: LexerProblem::MatchesEmptyString, symbol: Some(1), symbol_name: Some(String::from("Whitespace")) } ]);
+    }
+
+    #[test]
+    fn detects_duplicate_symbol_name() {
+        let result = ValidateLexerTool::validate(&LexToolInput { new_tool_name: String::from("lexer"), symbols: vec![ symbol("Word", "[a-z]+"), symbol("Number", "[0-9]+"), symbol("Word", "[A-Z]+") ] });
+
+        assert!(result == vec![ LexerDiagnostic { problem: LexerProblem::DuplicateSymbolName, symbol: Some(2), symbol_name: Some(String::from("Word")) } ]);
+    }
+
+    #[test]
+    fn detects_invalid_repetition_count() {
+        let result = ValidateLexerTool::validate(&LexToolInput { new_tool_name: String::from("lexer"), symbols: vec![ symbol("Number", "[0-9]{3,2}") ] });
+
+        assert!(result == vec![ LexerDiagnostic { problem: LexerProblem::InvalidRepetitionCount, symbol: Some(0), symbol_name: Some(String::from("Number")) } ]);
+    }
+
+    #[test]
+    fn can_call_from_lex_tools() {
+        let env = DynamicEnvironment::new();
+        env.import(super::super::LexTools::new());
+
+        let tool    = env.get_typed_tool::<LexToolInput, ValidateLexerResult>(super::super::tool::VALIDATE_LEXER).unwrap();
+        let result  = tool.invoke(LexToolInput { new_tool_name: String::from("lexer"), symbols: vec![] }, &env).unwrap();
+
+        assert!(!result.valid);
+    }
+
+    #[test]
+    fn detects_empty_lexer() {
+        let result = ValidateLexerTool::validate(&LexToolInput { new_tool_name: String::from("lexer"), symbols: vec![] });
+
+        assert!(result == vec![ LexerDiagnostic { problem: LexerProblem::NoSymbols, symbol: None, symbol_name: None } ]);
+    }
+}
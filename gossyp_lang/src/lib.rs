@@ -5,6 +5,7 @@ extern crate serde;
 #[macro_use] extern crate lazy_static;
 extern crate concordance;
 extern crate gossyp_base;
+extern crate gossyp_toolkit;
 
 pub mod lex;
 pub mod script;
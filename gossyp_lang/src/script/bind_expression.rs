@@ -1,11 +1,13 @@
-use std::rc::*;
+use std::sync::*;
 use std::result::Result;
 use serde_json::*;
+use gossyp_base::Environment;
 
 use super::script::*;
 use super::bound_script::*;
 use super::script_interpreter::*;
 use super::binding_environment::*;
+use super::evaluate_expression::*;
 
 ///
 /// Creates an unquoted version of a string
@@ -40,15 +42,53 @@ fn unquote_string(string: &str) -> String {
 }
 
 ///
-/// Parses a number string
+/// Returns the value of a constant keyword (`true`, `false` or `null`)
 ///
-fn parse_number(number: &str) -> Value {
-    if number.contains('.') || number.contains('e') || number.contains('E') {
-        json![ number.parse::<f64>().unwrap() ]
-    } else if number.starts_with("0x") {
-        json![ i64::from_str_radix(&number[2..], 16).unwrap() ]
+fn constant_value(keyword: &ScriptToken) -> Value {
+    match keyword.token {
+        ScriptLexerToken::True  => Value::Bool(true),
+        ScriptLexerToken::False => Value::Bool(false),
+        _                       => Value::Null
+    }
+}
+
+///
+/// Parses a number string, returning None if it is malformed
+///
+pub fn parse_number(number: &str) -> Option<Value> {
+    let (negative, unsigned) = if number.starts_with('-') { (true, &number[1..]) } else { (false, number) };
+
+    if unsigned.starts_with("0x") {
+        parse_integer(negative, &unsigned[2..], 16)
+    } else if unsigned.starts_with("0b") {
+        parse_integer(negative, &unsigned[2..], 2)
+    } else if unsigned.starts_with("0o") {
+        parse_integer(negative, &unsigned[2..], 8)
+    } else if unsigned.contains('.') || unsigned.contains('e') || unsigned.contains('E') {
+        unsigned.parse::<f64>().ok()
+            .map(|num| if negative { -num } else { num })
+            .and_then(|num| Number::from_f64(num))
+            .map(|num| Value::Number(num))
     } else {
-        json![ number.parse::<i64>().unwrap() ]
+        parse_integer(negative, unsigned, 10)
+    }
+}
+
+///
+/// Parses the digits of an integer in a particular radix, returning None if it won't fit in 64 bits
+///
+/// Negative numbers must fit in an i64, but positive numbers can use the full range of a u64.
+///
+fn parse_integer(negative: bool, digits: &str, radix: u32) -> Option<Value> {
+    if digits.starts_with('-') || digits.starts_with('+') {
+        // The only sign allowed is the one that's already been removed
+        None
+    } else if negative {
+        i64::from_str_radix(&format!("-{}", digits), radix).map(|num| json![ num ]).ok()
+    } else {
+        i64::from_str_radix(digits, radix).map(|num| json![ num ])
+            .or_else(|_| u64::from_str_radix(digits, radix).map(|num| json![ num ]))
+            .ok()
     }
 }
 
@@ -69,7 +109,7 @@ pub fn bind_tool(tool_name: &ScriptToken, expr: &Expression, binding_environment
     let bound_to = binding_environment.lookup(&tool_name.matched);
 
     match bound_to {
-        BindingResult::Tool(tool)           => Ok(BoundExpression::Tool(Rc::new(tool), tool_name.clone())),
+        BindingResult::Tool(tool)           => Ok(BoundExpression::Tool(Arc::new(tool), tool_name.clone())),
         BindingResult::Variable(variable)   => Ok(BoundExpression::Variable(variable, tool_name.clone())),
         BindingResult::Error(_)             => Err(generate_expression_error(ScriptEvaluationError::ExpressionDoesNotEvaluateToTool, expr))
     }
@@ -104,16 +144,37 @@ pub fn bind_tuple(items: &Vec<Expression>, binding_environment: &BindingEnvironm
         .map(|tuple_items| BoundExpression::Tuple(tuple_items))
 }
 
+///
+/// Binds the key of a map
+///
+/// A name is treated as a literal string, so `{ foo: 1 }` evaluates to `{ "foo": 1 }` without
+/// looking up a `foo` tool. Any other key, including a parenthesized identifier such as
+/// `{ (foo): 1 }`, is evaluated as normal.
+///
+pub fn bind_map_key(key: &MapKey, binding_environment: &BindingEnvironment) -> Result<BoundExpression, Value> {
+    match key {
+        &MapKey::Name(ref name)         => Ok(BoundExpression::Value(Value::String(name.matched.clone()), name.clone())),
+        &MapKey::Expression(ref key)    => bind_expression(key, binding_environment)
+    }
+}
+
 ///
 /// Generates a map binding
 ///
-pub fn bind_map(items: &Vec<(Expression, Expression)>, binding_environment: &BindingEnvironment) -> Result<BoundExpression, Value> {
+pub fn bind_map(items: &Vec<(MapKey, Expression)>, binding_environment: &BindingEnvironment) -> Result<BoundExpression, Value> {
     let mut result = vec![];
 
     for &(ref lexpr, ref rexpr) in items {
-        let lbound = bind_expression(lexpr, binding_environment)?;
+        let lbound = bind_map_key(lexpr, binding_environment)?;
         let rbound = bind_expression(rexpr, binding_environment)?;
 
+        // Constant keys can be checked now rather than waiting for the map to be evaluated
+        match lbound {
+            BoundExpression::Value(Value::String(_), _) => (),
+            BoundExpression::Value(_, _)                => return Err(generate_map_key_error(&lbound)),
+            _                                           => ()
+        }
+
         result.push((lbound, rbound));
     }
 
@@ -132,6 +193,19 @@ pub fn bind_index(index: &Box<(Expression, Expression)>, binding_environment: &B
     Ok(BoundExpression::Index(Box::new((bound_tool, bound_indexer))))
 }
 
+///
+/// Binds a slice expression (a[b:c])
+///
+pub fn bind_slice(slice: &Box<(Expression, Option<Expression>, Option<Expression>)>, binding_environment: &BindingEnvironment) -> Result<BoundExpression, Value> {
+    let (ref sliced, ref start, ref end) = **slice;
+
+    let bound_sliced    = bind_expression(sliced, binding_environment)?;
+    let bound_start     = match start { &Some(ref start) => Some(bind_expression(start, binding_environment)?), &None => None };
+    let bound_end       = match end { &Some(ref end) => Some(bind_expression(end, binding_environment)?), &None => None };
+
+    Ok(BoundExpression::Slice(Box::new((bound_sliced, bound_start, bound_end))))
+}
+
 pub fn bind_field_expression(expr: &Expression) -> Result<BoundExpression, Value> {
     match expr {
         &Expression::Identifier(ref token)  => Ok(BoundExpression::Field(token.matched.clone(), token.clone())),
@@ -163,13 +237,37 @@ pub fn bind_apply(apply: &Box<(Expression, Expression)>, binding_environment: &B
     Ok(BoundExpression::Apply(Box::new((bound_tool, bound_parameters))))
 }
 
+///
+/// Binds a binary operator expression (a + b)
+///
+pub fn bind_binary_op(operator: &ScriptToken, operands: &Box<(Expression, Expression)>, binding_environment: &BindingEnvironment) -> Result<BoundExpression, Value> {
+    let (ref lhs, ref rhs) = **operands;
+
+    let bound_lhs   = bind_expression(lhs, binding_environment)?;
+    let bound_rhs   = bind_expression(rhs, binding_environment)?;
+
+    Ok(BoundExpression::BinaryOp(operator.clone(), Box::new((bound_lhs, bound_rhs))))
+}
+
+///
+/// Binds a unary operator expression (not a)
+///
+pub fn bind_unary_op(operator: &ScriptToken, operand: &Box<Expression>, binding_environment: &BindingEnvironment) -> Result<BoundExpression, Value> {
+    let bound_operand = bind_expression(operand, binding_environment)?;
+
+    Ok(BoundExpression::UnaryOp(operator.clone(), Box::new(bound_operand)))
+}
+
 ///
 /// Binds an expression to an environment
 ///
 pub fn bind_expression(expr: &Expression, binding_environment: &BindingEnvironment) -> Result<BoundExpression, Value> {
     match expr {
         &Expression::String(ref s)              => Ok(BoundExpression::Value(Value::String(unquote_string(&s.matched)), s.clone())),
-        &Expression::Number(ref n)              => Ok(BoundExpression::Value(parse_number(&n.matched), n.clone())),
+        &Expression::Number(ref n)              => parse_number(&n.matched)
+            .map(|num| BoundExpression::Value(num, n.clone()))
+            .ok_or_else(|| generate_expression_error(ScriptEvaluationError::MalformedNumber, expr)),
+        &Expression::Constant(ref c)            => Ok(BoundExpression::Value(constant_value(c), c.clone())),
 
         &Expression::Array(ref items)           => bind_array(items, binding_environment),
         &Expression::Tuple(ref items)           => bind_tuple(items, binding_environment),
@@ -177,11 +275,26 @@ pub fn bind_expression(expr: &Expression, binding_environment: &BindingEnvironme
 
         &Expression::Identifier(ref id)         => bind_tool(id, expr, binding_environment),
         &Expression::Index(ref indexer)         => bind_index(indexer, binding_environment),
+        &Expression::Slice(ref slice)           => bind_slice(slice, binding_environment),
         &Expression::FieldAccess(ref accessor)  => bind_field_access(accessor, binding_environment),
         &Expression::Apply(ref application)     => bind_apply(application, binding_environment),
+        &Expression::BinaryOp(ref op, ref args) => bind_binary_op(op, args, binding_environment),
+        &Expression::UnaryOp(ref op, ref arg)   => bind_unary_op(op, arg, binding_environment),
     }
 }
 
+///
+/// Binds an expression that is not part of a script against an environment
+///
+/// The result can be evaluated any number of times, with any execution environment, without
+/// needing to be bound again.
+///
+pub fn bind_standalone_expression(expr: &Expression, environment: &Environment) -> Result<BoundExpression, Value> {
+    let binding_environment = BindingEnvironment::from_environment(environment);
+
+    bind_expression(expr, &*binding_environment)
+}
+
 #[cfg(test)]
 mod test {
     use gossyp_base::basic::*;
@@ -205,6 +318,30 @@ mod test {
         assert!(match bind_expression(&string_expr, &mut *env) { Ok(BoundExpression::Value(num, _)) => num == json![ 42 ], _ => false }); 
     }
 
+    #[test]
+    fn can_parse_signed_numbers() {
+        assert!(parse_number("-5") == Some(json![ -5 ]));
+        assert!(parse_number("-1.5") == Some(json![ -1.5 ]));
+        assert!(parse_number("-0x10") == Some(json![ -16 ]));
+        assert!(parse_number("-0b11") == Some(json![ -3 ]));
+        assert!(parse_number("-9223372036854775808") == Some(json![ i64::min_value() ]));
+        assert!(parse_number("18446744073709551615") == Some(json![ u64::max_value() ]));
+        assert!(parse_number("-18446744073709551615") == None);
+        assert!(parse_number("--5") == None);
+        assert!(parse_number("0x-5") == None);
+    }
+
+    #[test]
+    fn can_bind_constants() {
+        let empty_environment   = EmptyEnvironment::new();
+        let env                 = BindingEnvironment::from_environment(&empty_environment);
+        let constant            = |token, matched| Expression::Constant(ScriptToken::new(token, 0, 4, String::from(matched)));
+
+        assert!(match bind_expression(&constant(ScriptLexerToken::True, "true"), &*env) { Ok(BoundExpression::Value(value, _)) => value == Value::Bool(true), _ => false });
+        assert!(match bind_expression(&constant(ScriptLexerToken::False, "false"), &*env) { Ok(BoundExpression::Value(value, _)) => value == Value::Bool(false), _ => false });
+        assert!(match bind_expression(&constant(ScriptLexerToken::Null, "null"), &*env) { Ok(BoundExpression::Value(value, _)) => value == Value::Null, _ => false });
+    }
+
     #[test]
     fn can_bind_tool() {
         let tool_expr           = Expression::identifier("test");
@@ -254,7 +391,7 @@ mod test {
 
     #[test]
     fn can_bind_map() {
-        let map_expr            = Expression::Map(vec![(Expression::string("\"test\""), Expression::identifier("test"))]);
+        let map_expr            = Expression::Map(vec![(MapKey::Expression(Expression::string("\"test\"")), Expression::identifier("test"))]);
         let tool_environment    = DynamicEnvironment::new();
 
         tool_environment.define("test", Box::new(make_pure_tool(|_: ()| "Success")));
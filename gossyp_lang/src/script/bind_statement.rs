@@ -1,4 +1,7 @@
 use std::result::Result;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::*;
 
 use serde_json::*;
 
@@ -21,11 +24,23 @@ fn generate_statement_error(error: ScriptEvaluationError, script: &Script) -> Va
 ///
 /// Binds a sequnce in a script
 ///
-fn bind_sequence(sequence: &Vec<Script>, binding_environment: &mut BindingEnvironment) -> Result<Vec<BoundScript>, Value> {
+/// Tools defined by `def` don't exist until the script is evaluated, so any statements that
+/// follow a definition look up the defined tools when they're invoked. Other names are bound
+/// as normal, so misspelled names are still reported when the script is bound.
+///
+fn bind_sequence(sequence: &[Script], binding_environment: &mut BindingEnvironment) -> Result<Vec<BoundScript>, Value> {
     let mut result = vec![];
 
-    for statement in sequence {
+    for (index, statement) in sequence.iter().enumerate() {
         result.push(bind_statement_without_allocation(statement, binding_environment)?);
+
+        if let &Script::Def(ref name, _, _) = statement {
+            let defined_tools = vec![name.matched.clone()].into_iter().collect::<HashSet<_>>();
+            let mut late_binding_environment = BindingEnvironment::with_defined_tools(binding_environment, defined_tools);
+            result.extend(bind_sequence(&sequence[(index+1)..], &mut *late_binding_environment)?);
+
+            break;
+        }
     }
 
     Ok(result)
@@ -56,6 +71,93 @@ fn get_variable_name(name: &ScriptToken, script: &Script, binding_environment: &
     }
 }
 
+///
+/// Binds a using statement
+///
+/// The tools supplied by the using expression aren't known until the script is evaluated, and
+/// they replace any tools with the same name outside of the block, so every tool in the block
+/// is looked up when it's invoked.
+///
+fn bind_using(expr: &Expression, block: &Script, binding_environment: &mut BindingEnvironment) -> Result<BoundScript, Value> {
+    let bound_expr = bind_expression(expr, binding_environment)?;
+
+    let bound_block = {
+        let mut using_environment   = BindingEnvironment::with_replaceable_tools(binding_environment);
+        let mut block_environment   = using_environment.create_sub_environment();
+
+        bind_statement_without_allocation(block, &mut *block_environment)?
+    };
+
+    Ok(BoundScript::Using(bound_expr, Box::new(bound_block)))
+}
+
+///
+/// Binds a loop statement
+///
+fn bind_loop(block: &Script, binding_environment: &mut BindingEnvironment) -> Result<BoundScript, Value> {
+    let bound_block = {
+        let mut block_environment = binding_environment.create_sub_environment();
+        bind_statement_without_allocation(block, &mut *block_environment)?
+    };
+
+    Ok(BoundScript::Loop(Box::new(bound_block)))
+}
+
+///
+/// Binds a while statement
+///
+fn bind_while(expr: &Expression, block: &Script, binding_environment: &mut BindingEnvironment) -> Result<BoundScript, Value> {
+    let bound_expr  = bind_expression(expr, binding_environment)?;
+    let bound_block = {
+        let mut block_environment = binding_environment.create_sub_environment();
+        bind_statement_without_allocation(block, &mut *block_environment)?
+    };
+
+    Ok(BoundScript::While(bound_expr, Box::new(bound_block)))
+}
+
+///
+/// Binds a for statement
+///
+/// The loop variable is only visible inside the block
+///
+fn bind_for(name: &ScriptToken, expr: &Expression, block: &Script, script: &Script, binding_environment: &mut BindingEnvironment) -> Result<BoundScript, Value> {
+    let bound_expr                  = bind_expression(expr, binding_environment)?;
+    let (variable, bound_block)     = {
+        let mut block_environment   = binding_environment.create_sub_environment();
+        let variable                = bind_variable_name(name, script, &mut *block_environment)?;
+
+        (variable, bind_statement_without_allocation(block, &mut *block_environment)?)
+    };
+
+    Ok(BoundScript::For(variable, bound_expr, Box::new(bound_block), name.clone()))
+}
+
+///
+/// Binds a def statement
+///
+/// The body is bound separately from the rest of the script, with the arguments as its first
+/// variables. It can't see the script's variables, and the tools it uses are looked up when
+/// it's invoked (so tools can call themselves or tools that are defined later on).
+///
+fn bind_def(arguments: &Vec<ScriptToken>, block: &Script, script: &Script) -> Result<(u32, BoundScript), Value> {
+    let mut argument_variables = HashMap::new();
+    for (index, argument) in arguments.iter().enumerate() {
+        if argument_variables.insert(argument.matched.clone(), index as u32).is_some() {
+            return Err(generate_statement_error(ScriptEvaluationError::VariableNameAlreadyInUse, script));
+        }
+    }
+
+    let num_arguments   = arguments.len() as u32;
+    let mut variables   = BindingEnvironment::with_variables(argument_variables, num_arguments);
+    let bound_block     = {
+        let mut block_environment = BindingEnvironment::with_late_bound_tools(&mut *variables);
+        bind_statement(block, &mut *block_environment)?
+    };
+
+    Ok((num_arguments, bound_block))
+}
+
 ///
 /// Binds a statement to an environment (does not allocate space for variables)
 ///
@@ -65,10 +167,21 @@ fn bind_statement_without_allocation(script: &Script, binding_environment: &mut
     match *script {
         Script::RunCommand(ref expr)        => Ok(RunCommand(bind_expression(expr, binding_environment)?)),
         Script::Sequence(ref parts)         => Ok(Sequence(bind_sequence(parts, binding_environment)?)),
+        Script::Let(ref name, ref expr)     => Ok(Let(bind_variable_name(name, script, binding_environment)?, bind_expression(expr, binding_environment)?, name.clone())),
         Script::Var(ref name, ref expr)     => Ok(Var(bind_variable_name(name, script, binding_environment)?, bind_expression(expr, binding_environment)?, name.clone())),
         Script::Assign(ref name, ref expr)  => Ok(Assign(get_variable_name(name, script, binding_environment)?, bind_expression(expr, binding_environment)?, name.clone())),
+        Script::Using(ref expr, ref block)  => bind_using(expr, block, binding_environment),
+        Script::Loop(ref block)             => bind_loop(block, binding_environment),
+        Script::While(ref expr, ref block)  => bind_while(expr, block, binding_environment),
+        Script::For(ref name, ref expr, ref block) => bind_for(name, expr, block, script, binding_environment),
+        Script::Break(None)                 => Ok(Break(None)),
+        Script::Break(Some(ref expr))       => Ok(Break(Some(bind_expression(expr, binding_environment)?))),
+        Script::Def(ref name, ref arguments, ref block) => {
+            let (num_arguments, bound_block) = bind_def(arguments, block, script)?;
+            Ok(Def(name.clone(), num_arguments, Arc::new(bound_block)))
+        },
 
-        _ => unimplemented!()
+        _ => Err(generate_statement_error(ScriptEvaluationError::StatementNotImplemented, script))
     }
 }
 
@@ -1,11 +1,16 @@
 use std::result::Result;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::cmp;
 
+use serde_json::*;
+
 use gossyp_base::RetrieveToolError;
 use gossyp_base::Environment;
 use gossyp_base::Tool;
 
+use super::script_interpreter::*;
+
 ///
 /// Errors that can occur when binding a variable
 ///
@@ -64,6 +69,60 @@ struct ChildBindingEnvironment<'a> {
     bindings: HashMap<String, u32>
 }
 
+///
+/// The names that a late binding environment looks up when they're invoked
+///
+enum LateBoundNames {
+    /// Any name that isn't a variable is a tool that is looked up when it's invoked
+    AllTools,
+
+    /// Names that can't be found are looked up when they're invoked
+    UnknownNames,
+
+    /// Only these names are looked up when they're invoked, even if they already exist in the base environment
+    DefinedTools(HashSet<String>)
+}
+
+///
+/// Binding environment where some tools are looked up when they are invoked rather than when
+/// the script is bound
+///
+struct LateBindingEnvironment<'a> {
+    /// The binding environment to look names up in first
+    base_environment: &'a mut BindingEnvironment,
+
+    /// The names that are looked up when they're invoked
+    late_bound_names: LateBoundNames
+}
+
+///
+/// Tool that looks up another tool by name in the environment it is invoked in
+///
+pub struct LateBoundTool {
+    /// The name of the tool to look up
+    name: String
+}
+
+impl LateBoundTool {
+    ///
+    /// Creates a tool that will invoke the tool with the specified name
+    ///
+    pub fn new(name: &str) -> LateBoundTool {
+        LateBoundTool { name: String::from(name) }
+    }
+}
+
+impl Tool for LateBoundTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let tool = environment.get_json_tool(&self.name).map_err(|_| json![{
+            "error":        ScriptEvaluationError::ToolNameNotFound,
+            "tool-name":    self.name
+        }])?;
+
+        tool.invoke_json(input, environment)
+    }
+}
+
 ///
 /// Trait implemented by objects that represent a binding environment
 ///
@@ -109,6 +168,16 @@ impl BindingEnvironment {
         })
     }
 
+    ///
+    /// Creates a binding environment where some variables have already been allocated
+    ///
+    pub fn with_variables(variable_names: HashMap<String, u32>, num_variables: u32) -> Box<VariableBindingEnvironment> {
+        Box::new(VariableBindingEnvironment {
+            next_to_allocate:   num_variables,
+            bindings:           variable_names
+        })
+    }
+
     ///
     /// Creates a new binding environment which will fetch tools from an outside environment
     ///
@@ -126,6 +195,35 @@ impl BindingEnvironment {
         Box::new(tool_environment)
     }
 
+    ///
+    /// Creates a binding environment where any names that aren't found in a base environment are
+    /// bound to tools that will be looked up when the script is evaluated
+    ///
+    pub fn with_late_bound_tools<'a>(base_environment: &'a mut BindingEnvironment) -> Box<BindingEnvironment+'a> {
+        Box::new(LateBindingEnvironment { base_environment: base_environment, late_bound_names: LateBoundNames::UnknownNames })
+    }
+
+    ///
+    /// Creates a binding environment where every name that isn't a variable is bound to a tool that
+    /// will be looked up when the script is evaluated
+    ///
+    /// This is used for `using` blocks: the tools they supply aren't known until the script runs and
+    /// can replace tools that already exist in the base environment.
+    ///
+    pub fn with_replaceable_tools<'a>(base_environment: &'a mut BindingEnvironment) -> Box<BindingEnvironment+'a> {
+        Box::new(LateBindingEnvironment { base_environment: base_environment, late_bound_names: LateBoundNames::AllTools })
+    }
+
+    ///
+    /// Creates a late binding environment where the specified tool names are always looked up
+    /// when they are invoked, even if they already exist in the base environment
+    ///
+    /// Other names are bound as they would be in the base environment.
+    ///
+    pub fn with_defined_tools<'a>(base_environment: &'a mut BindingEnvironment, defined_tools: HashSet<String>) -> Box<BindingEnvironment+'a> {
+        Box::new(LateBindingEnvironment { base_environment: base_environment, late_bound_names: LateBoundNames::DefinedTools(defined_tools) })
+    }
+
     ///
     /// Combines two binding environments into a single environment
     ///
@@ -137,6 +235,15 @@ impl BindingEnvironment {
     }
 }
 
+impl VariableBindingEnvironment {
+    ///
+    /// Retrieves the names of the variables that have been allocated in this environment
+    ///
+    pub fn get_variable_names(&self) -> HashMap<String, u32> {
+        self.bindings.clone()
+    }
+}
+
 impl BindingEnvironment for VariableBindingEnvironment {
     ///
     /// Creates a new sub-environment, where new variable names can be a
@@ -269,6 +376,37 @@ impl<'a> BindingEnvironment for ChildBindingEnvironment<'a> {
     }
 }
 
+impl<'a> BindingEnvironment for LateBindingEnvironment<'a> {
+    fn allocate_location(&mut self) -> u32 {
+        self.base_environment.allocate_location()
+    }
+
+    fn allocate_variable(&mut self, name: &str) -> Result<u32, BindingError> {
+        self.base_environment.allocate_variable(name)
+    }
+
+    fn lookup(&self, name: &str) -> BindingResult {
+        match (self.base_environment.lookup(name), &self.late_bound_names) {
+            (BindingResult::Variable(variable), _)                                  => BindingResult::Variable(variable),
+            (_, &LateBoundNames::AllTools)                                          => BindingResult::Tool(Box::new(LateBoundTool::new(name))),
+            (BindingResult::Error(_), &LateBoundNames::UnknownNames)                => BindingResult::Tool(Box::new(LateBoundTool::new(name))),
+            (_, &LateBoundNames::DefinedTools(ref names)) if names.contains(name)   => BindingResult::Tool(Box::new(LateBoundTool::new(name))),
+            (found, _)                                                              => found
+        }
+    }
+
+    fn get_number_of_variables(&self) -> u32 {
+        self.base_environment.get_number_of_variables()
+    }
+
+    fn create_sub_environment<'b>(&'b mut self) -> Box<BindingEnvironment + 'b> {
+        Box::new(ChildBindingEnvironment {
+            base_environment:   self,
+            bindings:           HashMap::new()
+        })
+    }
+}
+
 impl<'a> BindingEnvironment for (&'a mut BindingEnvironment, &'a BindingEnvironment) {
     fn allocate_location(&mut self) -> u32 {
         let (ref mut primary, _) = *self;
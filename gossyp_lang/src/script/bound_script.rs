@@ -1,4 +1,5 @@
-use std::rc::*;
+use std::sync::*;
+use std::cmp;
 use serde_json::*;
 
 use gossyp_base::*;
@@ -25,7 +26,7 @@ pub enum BoundExpression {
     // -- Identifier bindings
 
     /// Identifier that was bound to a particular tool from the script environment
-    Tool(Rc<Box<Tool>>, ScriptToken),
+    Tool(Arc<Box<Tool>>, ScriptToken),
 
     /// Identifier that was bound to a particular variable from the script environment
     Variable(u32, ScriptToken),
@@ -38,11 +39,75 @@ pub enum BoundExpression {
     /// a[b]
     Index(Box<(BoundExpression, BoundExpression)>),
 
+    /// a[b:c]
+    Slice(Box<(BoundExpression, Option<BoundExpression>, Option<BoundExpression>)>),
+
     /// a.b
     FieldAccess(Box<(BoundExpression, BoundExpression)>),
 
     /// a(parameters)
-    Apply(Box<(BoundExpression, BoundExpression)>)
+    Apply(Box<(BoundExpression, BoundExpression)>),
+
+    /// a + b (the token is the operator)
+    BinaryOp(ScriptToken, Box<(BoundExpression, BoundExpression)>),
+
+    /// not a (the token is the operator)
+    UnaryOp(ScriptToken, Box<BoundExpression>)
+}
+
+impl BoundExpression {
+    ///
+    /// Returns the range of the source script covered by this expression, if it's known
+    ///
+    pub fn span(&self) -> Option<(i32, i32)> {
+        use self::BoundExpression::*;
+
+        match self {
+            &Value(_, ref token)            |
+            &Tool(_, ref token)             |
+            &Variable(_, ref token)         |
+            &Field(_, ref token)            => Some((token.start, token.end)),
+
+            &Array(ref items)               |
+            &Tuple(ref items)               => BoundExpression::combine_spans(items.iter().map(|item| item.span())),
+            &Map(ref items)                 => BoundExpression::combine_spans(items.iter().flat_map(|&(ref key, ref value)| vec![key.span(), value.span()])),
+
+            &Index(ref pair)                |
+            &FieldAccess(ref pair)          |
+            &Apply(ref pair)                => {
+                let (ref lhs, ref rhs) = **pair;
+                BoundExpression::combine_spans(vec![lhs.span(), rhs.span()].into_iter())
+            },
+
+            &Slice(ref slice)               => {
+                let (ref lhs, ref start, ref end) = **slice;
+                BoundExpression::combine_spans(vec![lhs.span(), start.as_ref().and_then(|start| start.span()), end.as_ref().and_then(|end| end.span())].into_iter())
+            },
+
+            &BinaryOp(ref operator, ref pair) => {
+                let (ref lhs, ref rhs) = **pair;
+                BoundExpression::combine_spans(vec![lhs.span(), Some((operator.start, operator.end)), rhs.span()].into_iter())
+            },
+
+            &UnaryOp(ref operator, ref operand) => {
+                BoundExpression::combine_spans(vec![Some((operator.start, operator.end)), operand.span()].into_iter())
+            }
+        }
+    }
+
+    ///
+    /// Finds the range that covers a set of spans
+    ///
+    fn combine_spans<TSpans: Iterator<Item=Option<(i32, i32)>>>(spans: TSpans) -> Option<(i32, i32)> {
+        spans
+            .filter_map(|span| span)
+            .fold(None, |combined, (start, end)| {
+                match combined {
+                    None                                    => Some((start, end)),
+                    Some((combined_start, combined_end))    => Some((cmp::min(start, combined_start), cmp::max(end, combined_end)))
+                }
+            })
+    }
 }
 
 ///
@@ -72,10 +137,16 @@ pub enum BoundScript {
 
     /// while expr { stuff }
     While(BoundExpression, Box<BoundScript>),
+
+    /// for a in expr { stuff }
+    For(u32, BoundExpression, Box<BoundScript>, ScriptToken),
+
+    /// break (expr)
+    Break(Option<BoundExpression>),
     
     /// using expr { stuff }
     Using(BoundExpression, Box<BoundScript>),
 
-    /// def tool pattern { stuff }
-    Def(ScriptToken, BoundExpression, Box<BoundScript>)
+    /// def tool(arguments) { stuff } (the body is bound with the arguments as its first variables)
+    Def(ScriptToken, u32, Arc<BoundScript>)
 }
@@ -0,0 +1,244 @@
+//!
+//! The calc tool evaluates simple arithmetic expressions
+//!
+//! This is independent of the main scripting language: it only understands numbers, the operators
+//! `+ - * /` and parentheses, so it can't call tools or otherwise affect its environment.
+//!
+
+use std::result::Result;
+use std::error::Error;
+
+use serde_json::*;
+use gossyp_base::*;
+
+use super::script::*;
+use super::lex_script_tool::*;
+use super::bind_expression::*;
+
+///
+/// Tool that evaluates an arithmetic expression supplied as a string
+///
+/// The usual precedence rules apply, so `"2 * (3 + 4)"` evaluates to 14. Results that are whole
+/// numbers are returned as integers.
+///
+pub struct CalcTool {
+}
+
+///
+/// Parser/evaluator for calc expressions
+///
+/// The grammar is:
+///
+/// ```text
+/// expression  := term (('+' | '-') term)*
+/// term        := factor (('*' | '/') factor)*
+/// factor      := number | '(' expression ')' | '-' factor
+/// ```
+///
+struct CalcState {
+    tokens:         Vec<ScriptToken>,
+    position:       usize,
+    end_of_input:   i32
+}
+
+impl CalcState {
+    ///
+    /// Creates the calc state for an expression
+    ///
+    fn new(expression: &str) -> CalcState {
+        let lexed = create_lex_script_tool().lex(expression);
+
+        let tokens = lexed.iter()
+            .map(|token| ScriptToken::from_lexer_match(token))
+            .filter(|token| match token.token {
+                ScriptLexerToken::Whitespace    |
+                ScriptLexerToken::Newline       |
+                ScriptLexerToken::Comment       => false,
+                _                               => true
+            })
+            .collect();
+
+        // The lexer reads '3 -4' as two numbers: when a negative number follows an operand, split it into a subtraction
+        let tokens = split_subtractions(tokens);
+
+        CalcState { tokens: tokens, position: 0, end_of_input: expression.len() as i32 }
+    }
+
+    ///
+    /// Generates an error at the current position
+    ///
+    fn error(&self, message: &str) -> Value {
+        let (start, end) = self.tokens.get(self.position)
+            .map(|token| (token.start, token.end))
+            .unwrap_or((self.end_of_input, self.end_of_input));
+
+        json![{
+            "error":    message,
+            "position": { "start": start, "end": end }
+        }]
+    }
+
+    ///
+    /// If the next token is the specified symbol, moves past it and returns true
+    ///
+    fn accept(&mut self, symbol: &str) -> bool {
+        let matches = self.tokens.get(self.position)
+            .map(|token| token.token == ScriptLexerToken::symbol(symbol))
+            .unwrap_or(false);
+
+        if matches {
+            self.position += 1;
+        }
+
+        matches
+    }
+
+    ///
+    /// Evaluates the whole expression, which must not be followed by any other tokens
+    ///
+    fn evaluate(&mut self) -> Result<f64, Value> {
+        let result = self.expression()?;
+
+        if self.position < self.tokens.len() {
+            Err(self.error("Unexpected token in expression"))
+        } else {
+            Ok(result)
+        }
+    }
+
+    fn expression(&mut self) -> Result<f64, Value> {
+        let mut result = self.term()?;
+
+        loop {
+            if self.accept("+") {
+                result += self.term()?;
+            } else if self.accept("-") {
+                result -= self.term()?;
+            } else {
+                return Ok(result);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<f64, Value> {
+        let mut result = self.factor()?;
+
+        loop {
+            if self.accept("*") {
+                result *= self.factor()?;
+            } else if self.accept("/") {
+                let divisor_pos = self.position;
+                let divisor     = self.factor()?;
+
+                if divisor == 0.0 {
+                    self.position = divisor_pos;
+                    return Err(self.error("Division by zero"));
+                }
+
+                result /= divisor;
+            } else {
+                return Ok(result);
+            }
+        }
+    }
+
+    fn factor(&mut self) -> Result<f64, Value> {
+        if self.accept("(") {
+            let result = self.expression()?;
+
+            if self.accept(")") {
+                Ok(result)
+            } else {
+                Err(self.error("Missing ')'"))
+            }
+
+        } else if self.accept("-") {
+            Ok(-self.factor()?)
+
+        } else {
+            let number = self.tokens.get(self.position)
+                .and_then(|token| if token.is_number() { parse_number(&token.matched) } else { None })
+                .and_then(|number| number.as_f64());
+
+            match number {
+                Some(number)    => { self.position += 1; Ok(number) },
+                None            => Err(self.error("Was expecting a number"))
+            }
+        }
+    }
+}
+
+impl CalcTool {
+    ///
+    /// Creates a new calc tool
+    ///
+    pub fn new() -> CalcTool {
+        CalcTool { }
+    }
+
+    ///
+    /// Evaluates an arithmetic expression
+    ///
+    pub fn calculate(expression: &str) -> Result<Value, Value> {
+        let result = CalcState::new(expression).evaluate()?;
+
+        // Whole numbers are returned as integers where they can be represented exactly
+        if result.fract() == 0.0 && result.abs() < 9007199254740992.0 {
+            Ok(json![ result as i64 ])
+        } else {
+            Number::from_f64(result)
+                .map(|number| Value::Number(number))
+                .ok_or_else(|| json![{ "error": "Result is not a finite number" }])
+        }
+    }
+}
+
+impl Tool for CalcTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        match from_value::<String>(input) {
+            Ok(expression)  => CalcTool::calculate(&expression),
+            Err(erm)        => Err(json![{
+                "error":        "calc must be called with a string",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn multiplication_has_precedence() {
+        assert!(CalcTool::calculate("2 + 3 * 4") == Ok(json![14]));
+        assert!(CalcTool::calculate("2 * 3 - 4 / 2") == Ok(json![4]));
+        assert!(CalcTool::calculate("10-4-3") == Ok(json![3]));
+    }
+
+    #[test]
+    fn parentheses_override_precedence() {
+        let env     = EmptyEnvironment::new();
+        let tool    = CalcTool::new();
+
+        assert!(tool.invoke_json(json!["2 * (3 + 4)"], &env) == Ok(json![14]));
+        assert!(tool.invoke_json(json!["-(1 + 2) * 0.5"], &env) == Ok(json![-1.5]));
+    }
+
+    #[test]
+    fn division_by_zero_is_error() {
+        let result = CalcTool::calculate("1 / (2 - 2)");
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err()["error"] == json!["Division by zero"]);
+    }
+
+    #[test]
+    fn malformed_expressions_are_errors() {
+        assert!(CalcTool::calculate("2 +").unwrap_err()["position"] == json![{ "start": 3, "end": 3 }]);
+        assert!(CalcTool::calculate("(2 + 3").unwrap_err()["error"] == json!["Missing ')'"]);
+        assert!(CalcTool::calculate("2 3").unwrap_err()["error"] == json!["Unexpected token in expression"]);
+        assert!(CalcTool::calculate("2 * foo").unwrap_err()["position"] == json![{ "start": 4, "end": 7 }]);
+    }
+}
@@ -0,0 +1,98 @@
+//!
+//! The check-script tool reports whether or not a script parses, without evaluating it
+//!
+
+use std::result::Result;
+use std::error::Error;
+
+use serde_json::*;
+use gossyp_base::*;
+
+use super::lex_script_tool::*;
+use super::parse_script_tool::*;
+
+///
+/// Tool that lexes and parses a script and reports any errors
+///
+/// The result is `{ "ok": true }` if the script is valid, or `{ "ok": false, "errors": [ ... ] }`
+/// if it is not. Each error has a message and the position in the source where it occurred.
+///
+pub struct CheckScriptTool {
+}
+
+impl CheckScriptTool {
+    ///
+    /// Creates a new check-script tool
+    ///
+    pub fn new() -> CheckScriptTool {
+        CheckScriptTool { }
+    }
+
+    ///
+    /// Generates the JSON description of a parse error
+    ///
+    fn describe_error(error: &ParseError) -> Value {
+        let position = error.position()
+            .map(|(start, end)| json![{ "start": start, "end": end }])
+            .unwrap_or(Value::Null);
+
+        json![{
+            "message":  error.message,
+            "position": position
+        }]
+    }
+
+    ///
+    /// Checks a script, returning the result of the check-script tool
+    ///
+    pub fn check(script: &str) -> Value {
+        let lexed = create_lex_script_tool().lex(script);
+
+        match ParseScriptTool::parse_all(&lexed) {
+            Ok(_)       => json![{ "ok": true }],
+            Err(errors) => json![{
+                "ok":       false,
+                "errors":   errors.iter().map(|error| CheckScriptTool::describe_error(error)).collect::<Vec<_>>()
+            }]
+        }
+    }
+}
+
+impl Tool for CheckScriptTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        match from_value::<String>(input) {
+            Ok(script)  => Ok(CheckScriptTool::check(&script)),
+            Err(erm)    => Err(json![{
+                "error":        "check-script must be called with a string",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn valid_script_is_ok() {
+        let env     = EmptyEnvironment::new();
+        let tool    = CheckScriptTool::new();
+
+        assert!(tool.invoke_json(json!["let x = 1\nfoo x"], &env) == Ok(json![{ "ok": true }]));
+    }
+
+    #[test]
+    fn reports_all_errors() {
+        let env     = EmptyEnvironment::new();
+        let tool    = CheckScriptTool::new();
+
+        let result  = tool.invoke_json(json!["let = 1\nfoo\nlet x 2"], &env).unwrap();
+
+        assert!(result["ok"] == json![false]);
+        assert!(result["errors"].as_array().unwrap().len() == 2);
+        assert!(result["errors"][0]["position"] == json![{ "start": 4, "end": 5 }]);
+        assert!(result["errors"][1]["position"] == json![{ "start": 18, "end": 19 }]);
+    }
+}
@@ -0,0 +1,161 @@
+//!
+//! The define-many tool defines several tools at once
+//!
+
+use std::result::Result;
+use std::error::Error;
+
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+use super::define_script_tool::*;
+use super::super::lex::*;
+
+///
+/// A single tool definition passed to the define-many tool
+///
+/// Tools are either defined by a script (`source`) or as a lexer (`symbols`, in the same form
+/// as the input to the lex tool)
+///
+#[derive(Serialize, Deserialize)]
+pub struct DefineManyEntry {
+    /// The name of the tool to define
+    pub name: String,
+
+    /// The source of the script that the new tool will run
+    pub source: Option<String>,
+
+    /// The symbols matched by the new lexer tool
+    pub symbols: Option<Vec<LexToolSymbol>>
+}
+
+///
+/// The outcome of defining a single tool
+///
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct DefineManyResult {
+    /// The name of the tool, if the entry had one
+    pub name: Option<String>,
+
+    /// True if the tool was defined
+    pub defined: bool,
+
+    /// The reason the tool could not be defined
+    pub error: Option<Value>
+}
+
+///
+/// Tool that takes an array of tool definitions and defines all of them
+///
+/// Entries are defined in order, so scripts can use the tools defined by earlier entries. An
+/// entry that can't be defined doesn't stop the others: the result has an entry for each
+/// definition indicating whether or not it succeeded.
+///
+pub struct DefineManyTool {
+}
+
+impl DefineManyTool {
+    ///
+    /// Creates a new define-many tool
+    ///
+    pub fn new() -> DefineManyTool {
+        DefineManyTool { }
+    }
+
+    ///
+    /// Defines the tool described by a single entry
+    ///
+    fn define_entry(entry: DefineManyEntry, environment: &Environment) -> Result<(), Value> {
+        match entry {
+            DefineManyEntry { name, source: Some(source), symbols: None } => {
+                let script_tool = BoundScriptTool::compile(&source, environment)?;
+                define_new_tool(environment, &name, Box::new(script_tool))?;
+
+                Ok(())
+            },
+
+            DefineManyEntry { name, source: None, symbols: Some(symbols) } => {
+                let lex_input = LexToolInput { new_tool_name: name, symbols: symbols };
+                LexTool::new().invoke_json(to_value(lex_input).unwrap(), environment)?;
+
+                Ok(())
+            },
+
+            _ => Err(json![{
+                "error": "Tool definitions must have exactly one of 'source' or 'symbols'"
+            }])
+        }
+    }
+
+    ///
+    /// Defines each tool in a list of definitions, returning the outcome of each one
+    ///
+    pub fn define_many(definitions: Vec<Value>, environment: &Environment) -> Vec<DefineManyResult> {
+        definitions.into_iter()
+            .map(|definition| {
+                let name    = definition["name"].as_str().map(|name| String::from(name));
+                let entry   = from_value::<DefineManyEntry>(definition).map_err(|erm| json![{
+                    "error":        "Tool definitions must be of the form { \"name\": <tool_name>, \"source\": <script> } or { \"name\": <tool_name>, \"symbols\": [ ... ] }",
+                    "description":  erm.description()
+                }]);
+
+                match entry.and_then(|entry| DefineManyTool::define_entry(entry, environment)) {
+                    Ok(())      => DefineManyResult { name: name, defined: true, error: None },
+                    Err(error)  => DefineManyResult { name: name, defined: false, error: Some(error) }
+                }
+            })
+            .collect()
+    }
+}
+
+impl Tool for DefineManyTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        match from_value::<Vec<Value>>(input) {
+            Ok(definitions) => Ok(to_value(DefineManyTool::define_many(definitions, environment)).unwrap()),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to define-many must be an array of tool definitions",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn invalid_entry_does_not_stop_other_definitions() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "add", |(a, b): (i32, i32)| a+b).is_ok());
+
+        let result = DefineManyTool::new().invoke_json(json![[
+            { "name": "double",     "source": "add (input, input)" },
+            { "name": "broken",     "source": "[ 1," },
+            { "name": "quadruple",  "source": "double(double(input))" },
+            { "name": "words",      "symbols": [ { "symbol_name": "Word", "match_rule": "[a-z]+" } ] }
+        ]], &env).unwrap();
+        let result = from_value::<Vec<DefineManyResult>>(result).unwrap();
+
+        assert!(result.len() == 4);
+        assert!(result.iter().map(|entry| entry.defined).collect::<Vec<_>>() == vec![ true, false, true, true ]);
+        assert!(result[1].name == Some(String::from("broken")));
+        assert!(result[1].error.is_some());
+
+        assert!(env.get_json_tool("broken").is_err());
+        assert!(env.get_json_tool("quadruple").unwrap().invoke_json(json![ 3 ], &env) == Ok(json![ 12 ]));
+        assert!(env.get_json_tool("words").is_ok());
+    }
+
+    #[test]
+    fn entry_without_definition_is_an_error() {
+        let env     = DynamicEnvironment::new();
+        let result  = DefineManyTool::define_many(vec![ json![{ "name": "nothing" }], json![ 42 ] ], &env);
+
+        assert!(result[0] == DefineManyResult { name: Some(String::from("nothing")), defined: false, error: Some(json![{ "error": "Tool definitions must have exactly one of 'source' or 'symbols'" }]) });
+        assert!(result[1].name == None);
+        assert!(!result[1].defined);
+    }
+}
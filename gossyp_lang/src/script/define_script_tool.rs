@@ -0,0 +1,199 @@
+//!
+//! The define-script-tool tool creates a new tool in the environment that runs a script
+//!
+
+use std::result::Result;
+use std::error::Error;
+use std::collections::HashMap;
+
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+use super::script::*;
+use super::bound_script::*;
+use super::lex_script_tool::*;
+use super::parse_script_tool::*;
+use super::binding_environment::*;
+use super::bind_statement::*;
+use super::evaluate_statement::*;
+use super::script_interpreter::*;
+
+/// The name of the variable that contains the input to a script tool
+pub const SCRIPT_TOOL_INPUT: &'static str = "input";
+
+///
+/// Parameters that can be passed to the define-script-tool tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct DefineScriptToolParameters {
+    /// The name of the tool to define
+    pub name: String,
+
+    /// The source of the script that the new tool will run
+    pub source: String
+}
+
+impl DefineScriptToolParameters {
+    pub fn new(name: &str, source: &str) -> DefineScriptToolParameters {
+        DefineScriptToolParameters { name: String::from(name), source: String::from(source) }
+    }
+}
+
+///
+/// A tool that runs a script that has already been bound
+///
+/// The input to the tool is stored in the `input` variable, and the result is the value of
+/// the last statement in the script.
+///
+pub struct BoundScriptTool {
+    script: BoundScript
+}
+
+impl BoundScriptTool {
+    ///
+    /// Creates the binding environment for a script tool, where the input is the first variable
+    ///
+    fn input_variables() -> Box<VariableBindingEnvironment> {
+        let mut input_variable = HashMap::new();
+        input_variable.insert(String::from(SCRIPT_TOOL_INPUT), 0);
+
+        BindingEnvironment::with_variables(input_variable, 1)
+    }
+
+    ///
+    /// Compiles a script so it can be run as a tool in the specified environment
+    ///
+    pub fn compile(source: &str, environment: &Environment) -> Result<BoundScriptTool, Value> {
+        // Parse the script
+        let lexed   = create_lex_script_tool().lex(source);
+        let parsed  = ParseScriptTool::parse(&lexed).map_err(|parse_error| to_value(parse_error).unwrap())?;
+
+        // Bind it
+        let mut variables   = BoundScriptTool::input_variables();
+        let bound           = {
+            let tools       = BindingEnvironment::from_environment(environment);
+            let mut binding = BindingEnvironment::combine(&mut *variables, &*tools);
+
+            bind_statement(&Script::Sequence(parsed), &mut *binding)?
+        };
+
+        Ok(BoundScriptTool { script: bound })
+    }
+
+    ///
+    /// Binds a parsed script so it can be run as a tool
+    ///
+    /// There's no environment to bind against, so any name that isn't a variable is treated as
+    /// a tool that is looked up in the environment the tool is invoked in.
+    ///
+    pub fn from_statements(statements: Vec<Script>) -> Result<BoundScriptTool, Value> {
+        let mut variables   = BoundScriptTool::input_variables();
+        let bound           = {
+            let mut binding = BindingEnvironment::with_late_bound_tools(&mut *variables);
+            bind_statement(&Script::Sequence(statements), &mut *binding)?
+        };
+
+        Ok(BoundScriptTool { script: bound })
+    }
+}
+
+impl Tool for BoundScriptTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        // Store the input in its variable
+        let mut execution_environment = ScriptExecutionEnvironment::new();
+        execution_environment.allocate_variables(1);
+        execution_environment.set_variable(0, Box::new(input));
+
+        // The result of a sequence is the result of each statement: a tool only produces the last one
+        match evaluate_statement(&self.script, environment, &mut execution_environment)? {
+            Value::Array(mut results)   => Ok(results.pop().unwrap_or(Value::Null)),
+            other                       => Ok(other)
+        }
+    }
+}
+
+///
+/// Converts a parsed script into a tool
+///
+/// The script is bound once, and the input to the tool is available in the `input` variable.
+///
+pub fn script_to_tool(statements: Vec<Script>) -> Result<Box<Tool>, Value> {
+    Ok(Box::new(BoundScriptTool::from_statements(statements)?))
+}
+
+///
+/// Tool that defines a new tool in the environment from a script
+///
+/// The script is compiled once, when the tool is defined.
+///
+pub struct DefineScriptTool {
+}
+
+impl DefineScriptTool {
+    ///
+    /// Creates a new define-script-tool tool
+    ///
+    pub fn new() -> DefineScriptTool {
+        DefineScriptTool { }
+    }
+}
+
+impl Tool for DefineScriptTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<DefineScriptToolParameters>(input);
+
+        match parameters {
+            Ok(DefineScriptToolParameters { name, source }) => {
+                let script_tool = BoundScriptTool::compile(&source, environment)?;
+                define_new_tool(environment, &name, Box::new(script_tool))?;
+
+                Ok(Value::Null)
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to define-script-tool must be of the form { \"name\": <tool_name>, \"source\": <script> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn can_define_double_tool_from_script() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "add", |(a, b): (i32, i32)| a+b).is_ok());
+
+        let define = DefineScriptTool::new();
+        assert!(define.invoke_json(to_value(DefineScriptToolParameters::new("double", "add (input, input)")).unwrap(), &env).is_ok());
+
+        let double = env.get_json_tool("double").unwrap();
+        assert!(double.invoke_json(json![ 2 ], &env) == Ok(json![ 4 ]));
+        assert!(double.invoke_json(json![ 21 ], &env) == Ok(json![ 42 ]));
+    }
+
+    #[test]
+    fn script_tool_can_read_input() {
+        let env         = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "add", |(a, b): (i32, i32)| a+b).is_ok());
+
+        let statements  = ParseScriptTool::parse(&create_lex_script_tool().lex("var x = input\nadd (x, 1)")).unwrap();
+        let tool        = script_to_tool(statements).unwrap();
+
+        assert!(tool.invoke_json(json![ 41 ], &env) == Ok(json![ 42 ]));
+        assert!(tool.invoke_json(json![ 1 ], &env) == Ok(json![ 2 ]));
+    }
+
+    #[test]
+    fn script_with_parse_error_is_not_defined() {
+        let env     = DynamicEnvironment::new();
+        let define  = DefineScriptTool::new();
+
+        assert!(define.invoke_json(to_value(DefineScriptToolParameters::new("broken", "[ 1,")).unwrap(), &env).is_err());
+        assert!(env.get_json_tool("broken").is_err());
+    }
+}
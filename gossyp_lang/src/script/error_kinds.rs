@@ -0,0 +1,62 @@
+//!
+//! The error-kinds tool lists the kinds of error that can occur when evaluating a script
+//!
+
+use std::result::Result;
+
+use serde_json::*;
+use gossyp_base::*;
+
+use super::script_interpreter::*;
+
+///
+/// Tool that returns the names of all of the kinds of script evaluation error
+///
+pub struct ErrorKindsTool {
+}
+
+impl ErrorKindsTool {
+    ///
+    /// Creates a new error-kinds tool
+    ///
+    pub fn new() -> ErrorKindsTool {
+        ErrorKindsTool { }
+    }
+}
+
+impl Tool for ErrorKindsTool {
+    fn invoke_json(&self, _input: Value, _environment: &Environment) -> Result<Value, Value> {
+        Ok(to_value(ScriptEvaluationError::all()).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn includes_defined_errors() {
+        let env     = EmptyEnvironment::new();
+        let kinds   = ErrorKindsTool::new().invoke_json(Value::Null, &env).unwrap();
+        let kinds   = kinds.as_array().unwrap();
+
+        assert!(kinds[0] == json!["ExpressionNotImplemented"]);
+        assert!(kinds.contains(&json!["ToolNameNotFound"]));
+        assert!(kinds.contains(&json!["UsingMustEvaluateToAMapOfToolNames"]));
+        assert!(kinds.contains(&json!["DeadlineExceeded"]));
+        assert!(kinds.contains(&json!["BreakOutsideLoop"]));
+        assert!(kinds.contains(&json!["ResultIsNotAFiniteNumber"]));
+    }
+
+    #[test]
+    fn each_kind_is_listed_once() {
+        let env     = EmptyEnvironment::new();
+        let kinds   = ErrorKindsTool::new().invoke_json(Value::Null, &env).unwrap();
+        let kinds   = kinds.as_array().unwrap();
+
+        for (index, kind) in kinds.iter().enumerate() {
+            assert!(!kinds[(index+1)..].contains(kind));
+        }
+    }
+}
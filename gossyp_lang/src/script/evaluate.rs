@@ -1,5 +1,7 @@
 use std::result::Result;
+use std::error::Error;
 
+use serde::*;
 use serde_json::*;
 use gossyp_base::Environment;
 
@@ -30,6 +32,55 @@ pub fn gossyp_eval(script: &str, environment: &Environment) -> Result<Value, Val
     evaluate_statement(&bound, environment, &mut execution_environment)
 }
 
+///
+/// Evaluates a gossyp script and decodes the value of its final statement into a Rust type
+///
+/// A script with no statements has a null value.
+///
+pub fn eval_as<TOut>(script: &str, environment: &Environment) -> Result<TOut, Value>
+where for<'de> TOut: Deserialize<'de> {
+    // The result of a script is an array with the result of each statement
+    let final_value = match gossyp_eval(script, environment)? {
+        Value::Array(mut results)   => results.pop().unwrap_or(Value::Null),
+        other                       => other
+    };
+
+    from_value::<TOut>(final_value).map_err(|erm| json![{
+        "error":        "Result decode failed",
+        "description":  erm.description()
+    }])
+}
+
+///
+/// Evaluates a set of statements starting from an existing execution state
+///
+/// The state after evaluation is returned along with the result, so it can be passed in to
+/// evaluate further statements that use the same variables.
+///
+pub fn gossyp_eval_with_state(script: Vec<Script>, execution_environment: ScriptExecutionEnvironment, environment: &Environment) -> (Result<Value, Value>, ScriptExecutionEnvironment) {
+    let mut execution_environment   = execution_environment;
+
+    // Bind against the variables already in the state and the tools in the environment
+    let mut variables               = BindingEnvironment::with_variables(execution_environment.get_variable_names().clone(), execution_environment.get_number_of_variables());
+    let bound                       = {
+        let tools       = BindingEnvironment::from_environment(environment);
+        let mut binding = BindingEnvironment::combine(&mut *variables, &*tools);
+
+        bind_statement(&Script::Sequence(script), &mut *binding)
+    };
+
+    let bound = match bound {
+        Ok(bound)   => bound,
+        Err(erm)    => return (Err(erm), execution_environment)
+    };
+
+    // Execute it, remembering any new variable names
+    execution_environment.set_variable_names(variables.get_variable_names());
+    let result = evaluate_statement(&bound, environment, &mut execution_environment);
+
+    (result, execution_environment)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -42,4 +93,438 @@ mod test {
 
         assert!(gossyp_eval("add_one 1", &env) == Ok(json![vec![2]]));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn numeric_map_key_reports_position() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        let result = gossyp_eval("id { \"a\": 1, 42: 2 }", &env);
+
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert!(error["error"] == json!["MapKeysMustEvaluateToAString"]);
+        assert!(error["position"] == json![{ "start": 13, "end": 15 }]);
+    }
+
+    #[test]
+    fn computed_map_key_reports_position() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+        assert!(define_pure_tool(&env, "num", |_: Value| 42).is_ok());
+
+        let result = gossyp_eval("id { num(): 2 }", &env);
+
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert!(error["error"] == json!["MapKeysMustEvaluateToAString"]);
+        assert!(error["position"]["start"] == json![5]);
+    }
+
+    #[test]
+    fn can_evaluate_binary_and_octal_literals() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("id { \"b\": 0b1010, \"o\": 0o17, \"x\": 0xff }", &env) == Ok(json![[{ "b": 10, "o": 15, "x": 255 }]]));
+    }
+
+    #[test]
+    fn can_evaluate_arithmetic() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("id 1 + 2 * 3", &env) == Ok(json![[ 7 ]]));
+        assert!(gossyp_eval("id (1 + 2) * 3", &env) == Ok(json![[ 9 ]]));
+        assert!(gossyp_eval("id 10 - 4 - 3", &env) == Ok(json![[ 3 ]]));
+        assert!(gossyp_eval("id 7 % 3", &env) == Ok(json![[ 1 ]]));
+        assert!(gossyp_eval("var x = 41\nx + 1", &env) == Ok(json![[ 41, 42 ]]));
+    }
+
+    #[test]
+    fn arithmetic_preserves_integers() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("id 6 / 2", &env) == Ok(json![[ 3 ]]));
+        assert!(gossyp_eval("id 7 / 2", &env) == Ok(json![[ 3.5 ]]));
+        assert!(gossyp_eval("id 1.5 + 1", &env) == Ok(json![[ 2.5 ]]));
+        assert!(gossyp_eval("id 2.0 * 2", &env).unwrap()[0].is_f64());
+    }
+
+    #[test]
+    fn can_evaluate_comparisons() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("id 1 + 1 == 2", &env) == Ok(json![[ true ]]));
+        assert!(gossyp_eval("id 1 != 1", &env) == Ok(json![[ false ]]));
+        assert!(gossyp_eval("id 1 < 2", &env) == Ok(json![[ true ]]));
+        assert!(gossyp_eval("id 2 <= 2", &env) == Ok(json![[ true ]]));
+        assert!(gossyp_eval("id 1.5 > 2", &env) == Ok(json![[ false ]]));
+        assert!(gossyp_eval("id \"b\" >= \"a\"", &env) == Ok(json![[ true ]]));
+    }
+
+    #[test]
+    fn values_of_different_types_are_not_equal() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("id 1 == \"1\"", &env) == Ok(json![[ false ]]));
+        assert!(gossyp_eval("id 1 != \"1\"", &env) == Ok(json![[ true ]]));
+    }
+
+    #[test]
+    fn can_compare_arrays_and_objects() {
+        let env = DynamicEnvironment::new();
+
+        assert!(gossyp_eval("var a = [ 1, 2 ] == [ 1, 2 ]", &env) == Ok(json![[ true ]]));
+        assert!(gossyp_eval("var a = [ 1, 2 ] < [ 1, 3 ]", &env) == Ok(json![[ true ]]));
+        assert!(gossyp_eval("var a = { \"x\": [ 1 ] } == { \"x\": [ 1 ] }", &env) == Ok(json![[ true ]]));
+        assert!(gossyp_eval("var a = { \"x\": 1 } != { \"x\": 2 }", &env) == Ok(json![[ true ]]));
+    }
+
+    #[test]
+    fn while_loop_can_use_comparison() {
+        let env = DynamicEnvironment::new();
+
+        assert!(gossyp_eval("var x = 0\nwhile x < 3 {\nx = x + 1\n}\nx", &env) == Ok(json![[ 0, 3, 3 ]]));
+    }
+
+    #[test]
+    fn can_evaluate_boolean_operators() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("id 1 < 2 and 2 < 3", &env) == Ok(json![[ true ]]));
+        assert!(gossyp_eval("id 1 > 2 or 2 > 3", &env) == Ok(json![[ false ]]));
+        assert!(gossyp_eval("id not 1 > 2", &env) == Ok(json![[ true ]]));
+        assert!(gossyp_eval("id not 1 > 2 and 0", &env) == Ok(json![[ false ]]));
+    }
+
+    #[test]
+    fn falsy_values_are_false() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "nothing", |_: Value| Value::Null).is_ok());
+
+        assert!(gossyp_eval("var a = [ not nothing, not 0, not \"\", not [], not { }, not \"a\", not [ 0 ] ]", &env) == Ok(json![[ [ true, true, true, true, false, false, false ] ]]));
+    }
+
+    #[test]
+    fn can_evaluate_negative_numbers() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("let x = -5\nid x", &env) == Ok(json![[ -5, -5 ]]));
+        assert!(gossyp_eval("var a = [ -1.5, -0x10, 2 * -3 ]", &env) == Ok(json![[ [ -1.5, -16, -6 ] ]]));
+    }
+
+    #[test]
+    fn negative_index_counts_from_end() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("var a = [ 1, 2, 3 ]\nid a[-1]", &env) == Ok(json![[ [ 1, 2, 3 ], 3 ]]));
+        assert!(gossyp_eval("var a = [ 1, 2, 3 ]\nid a[-10]", &env).unwrap_err()["error"] == json![ "IndexOutOfBounds" ]);
+    }
+
+    #[test]
+    fn can_slice_arrays_and_strings() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("var a = [ 1, 2, 3, 4 ]\nid a[1:3]", &env) == Ok(json![[ [ 1, 2, 3, 4 ], [ 2, 3 ] ]]));
+        assert!(gossyp_eval("var s = \"Hello\"\nid s[:-1]", &env) == Ok(json![[ "Hello", "Hell" ]]));
+    }
+
+    #[test]
+    fn negative_number_after_operand_is_subtraction() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("id 3 -1", &env) == Ok(json![[ 2 ]]));
+        assert!(gossyp_eval("id (3)-1", &env) == Ok(json![[ 2 ]]));
+    }
+
+    #[test]
+    fn can_evaluate_constants() {
+        let env = DynamicEnvironment::new();
+
+        assert!(gossyp_eval("var a = [ true, false, null ]", &env) == Ok(json![[ [ true, false, null ] ]]));
+        assert!(gossyp_eval("var b = not false and true", &env) == Ok(json![[ true ]]));
+    }
+
+    #[test]
+    fn constant_keywords_do_not_hide_identifiers() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "truthy", |x: Value| json![[ "truthy", x ]]).is_ok());
+
+        assert!(gossyp_eval("truthy null", &env) == Ok(json![[ [ "truthy", null ] ]]));
+    }
+
+    #[test]
+    fn boolean_operators_short_circuit() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        // Dividing by zero would produce an error if it were evaluated
+        assert!(gossyp_eval("id 0 and 1 / 0", &env) == Ok(json![[ false ]]));
+        assert!(gossyp_eval("id 1 or 1 / 0", &env) == Ok(json![[ true ]]));
+        assert!(gossyp_eval("id 1 and 1 / 0", &env).unwrap_err()["error"] == json!["DivisionByZero"]);
+    }
+
+    #[test]
+    fn adding_strings_concatenates_them() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("id \"foo\" + \"bar\"", &env) == Ok(json![[ "foobar" ]]));
+    }
+
+    #[test]
+    fn arithmetic_errors() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("id 1 / 0", &env).unwrap_err()["error"] == json!["DivisionByZero"]);
+        assert!(gossyp_eval("id 1 % 0", &env).unwrap_err()["error"] == json!["DivisionByZero"]);
+        assert!(gossyp_eval("id \"foo\" + 1", &env).unwrap_err()["error"] == json!["OperandTypeMismatch"]);
+        assert!(gossyp_eval("id \"foo\" * \"bar\"", &env).unwrap_err()["error"] == json!["OperandTypeMismatch"]);
+    }
+
+    #[test]
+    fn can_evaluate_chained_field_access() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("var x = { \"a\": { \"b\": { \"c\": 42 } } }\nid x.a.b.c", &env) == Ok(json![[ { "a": { "b": { "c": 42 } } }, 42 ]]));
+    }
+
+    #[test]
+    fn using_makes_tools_available_in_block() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "hidden-double", |x: i32| x*2).is_ok());
+        assert!(define_pure_tool(&env, "provider", |_: ()| json![{ "double": "hidden-double" }]).is_ok());
+
+        assert!(gossyp_eval("using provider {\ndouble 2\n}", &env) == Ok(json![[ 4 ]]));
+        assert!(gossyp_eval("double 2", &env).is_err());
+    }
+
+    #[test]
+    fn using_replaces_tools_defined_outside_block() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "double", |x: i32| x*2).is_ok());
+        assert!(define_pure_tool(&env, "triple", |x: i32| x*3).is_ok());
+        assert!(define_pure_tool(&env, "provider", |_: ()| json![{ "double": "triple" }]).is_ok());
+
+        assert!(gossyp_eval("using provider {\ndouble 2\n}\ndouble 2", &env) == Ok(json![[ 6, 4 ]]));
+    }
+
+    #[test]
+    fn using_requires_map_of_tool_names() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "not-a-provider", |_: ()| 42).is_ok());
+        assert!(define_pure_tool(&env, "missing-provider", |_: ()| json![{ "double": "missing" }]).is_ok());
+
+        assert!(gossyp_eval("using not-a-provider {\nnot-a-provider\n}", &env).unwrap_err()["error"] == json!["UsingMustEvaluateToAMapOfToolNames"]);
+        assert!(gossyp_eval("using missing-provider {\nnot-a-provider\n}", &env).unwrap_err()["error"] == json!["ToolNameNotFound"]);
+    }
+
+    #[test]
+    fn state_carries_over_between_evaluations() {
+        let env             = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "add_one", |x: i32| x+1).is_ok());
+
+        let first           = ParseScriptTool::parse(&create_lex_script_tool().lex("var x = 41")).unwrap();
+        let second          = ParseScriptTool::parse(&create_lex_script_tool().lex("var y = x\nadd_one y")).unwrap();
+
+        let (result, state) = gossyp_eval_with_state(first, ScriptExecutionEnvironment::new(), &env);
+        assert!(result == Ok(json![[ 41 ]]));
+
+        let (result, state) = gossyp_eval_with_state(second, state, &env);
+        assert!(result == Ok(json![[ 41, 42 ]]));
+        assert!(state.get_variable_names().len() == 2);
+    }
+
+
+    #[test]
+    fn bare_map_keys_are_literal_strings() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("id { foo: 1, \"bar\": 2 }", &env) == Ok(json![[{ "foo": 1, "bar": 2 }]]));
+    }
+
+    #[test]
+    fn parenthesized_map_keys_are_evaluated() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("var key = \"bar\"\nid { (key): 1 }", &env) == Ok(json![[ "bar", { "bar": 1 } ]]));
+    }
+
+    #[test]
+    fn while_runs_until_counter_is_zero() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "dec", |x: i32| x-1).is_ok());
+        assert!(define_pure_tool(&env, "inc", |x: i32| x+1).is_ok());
+
+        let result = gossyp_eval("var i = 3\nvar count = 0\nwhile i {\ni = dec(i)\ncount = inc(count)\n}\ncount", &env);
+
+        assert!(result == Ok(json![[ 3, 0, [ 0, 3 ], 3 ]]));
+    }
+
+    #[test]
+    fn while_that_never_runs_is_null() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "inc", |x: i32| x+1).is_ok());
+
+        let result = gossyp_eval("var done = 0\nwhile done {\ninc 1\n}", &env);
+
+        assert!(result == Ok(json![[ 0, null ]]));
+    }
+
+    #[test]
+    fn for_runs_block_for_each_item() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "double", |x: i32| x*2).is_ok());
+
+        assert!(gossyp_eval("for x in [ 1, 2, 3 ] {\ndouble x\n}", &env) == Ok(json![[ [ 2, 4, 6 ] ]]));
+    }
+
+    #[test]
+    fn for_over_empty_array_is_empty() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "double", |x: i32| x*2).is_ok());
+
+        assert!(gossyp_eval("for x in [] {\ndouble x\n}", &env) == Ok(json![[ [] ]]));
+    }
+
+    #[test]
+    fn for_over_non_array_is_an_error() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "double", |x: i32| x*2).is_ok());
+
+        assert!(gossyp_eval("for x in 42 {\ndouble x\n}", &env).unwrap_err()["error"] == json!["ForMustIterateOverAnArray"]);
+    }
+
+    #[test]
+    fn for_variable_is_not_visible_after_loop() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "double", |x: i32| x*2).is_ok());
+
+        assert!(gossyp_eval("for x in [ 1 ] {\ndouble x\n}\ndouble x", &env).is_err());
+    }
+
+    #[test]
+    fn break_returns_value_from_loop() {
+        let env = DynamicEnvironment::new();
+
+        assert!(gossyp_eval("loop { break 42 }", &env) == Ok(json![[ 42 ]]));
+    }
+
+    #[test]
+    fn break_without_value_is_null() {
+        let env = DynamicEnvironment::new();
+
+        assert!(gossyp_eval("loop { break }", &env) == Ok(json![[ null ]]));
+    }
+
+    #[test]
+    fn break_ends_innermost_loop() {
+        let env = DynamicEnvironment::new();
+
+        assert!(gossyp_eval("loop {\nloop { break 1 }\nbreak 2\n}", &env) == Ok(json![[ 2 ]]));
+    }
+
+    #[test]
+    fn tool_error_that_looks_like_a_break_does_not_end_loop() {
+        let env = DynamicEnvironment::new();
+        env.define("fake_break", Box::new(make_tool(|_: ()| -> Result<(), Value> { Err(json![{ "error": "BreakOutsideLoop", "value": 42 }]) })));
+
+        assert!(gossyp_eval("loop {\nfake_break\n}", &env) == Err(json![{ "error": "BreakOutsideLoop", "value": 42 }]));
+    }
+
+    #[test]
+    fn break_outside_loop_is_an_error() {
+        let env = DynamicEnvironment::new();
+
+        assert!(gossyp_eval("break 1", &env).unwrap_err()["error"] == json!["BreakOutsideLoop"]);
+    }
+
+    #[test]
+    fn break_in_def_does_not_end_callers_loop() {
+        let env = DynamicEnvironment::new();
+
+        let result = gossyp_eval("def stop(x) {\nbreak x\n}\nloop {\nstop 7\n}", &env);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err()["error"] == json!["BreakOutsideLoop"]);
+    }
+
+    #[test]
+    fn def_defines_tool_that_can_be_called_later() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "add", |(a, b): (i32, i32)| a+b).is_ok());
+
+        let result = gossyp_eval("def add_three(a, b, c) {\nvar ab = add(a, b)\nadd(ab, c)\n}\nadd_three (1, 2, 3)", &env);
+
+        assert!(result == Ok(json![[ null, 6 ]]));
+        assert!(env.get_json_tool("add_three").unwrap().invoke_json(json![[ 4, 5, 6 ]], &env) == Ok(json![ 15 ]));
+    }
+
+    #[test]
+    fn def_with_one_argument_receives_whole_input() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("def whole(x) {\nid x\n}\nwhole(1, 2)", &env) == Ok(json![[ null, [ 1, 2 ] ]]));
+    }
+
+    #[test]
+    fn unknown_names_after_def_are_reported_when_binding() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("def one(x) {\n1\n}\nid misspelled", &env).unwrap_err()["error"] == json!["ExpressionDoesNotEvaluateToTool"]);
+        assert!(gossyp_eval("def one(x) {\n1\n}\nundeclared = 1", &env).unwrap_err()["error"] == json!["VariableNameNotFound"]);
+        assert!(env.get_json_tool("one").is_err());
+    }
+
+    #[test]
+    fn def_replaces_existing_tool() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "add", |(a, b): (i32, i32)| a+b).is_ok());
+        assert!(define_pure_tool(&env, "double", |x: i32| x*2).is_ok());
+
+        let result = gossyp_eval("def double(x) {\nadd (x, x)\n}\ndef double(x) {\nadd (x, 1)\n}\ndouble 2", &env);
+
+        assert!(result == Ok(json![[ null, null, 3 ]]));
+    }
+
+    #[test]
+    fn def_body_cannot_see_script_variables() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "id", |x: Value| x).is_ok());
+
+        assert!(gossyp_eval("var y = 1\ndef get_y(x) {\nid y\n}\nget_y 2", &env).is_err());
+    }
+
+    #[test]
+    fn can_evaluate_as_rust_type() {
+        let env = DynamicEnvironment::new();
+
+        assert!(eval_as::<i64>("var x = 1\nx", &env) == Ok(1));
+        assert!(eval_as::<String>("var x = 1\nvar y = \"final\"", &env) == Ok(String::from("final")));
+        assert!(eval_as::<Vec<i64>>("var x = [ 1, 2 ]", &env) == Ok(vec![ 1, 2 ]));
+        assert!(eval_as::<()>("", &env) == Ok(()));
+    }
+
+    #[test]
+    fn eval_as_reports_decode_errors() {
+        let env     = DynamicEnvironment::new();
+        let result  = eval_as::<i64>("var x = \"one\"", &env);
+
+        assert!(result.unwrap_err()["error"] == json![ "Result decode failed" ]);
+    }
+}
@@ -1,12 +1,14 @@
+use std::f64;
+use std::cmp;
 use std::result::Result;
 
 use serde_json::*;
 
 use gossyp_base::*;
 use gossyp_base::basic::*;
+use gossyp_toolkit::algorithm::CompareTool;
 use super::script::*;
 use super::bound_script::*;
-use super::binding_environment::*;
 use super::bind_expression::*;
 use super::script_interpreter::*;
 
@@ -23,8 +25,11 @@ pub enum FailedBoundExpression {
     Variable(ScriptToken),
     Field(ScriptToken),
     Index(Box<(FailedBoundExpression, FailedBoundExpression)>),
+    Slice(Box<(FailedBoundExpression, Option<FailedBoundExpression>, Option<FailedBoundExpression>)>),
     FieldAccess(Box<(FailedBoundExpression, FailedBoundExpression)>),
-    Apply(Box<(FailedBoundExpression, FailedBoundExpression)>)
+    Apply(Box<(FailedBoundExpression, FailedBoundExpression)>),
+    BinaryOp(ScriptToken, Box<(FailedBoundExpression, FailedBoundExpression)>),
+    UnaryOp(ScriptToken, Box<FailedBoundExpression>)
 }
 
 ///
@@ -47,6 +52,11 @@ pub fn generate_failed_bound_expression(expr: &BoundExpression) -> FailedBoundEx
             Index(Box::new((generate_failed_bound_expression(lhs), generate_failed_bound_expression(rhs))))
         },
 
+        &BoundExpression::Slice(ref boxed)          => {
+            let (ref lhs, ref start, ref end) = **boxed;
+            Slice(Box::new((generate_failed_bound_expression(lhs), start.as_ref().map(generate_failed_bound_expression), end.as_ref().map(generate_failed_bound_expression))))
+        },
+
         &BoundExpression::FieldAccess(ref boxed)    => {
             let (ref lhs, ref rhs) = **boxed;
             FieldAccess(Box::new((generate_failed_bound_expression(lhs), generate_failed_bound_expression(rhs))))
@@ -55,7 +65,14 @@ pub fn generate_failed_bound_expression(expr: &BoundExpression) -> FailedBoundEx
         &BoundExpression::Apply(ref boxed)          => {
             let (ref lhs, ref rhs) = **boxed;
             Apply(Box::new((generate_failed_bound_expression(lhs), generate_failed_bound_expression(rhs))))
-        }
+        },
+
+        &BoundExpression::BinaryOp(ref op, ref boxed) => {
+            let (ref lhs, ref rhs) = **boxed;
+            BinaryOp(op.clone(), Box::new((generate_failed_bound_expression(lhs), generate_failed_bound_expression(rhs))))
+        },
+
+        &BoundExpression::UnaryOp(ref op, ref operand) => UnaryOp(op.clone(), Box::new(generate_failed_bound_expression(operand)))
     }
 }
 
@@ -69,6 +86,22 @@ fn generate_bound_expression_error(error: ScriptEvaluationError, expr: &BoundExp
     }]
 }
 
+///
+/// Creates the error generated when a map key is not a string, including where the key is in the source
+///
+pub fn generate_map_key_error(key_expr: &BoundExpression) -> Value {
+    let mut error = generate_bound_expression_error(ScriptEvaluationError::MapKeysMustEvaluateToAString, key_expr);
+
+    if let Some((start, end)) = key_expr.span() {
+        error["position"] = json![{
+            "start":    start,
+            "end":      end
+        }];
+    }
+
+    error
+}
+
 ///
 /// Attempts to evaluate an expression to a tool
 ///
@@ -125,7 +158,7 @@ pub fn evaluate_map(exprs: &Vec<(BoundExpression, BoundExpression)>, environment
     for &(ref key_expr, ref value_expr) in exprs.iter() {
         let key = match evaluate_expression(key_expr, environment, execution_environment) {
             Ok(Value::String(key))  => key,
-            Ok(_)                   => return Err(generate_bound_expression_error(ScriptEvaluationError::MapKeysMustEvaluateToAString, key_expr)),
+            Ok(_)                   => return Err(generate_map_key_error(key_expr)),
             Err(erm)                => return Err(erm)
         };
 
@@ -137,6 +170,23 @@ pub fn evaluate_map(exprs: &Vec<(BoundExpression, BoundExpression)>, environment
     Ok(Value::Object(result))
 }
 
+///
+/// Converts an index into a position in an array or string of the specified length
+///
+/// Negative indexes count back from the end, so -1 is the last item. Returns None if the index
+/// is before the start of the sequence (positive indexes past the end are left to the caller).
+///
+fn sequence_position(index: &Number, len: usize) -> Option<usize> {
+    if let Some(index) = index.as_u64() {
+        Some(index as usize)
+    } else {
+        index.as_i64()
+            .and_then(|index| index.checked_neg())
+            .map(|from_end| from_end as u64)
+            .and_then(|from_end| if from_end <= len as u64 { Some(len - from_end as usize) } else { None })
+    }
+}
+
 ///
 /// Evaluates an index expression
 ///
@@ -150,8 +200,8 @@ pub fn evaluate_index(lhs: &BoundExpression, rhs: &BoundExpression, environment:
                     // Array[n] indexing: n must be a number
                     match rhs_res {
                         Value::Number(index) => {
-                            index.as_u64()
-                                .and_then(|index|       array.get(index as usize))
+                            sequence_position(&index, array.len())
+                                .and_then(|index|       array.get(index))
                                 .map(|indexed_value|    indexed_value.clone())
                                 .ok_or_else(||          generate_bound_expression_error(ScriptEvaluationError::IndexOutOfBounds, rhs))
                         },
@@ -164,8 +214,8 @@ pub fn evaluate_index(lhs: &BoundExpression, rhs: &BoundExpression, environment:
                     // String[n] indexing: n must be a number
                     match rhs_res {
                         Value::Number(index) => {
-                            index.as_u64()
-                                .and_then(|index|       string.chars().nth(index as usize))
+                            sequence_position(&index, string.chars().count())
+                                .and_then(|index|       string.chars().nth(index))
                                 .map(|indexed_value|    Value::String(indexed_value.to_string()))
                                 .ok_or_else(||          generate_bound_expression_error(ScriptEvaluationError::IndexOutOfBounds, rhs))
                         },
@@ -192,28 +242,243 @@ pub fn evaluate_index(lhs: &BoundExpression, rhs: &BoundExpression, environment:
         })
 }
 
+///
+/// Evaluates one of the bounds of a slice expression, clamping it to the length of the collection
+///
+/// Negative bounds count back from the end, as they do for indexes. A bound that's left out is
+/// the start or end of the collection, as specified by `default`.
+///
+fn evaluate_slice_bound(bound: &Option<BoundExpression>, default: usize, len: usize, environment: &Environment, execution_environment: &ScriptExecutionEnvironment) -> Result<usize, Value> {
+    let bound = match bound {
+        &Some(ref bound)    => bound,
+        &None               => return Ok(default)
+    };
+
+    let value = evaluate_expression(bound, environment, execution_environment)?;
+
+    let position = match value {
+        Value::Number(ref index) => match (index.as_u64(), index.as_i64()) {
+            (Some(index), _)    => Some(cmp::min(index, len as u64) as usize),
+            (None, Some(index)) => {
+                // Negative (wrapping_neg produces the right magnitude even for i64::MIN)
+                let from_end = index.wrapping_neg() as u64;
+                Some(len - cmp::min(from_end, len as u64) as usize)
+            },
+            _                   => None
+        },
+
+        _ => None
+    };
+
+    position.ok_or_else(|| generate_bound_expression_error(ScriptEvaluationError::ArrayIndexMustBeANumber, bound))
+}
+
+///
+/// Evaluates a slice expression
+///
+/// The result contains the items from the start index up to (but not including) the end index.
+/// Indexes past either end of the array or string are clamped, so slicing never produces an
+/// out of bounds error.
+///
+pub fn evaluate_slice(lhs: &BoundExpression, start: &Option<BoundExpression>, end: &Option<BoundExpression>, environment: &Environment, execution_environment: &ScriptExecutionEnvironment) -> Result<Value, Value> {
+    match evaluate_expression(lhs, environment, execution_environment)? {
+        Value::Array(array) => {
+            let start   = evaluate_slice_bound(start, 0, array.len(), environment, execution_environment)?;
+            let end     = evaluate_slice_bound(end, array.len(), array.len(), environment, execution_environment)?;
+
+            Ok(Value::Array(if start < end { array[start..end].to_vec() } else { vec![] }))
+        },
+
+        Value::String(string) => {
+            let len     = string.chars().count();
+            let start   = evaluate_slice_bound(start, 0, len, environment, execution_environment)?;
+            let end     = evaluate_slice_bound(end, len, len, environment, execution_environment)?;
+
+            Ok(Value::String(string.chars().skip(start).take(end.saturating_sub(start)).collect()))
+        },
+
+        _ => Err(generate_bound_expression_error(ScriptEvaluationError::IndexMustApplyToAnArrayOrAMap, lhs))
+    }
+}
+
+///
+/// Evaluates a field access expression
+///
+/// Chained field accesses like `a.b.c` are bound as `(a.b).c`, so the left-hand side is evaluated first
+///
+pub fn evaluate_field_access(lhs: &BoundExpression, rhs: &BoundExpression, environment: &Environment, execution_environment: &ScriptExecutionEnvironment) -> Result<Value, Value> {
+    let lhs_res = evaluate_expression(lhs, environment, execution_environment)?;
+
+    let field_name = match rhs {
+        &BoundExpression::Field(ref field_name, ref _token) => field_name,
+        _                                                   => return Err(generate_bound_expression_error(ScriptEvaluationError::FieldMustBeIdentifier, rhs))
+    };
+
+    match lhs_res {
+        Value::Object(map) => {
+            map.get(field_name)
+                .map(|ref_value|    ref_value.clone())
+                .ok_or_else(||      generate_bound_expression_error(ScriptEvaluationError::ObjectValueNotPresent, rhs))
+        },
+
+        _ => Err(generate_bound_expression_error(ScriptEvaluationError::FieldAccessMustApplyToAnObject, lhs))
+    }
+}
+
+///
+/// Applies an arithmetic operator to two integers, returning None if the result isn't a whole number or is out of range
+///
+fn integer_arithmetic(operator: &str, lhs: i64, rhs: i64) -> Option<i64> {
+    match operator {
+        "+" => lhs.checked_add(rhs),
+        "-" => lhs.checked_sub(rhs),
+        "*" => lhs.checked_mul(rhs),
+        "/" => if lhs.checked_rem(rhs) == Some(0) { lhs.checked_div(rhs) } else { None },
+        "%" => lhs.checked_rem(rhs),
+        _   => None
+    }
+}
+
+///
+/// Applies an arithmetic operator to two floating point numbers
+///
+fn float_arithmetic(operator: &str, lhs: f64, rhs: f64) -> f64 {
+    match operator {
+        "+" => lhs + rhs,
+        "-" => lhs - rhs,
+        "*" => lhs * rhs,
+        "/" => lhs / rhs,
+        "%" => lhs % rhs,
+        _   => f64::NAN
+    }
+}
+
+///
+/// Evaluates an 'and' or an 'or' expression
+///
+/// The right-hand side is only evaluated if the left-hand side doesn't determine the result.
+/// The result is always a boolean.
+///
+pub fn evaluate_logical_op(operator: &ScriptToken, lhs: &BoundExpression, rhs: &BoundExpression, environment: &Environment, execution_environment: &ScriptExecutionEnvironment) -> Result<Value, Value> {
+    let lhs_res = is_truthy(&evaluate_expression(lhs, environment, execution_environment)?);
+
+    match (&operator.token, lhs_res) {
+        (&ScriptLexerToken::And, false) => Ok(Value::Bool(false)),
+        (&ScriptLexerToken::Or, true)   => Ok(Value::Bool(true)),
+        _                               => Ok(Value::Bool(is_truthy(&evaluate_expression(rhs, environment, execution_environment)?)))
+    }
+}
+
+///
+/// Applies a comparison operator to two values, returning None if the operator is not a comparison
+///
+/// Values are ordered in the same way as the compare-values tool, so values of different types
+/// are never equal.
+///
+fn comparison(operator: &str, lhs: &Value, rhs: &Value) -> Option<bool> {
+    match operator {
+        "==" => Some(CompareTool::compare_values(lhs, rhs) == 0),
+        "!=" => Some(CompareTool::compare_values(lhs, rhs) != 0),
+        "<"  => Some(CompareTool::compare_values(lhs, rhs) < 0),
+        "<=" => Some(CompareTool::compare_values(lhs, rhs) <= 0),
+        ">"  => Some(CompareTool::compare_values(lhs, rhs) > 0),
+        ">=" => Some(CompareTool::compare_values(lhs, rhs) >= 0),
+        _    => None
+    }
+}
+
+///
+/// Evaluates a binary operator expression
+///
+/// Comparison operators produce a boolean. For the arithmetic operators, if both operands are
+/// integers, the result is an integer unless it's a fraction or is too large, in which case it's
+/// a floating point number. If either operand is a floating point number, so is the result. Adding
+/// two strings concatenates them.
+///
+pub fn evaluate_binary_op(operator: &ScriptToken, lhs: &BoundExpression, rhs: &BoundExpression, environment: &Environment, execution_environment: &ScriptExecutionEnvironment) -> Result<Value, Value> {
+    let lhs_res     = evaluate_expression(lhs, environment, execution_environment)?;
+    let rhs_res     = evaluate_expression(rhs, environment, execution_environment)?;
+    let operator    = operator.matched.as_str();
+
+    if let Some(result) = comparison(operator, &lhs_res, &rhs_res) {
+        return Ok(Value::Bool(result));
+    }
+
+    match (lhs_res, rhs_res) {
+        (Value::String(lhs_string), Value::String(rhs_string)) if operator == "+" => Ok(Value::String(lhs_string + &rhs_string)),
+
+        (Value::Number(lhs_number), Value::Number(rhs_number)) => {
+            let lhs_float = lhs_number.as_f64().unwrap_or(f64::NAN);
+            let rhs_float = rhs_number.as_f64().unwrap_or(f64::NAN);
+
+            if (operator == "/" || operator == "%") && rhs_float == 0.0 {
+                return Err(generate_bound_expression_error(ScriptEvaluationError::DivisionByZero, rhs));
+            }
+
+            let integer_result = match (lhs_number.as_i64(), rhs_number.as_i64()) {
+                (Some(lhs_int), Some(rhs_int))  => integer_arithmetic(operator, lhs_int, rhs_int),
+                _                               => None
+            };
+
+            match integer_result {
+                Some(result)    => Ok(json![ result ]),
+                None            => Number::from_f64(float_arithmetic(operator, lhs_float, rhs_float))
+                    .map(|result| Value::Number(result))
+                    .ok_or_else(|| generate_bound_expression_error(ScriptEvaluationError::ResultIsNotAFiniteNumber, lhs))
+            }
+        },
+
+        _ => Err(generate_bound_expression_error(ScriptEvaluationError::OperandTypeMismatch, lhs))
+    }
+}
+
 ///
 /// Evaluates a single expression
 ///
+/// Bound expressions are not modified by evaluation, so an expression bound once (for example by
+/// `bind_standalone_expression`) can be evaluated repeatedly with different execution environments.
+///
 pub fn evaluate_expression(expression: &BoundExpression, environment: &Environment, execution_environment: &ScriptExecutionEnvironment) -> Result<Value, Value> {
     match expression {
         &BoundExpression::Value(ref value, ref _token)          => Ok(value.clone()),
 
         &BoundExpression::Tool(ref tool, ref _token)            => call_tool(tool, Value::Null, environment),
         &BoundExpression::Variable(var_num, ref _token)         => Ok(execution_environment.get_variable(var_num).clone()),
-        &BoundExpression::Field(ref _field_name, ref _token)    => unimplemented!(),
+        &BoundExpression::Field(ref field_name, ref _token)     => Ok(Value::String(field_name.clone())),
         
         &BoundExpression::Array(ref values)                     => evaluate_array(values, environment, execution_environment),
         &BoundExpression::Tuple(ref values)                     => evaluate_array(values, environment, execution_environment),
         &BoundExpression::Map(ref values)                       => evaluate_map(values, environment, execution_environment),
 
-        &BoundExpression::FieldAccess(ref _accessor)            => unimplemented!(),
+        &BoundExpression::FieldAccess(ref accessor)             => {
+            let (ref lhs, ref rhs) = **accessor;
+            evaluate_field_access(lhs, rhs, environment, execution_environment)
+        },
         &BoundExpression::Apply(ref application)                => apply(&*application, environment, execution_environment),
 
         &BoundExpression::Index(ref index)                      => {
             let (ref lhs, ref rhs) = **index;
             evaluate_index(lhs, rhs, environment, execution_environment)
         },
+
+        &BoundExpression::Slice(ref slice)                      => {
+            let (ref lhs, ref start, ref end) = **slice;
+            evaluate_slice(lhs, start, end, environment, execution_environment)
+        },
+
+        &BoundExpression::BinaryOp(ref operator, ref operands)  => {
+            let (ref lhs, ref rhs) = **operands;
+
+            match operator.token {
+                ScriptLexerToken::And | ScriptLexerToken::Or    => evaluate_logical_op(operator, lhs, rhs, environment, execution_environment),
+                _                                               => evaluate_binary_op(operator, lhs, rhs, environment, execution_environment)
+            }
+        },
+
+        &BoundExpression::UnaryOp(ref _operator, ref operand)   => {
+            // 'not' is the only unary operator
+            Ok(Value::Bool(!is_truthy(&evaluate_expression(operand, environment, execution_environment)?)))
+        },
     }
 }
 
@@ -221,8 +486,7 @@ pub fn evaluate_expression(expression: &BoundExpression, environment: &Environme
 /// Evaluates a single expression
 ///
 pub fn evaluate_unbound_expression(expression: &Expression, environment: &Environment, execution_environment: &ScriptExecutionEnvironment) -> Result<Value, Value> {
-    let mut binding_environment = BindingEnvironment::from_environment(environment);
-    let bound                   = bind_expression(expression, &mut *binding_environment)?;
+    let bound = bind_standalone_expression(expression, environment)?;
 
     evaluate_expression(&bound, environment, execution_environment)
 }
@@ -323,6 +587,53 @@ mod test {
         assert!(result == Ok(json![ 2 ]));
     }
 
+    #[test]
+    fn can_slice_array() {
+        let array_expr          = Expression::Array(vec![Expression::number("1"), Expression::number("2"), Expression::number("3"), Expression::number("4")]);
+        let empty_environment   = EmptyEnvironment::new();
+        let mut env             = ScriptExecutionEnvironment::new();
+        let slice               = |start: Option<&str>, end: Option<&str>| Expression::Slice(Box::new((array_expr.clone(), start.map(Expression::number), end.map(Expression::number))));
+
+        assert!(evaluate_unbound_expression(&slice(Some("1"), Some("3")), &empty_environment, &mut env) == Ok(json![ [ 2, 3 ] ]));
+        assert!(evaluate_unbound_expression(&slice(None, Some("2")), &empty_environment, &mut env) == Ok(json![ [ 1, 2 ] ]));
+        assert!(evaluate_unbound_expression(&slice(Some("2"), None), &empty_environment, &mut env) == Ok(json![ [ 3, 4 ] ]));
+        assert!(evaluate_unbound_expression(&slice(None, None), &empty_environment, &mut env) == Ok(json![ [ 1, 2, 3, 4 ] ]));
+        assert!(evaluate_unbound_expression(&slice(Some("-3"), Some("-1")), &empty_environment, &mut env) == Ok(json![ [ 2, 3 ] ]));
+    }
+
+    #[test]
+    fn slice_is_clamped_to_bounds() {
+        let array_expr          = Expression::Array(vec![Expression::number("1"), Expression::number("2"), Expression::number("3")]);
+        let empty_environment   = EmptyEnvironment::new();
+        let mut env             = ScriptExecutionEnvironment::new();
+        let slice               = |start: &str, end: &str| Expression::Slice(Box::new((array_expr.clone(), Some(Expression::number(start)), Some(Expression::number(end)))));
+
+        assert!(evaluate_unbound_expression(&slice("-10", "100"), &empty_environment, &mut env) == Ok(json![ [ 1, 2, 3 ] ]));
+        assert!(evaluate_unbound_expression(&slice("5", "10"), &empty_environment, &mut env) == Ok(json![ [] ]));
+        assert!(evaluate_unbound_expression(&slice("2", "1"), &empty_environment, &mut env) == Ok(json![ [] ]));
+    }
+
+    #[test]
+    fn can_slice_string() {
+        let string_expr         = Expression::string("\"Abcd\"");
+        let slice_expr          = Expression::Slice(Box::new((string_expr, Some(Expression::number("1")), Some(Expression::number("3")))));
+        let empty_environment   = EmptyEnvironment::new();
+        let mut env             = ScriptExecutionEnvironment::new();
+
+        assert!(evaluate_unbound_expression(&slice_expr, &empty_environment, &mut env) == Ok(json![ "bc" ]));
+    }
+
+    #[test]
+    fn cannot_slice_number_or_map() {
+        let empty_environment   = EmptyEnvironment::new();
+        let mut env             = ScriptExecutionEnvironment::new();
+        let number_slice        = Expression::Slice(Box::new((Expression::number("42"), None, None)));
+        let map_slice           = Expression::Slice(Box::new((Expression::Map(vec![ (MapKey::Expression(Expression::string("\"a\"")), Expression::number("1")) ]), None, None)));
+
+        assert!(evaluate_unbound_expression(&number_slice, &empty_environment, &mut env).unwrap_err()["error"] == json![ "IndexMustApplyToAnArrayOrAMap" ]);
+        assert!(evaluate_unbound_expression(&map_slice, &empty_environment, &mut env).unwrap_err()["error"] == json![ "IndexMustApplyToAnArrayOrAMap" ]);
+    }
+
     #[test]
     fn can_lookup_string_index() {
         let string_expr         = Expression::string("\"Abcd\"");
@@ -346,14 +657,40 @@ mod test {
     }
 
     #[test]
-    fn negative_index_is_out_of_range() {
+    fn negative_index_counts_from_end() {
         let array_expr          = Expression::Array(vec![Expression::number("1"), Expression::number("2"), Expression::number("3")]);
-        let lookup_expr         = Expression::Index(Box::new((array_expr, Expression::number("-1"))));
+        let empty_environment   = EmptyEnvironment::new();
+        let mut env             = ScriptExecutionEnvironment::new();
+
+        let last                = Expression::Index(Box::new((array_expr.clone(), Expression::number("-1"))));
+        let first               = Expression::Index(Box::new((array_expr.clone(), Expression::number("-3"))));
+
+        assert!(evaluate_unbound_expression(&last, &empty_environment, &mut env) == Ok(json![ 3 ]));
+        assert!(evaluate_unbound_expression(&first, &empty_environment, &mut env) == Ok(json![ 1 ]));
+    }
+
+    #[test]
+    fn negative_string_index_counts_from_end() {
+        let string_expr         = Expression::string("\"Abcd\"");
+        let lookup_expr         = Expression::Index(Box::new((string_expr, Expression::number("-2"))));
         let empty_environment   = EmptyEnvironment::new();
         let mut env             = ScriptExecutionEnvironment::new();
         let result              = evaluate_unbound_expression(&lookup_expr, &empty_environment, &mut env);
 
-        assert!(result.is_err());
+        assert!(result == Ok(json![ "c" ]));
+    }
+
+    #[test]
+    fn negative_index_can_be_out_of_range() {
+        let array_expr          = Expression::Array(vec![Expression::number("1"), Expression::number("2"), Expression::number("3")]);
+        let empty_environment   = EmptyEnvironment::new();
+        let mut env             = ScriptExecutionEnvironment::new();
+
+        let too_far             = Expression::Index(Box::new((array_expr.clone(), Expression::number("-4"))));
+        let minimum             = Expression::Index(Box::new((array_expr.clone(), Expression::number("-9223372036854775808"))));
+
+        assert!(evaluate_unbound_expression(&too_far, &empty_environment, &mut env).unwrap_err()["error"] == json![ "IndexOutOfBounds" ]);
+        assert!(evaluate_unbound_expression(&minimum, &empty_environment, &mut env).unwrap_err()["error"] == json![ "IndexOutOfBounds" ]);
     }
 
     #[test]
@@ -379,7 +716,7 @@ mod test {
 
     #[test]
     fn can_evaluate_map() {
-        let map_expr            = Expression::Map(vec![ (Expression::string("\"Foo\""), Expression::number("1")), (Expression::string("\"Bar\""), Expression::number("2")) ]);
+        let map_expr            = Expression::Map(vec![ (MapKey::Expression(Expression::string("\"Foo\"")), Expression::number("1")), (MapKey::Expression(Expression::string("\"Bar\"")), Expression::number("2")) ]);
         let empty_environment   = EmptyEnvironment::new();
         let mut env             = ScriptExecutionEnvironment::new();
         let result              = evaluate_unbound_expression(&map_expr, &empty_environment, &mut env);
@@ -389,7 +726,7 @@ mod test {
 
     #[test]
     fn can_index_map() {
-        let map_expr            = Expression::Map(vec![ (Expression::string("\"Foo\""), Expression::number("1")), (Expression::string("\"Bar\""), Expression::number("2")) ]);
+        let map_expr            = Expression::Map(vec![ (MapKey::Expression(Expression::string("\"Foo\"")), Expression::number("1")), (MapKey::Expression(Expression::string("\"Bar\"")), Expression::number("2")) ]);
         let lookup_expr         = Expression::Index(Box::new((map_expr, Expression::string("\"Bar\""))));
         let empty_environment   = EmptyEnvironment::new();
         let mut env             = ScriptExecutionEnvironment::new();
@@ -400,7 +737,7 @@ mod test {
 
     #[test]
     fn can_evaluate_map_with_duplicate_keys() {
-        let map_expr            = Expression::Map(vec![ (Expression::string("\"Foo\""), Expression::number("1")), (Expression::string("\"Foo\""), Expression::number("2")) ]);
+        let map_expr            = Expression::Map(vec![ (MapKey::Expression(Expression::string("\"Foo\"")), Expression::number("1")), (MapKey::Expression(Expression::string("\"Foo\"")), Expression::number("2")) ]);
         let empty_environment   = EmptyEnvironment::new();
         let mut env             = ScriptExecutionEnvironment::new();
         let result              = evaluate_unbound_expression(&map_expr, &empty_environment, &mut env);
@@ -408,6 +745,39 @@ mod test {
         assert!(result == Ok(json![ { "Foo": 2 } ]));
     }
 
+    #[test]
+    fn can_access_map_field() {
+        let map_expr            = Expression::Map(vec![ (MapKey::Expression(Expression::string("\"Foo\"")), Expression::number("1")), (MapKey::Expression(Expression::string("\"Bar\"")), Expression::number("2")) ]);
+        let access_expr         = Expression::FieldAccess(Box::new((map_expr, Expression::identifier("Bar"))));
+        let empty_environment   = EmptyEnvironment::new();
+        let mut env             = ScriptExecutionEnvironment::new();
+        let result              = evaluate_unbound_expression(&access_expr, &empty_environment, &mut env);
+
+        assert!(result == Ok(json![ 2 ]));
+    }
+
+    #[test]
+    fn missing_field_is_not_present() {
+        let map_expr            = Expression::Map(vec![ (MapKey::Expression(Expression::string("\"Foo\"")), Expression::number("1")) ]);
+        let access_expr         = Expression::FieldAccess(Box::new((map_expr, Expression::identifier("Bar"))));
+        let empty_environment   = EmptyEnvironment::new();
+        let mut env             = ScriptExecutionEnvironment::new();
+        let result              = evaluate_unbound_expression(&access_expr, &empty_environment, &mut env);
+
+        assert!(result.unwrap_err()["error"] == json!["ObjectValueNotPresent"]);
+    }
+
+    #[test]
+    fn cannot_access_field_of_array() {
+        let array_expr          = Expression::Array(vec![Expression::number("1")]);
+        let access_expr         = Expression::FieldAccess(Box::new((array_expr, Expression::identifier("Foo"))));
+        let empty_environment   = EmptyEnvironment::new();
+        let mut env             = ScriptExecutionEnvironment::new();
+        let result              = evaluate_unbound_expression(&access_expr, &empty_environment, &mut env);
+
+        assert!(result.unwrap_err()["error"] == json!["FieldAccessMustApplyToAnObject"]);
+    }
+
     #[test]
     fn can_evaluate_tool_call() {
         let tool_expr           = Expression::identifier("test");
@@ -433,4 +803,54 @@ mod test {
 
         assert!(result == Ok(Value::String(String::from("Success"))));
     }
+
+    #[test]
+    fn can_reuse_bound_expression() {
+        let tool_expr           = Expression::Apply(Box::new((Expression::identifier("test"), Expression::Array(vec![ Expression::number("1"), Expression::number("2") ]))));
+        let tool_environment    = DynamicEnvironment::new();
+
+        tool_environment.define("test", Box::new(make_pure_tool(|(a, b): (i32, i32)| a+b)));
+
+        let bound               = bind_standalone_expression(&tool_expr, &tool_environment).unwrap();
+        let one_shot            = evaluate_unbound_expression(&tool_expr, &tool_environment, &ScriptExecutionEnvironment::new());
+
+        let first_env           = ScriptExecutionEnvironment::new();
+        let second_env          = ScriptExecutionEnvironment::new();
+
+        assert!(one_shot == Ok(json![ 3 ]));
+        assert!(evaluate_expression(&bound, &tool_environment, &first_env) == one_shot);
+        assert!(evaluate_expression(&bound, &tool_environment, &second_env) == one_shot);
+        assert!(evaluate_expression(&bound, &tool_environment, &first_env) == one_shot);
+    }
+
+    #[test]
+    fn can_evaluate_binary_number() {
+        let num_expr            = Expression::number("0b1010");
+        let empty_environment   = EmptyEnvironment::new();
+        let mut env             = ScriptExecutionEnvironment::new();
+        let result              = evaluate_unbound_expression(&num_expr, &empty_environment, &mut env);
+
+        assert!(result == Ok(json![ 10 ]));
+    }
+
+    #[test]
+    fn can_evaluate_octal_number() {
+        let num_expr            = Expression::number("0o17");
+        let empty_environment   = EmptyEnvironment::new();
+        let mut env             = ScriptExecutionEnvironment::new();
+        let result              = evaluate_unbound_expression(&num_expr, &empty_environment, &mut env);
+
+        assert!(result == Ok(json![ 15 ]));
+    }
+
+    #[test]
+    fn overflowing_number_is_malformed() {
+        let num_expr            = Expression::number("0x1ffffffffffffffff");
+        let empty_environment   = EmptyEnvironment::new();
+        let mut env             = ScriptExecutionEnvironment::new();
+        let result              = evaluate_unbound_expression(&num_expr, &empty_environment, &mut env);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err()["error"] == json![ "MalformedNumber" ]);
+    }
 }
@@ -1,7 +1,9 @@
 use std::result::Result;
+use std::sync::*;
 
 use serde_json::*;
 use gossyp_base::environment::Environment;
+use gossyp_base::basic::*;
 
 use super::bound_script::*;
 use super::evaluate_expression::*;
@@ -9,6 +11,7 @@ use super::binding_environment::*;
 use super::bind_statement::*;
 use super::script::*;
 use super::script_interpreter::*;
+use super::function_tool::*;
 
 ///
 /// Enumeration representing how a failed bound statement can be described
@@ -22,6 +25,8 @@ pub enum FailedBoundStatement {
     Assign(ScriptToken),
     Loop(Box<FailedBoundStatement>),
     While(FailedBoundExpression),
+    For(ScriptToken),
+    Break,
     Using(FailedBoundExpression),
     Def(ScriptToken)
 }
@@ -41,6 +46,8 @@ fn generate_failed_bound_statement(script: &BoundScript) -> FailedBoundStatement
         &BoundScript::Var(_, _, ref token)              => Var(token.clone()),
         &BoundScript::Loop(ref loop_box)                => Loop(Box::new(generate_failed_bound_statement(&**loop_box))),
         &BoundScript::While(ref expr, _)                => While(generate_failed_bound_expression(expr)),
+        &BoundScript::For(_, _, _, ref token)           => For(token.clone()),
+        &BoundScript::Break(_)                          => Break,
         &BoundScript::Using(ref expr, _)                => Using(generate_failed_bound_expression(expr)),
         &BoundScript::Def(ref token, _, _)              => Def(token.clone()),
     }
@@ -56,15 +63,42 @@ fn generate_script_error(error: ScriptEvaluationError, script: &BoundScript) ->
     }]
 }
 
+///
+/// The outcome of evaluating a statement
+///
+enum StatementFlow {
+    /// The statement finished normally with a value
+    Continue(Value),
+
+    /// The statement evaluated a break statement with a value, which ends the enclosing loop
+    Break(Value)
+}
+
 ///
 /// Evaluates the result of executing a sequence of steps
 ///
 pub fn evaluate_sequence(sequence: &Vec<BoundScript>, environment: &Environment, execution_environment: &mut ScriptExecutionEnvironment) -> Result<Value, Value> {
+    match evaluate_sequence_flow(sequence, environment, execution_environment)? {
+        StatementFlow::Continue(value)  => Ok(value),
+        StatementFlow::Break(value)     => Err(generate_break_error(FailedBoundStatement::Sequence(sequence.iter().map(generate_failed_bound_statement).collect()), value))
+    }
+}
+
+///
+/// Evaluates a sequence of steps, stopping early if one of them breaks
+///
+fn evaluate_sequence_flow(sequence: &Vec<BoundScript>, environment: &Environment, execution_environment: &mut ScriptExecutionEnvironment) -> Result<StatementFlow, Value> {
     // Execute the script
     let mut result = vec![];
     for statement in sequence.iter() {
+        // Stop if the script has run out of time or steps
+        execution_environment.step().map_err(|error| generate_script_error(error, statement))?;
+
         // Evaluate the next statement
-        let next_result = evaluate_statement(statement, environment, execution_environment)?;
+        let next_result = match evaluate_statement_flow(statement, environment, execution_environment)? {
+            StatementFlow::Continue(value)  => value,
+            StatementFlow::Break(value)     => return Ok(StatementFlow::Break(value))
+        };
 
         // The script result is built up from the result of each statement
         // TODO: unless there's something like a return statement?
@@ -72,15 +106,119 @@ pub fn evaluate_sequence(sequence: &Vec<BoundScript>, environment: &Environment,
     }
 
     // Script is done
+    Ok(StatementFlow::Continue(Value::Array(result)))
+}
+
+///
+/// Evaluates a break statement
+///
+/// Breaks are passed up to the enclosing loop, and are only reported as a `BreakOutsideLoop`
+/// error if there is no loop to catch them.
+///
+fn evaluate_break(expr: &Option<BoundExpression>, environment: &Environment, execution_environment: &mut ScriptExecutionEnvironment) -> Result<StatementFlow, Value> {
+    let value = match expr {
+        &Some(ref expr) => evaluate_expression(expr, environment, execution_environment)?,
+        &None           => Value::Null
+    };
+
+    Ok(StatementFlow::Break(value))
+}
+
+///
+/// Creates the error for a break statement with no loop to catch it
+///
+fn generate_break_error(failed_statement: FailedBoundStatement, value: Value) -> Value {
+    json![{
+        "error":                    ScriptEvaluationError::BreakOutsideLoop,
+        "failed-bound-statement":   failed_statement,
+        "value":                    value
+    }]
+}
+
+///
+/// Evaluates a loop statement
+///
+/// This finishes when the block evaluates a break statement (whose value is the result of the
+/// loop), produces an error or the script runs out of time or steps.
+///
+fn evaluate_loop(statement: &BoundScript, block: &BoundScript, environment: &Environment, execution_environment: &mut ScriptExecutionEnvironment) -> Result<Value, Value> {
+    loop {
+        execution_environment.step().map_err(|error| generate_script_error(error, statement))?;
+
+        if let StatementFlow::Break(value) = evaluate_statement_flow(block, environment, execution_environment)? {
+            return Ok(value);
+        }
+    }
+}
+
+///
+/// Evaluates a while statement
+///
+/// The result is the result of the last time the block was run, or null if it never ran. A
+/// break statement ends the loop with its value as the result.
+///
+fn evaluate_while(statement: &BoundScript, expr: &BoundExpression, block: &BoundScript, environment: &Environment, execution_environment: &mut ScriptExecutionEnvironment) -> Result<Value, Value> {
+    let mut result = Value::Null;
+
+    loop {
+        execution_environment.step().map_err(|error| generate_script_error(error, statement))?;
+
+        let condition = evaluate_expression(expr, environment, execution_environment)?;
+        if !is_truthy(&condition) {
+            return Ok(result);
+        }
+
+        match evaluate_statement_flow(block, environment, execution_environment)? {
+            StatementFlow::Continue(value)      => result = value,
+            StatementFlow::Break(value)         => return Ok(value)
+        }
+    }
+}
+
+///
+/// Evaluates a for statement
+///
+/// The result is an array of the results of running the block for each item. A break statement
+/// ends the loop with its value as the result.
+///
+fn evaluate_for(statement: &BoundScript, variable_index: u32, expr: &BoundExpression, block: &BoundScript, environment: &Environment, execution_environment: &mut ScriptExecutionEnvironment) -> Result<Value, Value> {
+    let items = match evaluate_expression(expr, environment, execution_environment)? {
+        Value::Array(items) => items,
+        _                   => return Err(generate_script_error(ScriptEvaluationError::ForMustIterateOverAnArray, statement))
+    };
+
+    let mut result = vec![];
+    for item in items {
+        execution_environment.step().map_err(|error| generate_script_error(error, statement))?;
+
+        execution_environment.set_variable(variable_index, Box::new(item));
+        match evaluate_statement_flow(block, environment, execution_environment)? {
+            StatementFlow::Continue(value)      => result.push(value),
+            StatementFlow::Break(value)         => return Ok(value)
+        }
+    }
+
     Ok(Value::Array(result))
 }
 
+///
+/// Evaluates a def statement by defining its tool in the environment
+///
+/// The tool replaces any existing tool with the same name, and shares the limits of the
+/// script that defines it
+///
+fn evaluate_def(name: &ScriptToken, num_arguments: u32, body: &Arc<BoundScript>, environment: &Environment, execution_environment: &ScriptExecutionEnvironment) -> Result<Value, Value> {
+    define_new_tool(environment, &name.matched, Box::new(FunctionTool::new(num_arguments, body.clone(), execution_environment.get_limits())))?;
+
+    Ok(Value::Null)
+}
+
 ///
 /// Allocates variables before continuing
 ///
-fn evaluate_allocate_variables(num_variables: u32, continuation: &BoundScript, environment: &Environment, execution_environment: &mut ScriptExecutionEnvironment) -> Result<Value, Value> {
+fn evaluate_allocate_variables(num_variables: u32, continuation: &BoundScript, environment: &Environment, execution_environment: &mut ScriptExecutionEnvironment) -> Result<StatementFlow, Value> {
     execution_environment.allocate_variables(num_variables);
-    evaluate_statement(continuation, environment, execution_environment)
+    evaluate_statement_flow(continuation, environment, execution_environment)
 }
 
 ///
@@ -94,17 +232,73 @@ fn evaluate_assignment(variable_index: u32, expr: &BoundExpression, environment:
 }
 
 ///
-/// Evaluates the result of executing a single statement
+/// Creates the environment containing the tools supplied to a using statement
 ///
-pub fn evaluate_statement(statement: &BoundScript, environment: &Environment, execution_environment: &mut ScriptExecutionEnvironment) -> Result<Value, Value> {
+/// The tools are supplied as a map of names to the names of tools in the current environment.
+///
+fn create_using_environment(tools: &Value, environment: &Environment) -> Result<DynamicEnvironment, ScriptEvaluationError> {
+    let tools = match tools {
+        &Value::Object(ref tools)   => tools,
+        _                           => return Err(ScriptEvaluationError::UsingMustEvaluateToAMapOfToolNames)
+    };
+
+    let using_environment = DynamicEnvironment::new();
+
+    for (name, tool_name) in tools.iter() {
+        let tool_name = match tool_name {
+            &Value::String(ref tool_name)   => tool_name,
+            _                               => return Err(ScriptEvaluationError::UsingMustEvaluateToAMapOfToolNames)
+        };
+
+        let tool = environment.get_json_tool(tool_name).map_err(|_| ScriptEvaluationError::ToolNameNotFound)?;
+        using_environment.define(name, tool);
+    }
+
+    Ok(using_environment)
+}
+
+///
+/// Evaluates a block with the tools supplied by a using expression
+///
+fn evaluate_using(statement: &BoundScript, expr: &BoundExpression, block: &BoundScript, environment: &Environment, execution_environment: &mut ScriptExecutionEnvironment) -> Result<StatementFlow, Value> {
+    let tools               = evaluate_expression(expr, environment, execution_environment)?;
+    let using_environment   = create_using_environment(&tools, environment).map_err(|error| generate_script_error(error, statement))?;
+    let block_environment   = CombinedEnvironment::from_environments(vec![ &using_environment, environment ]);
+
+    evaluate_statement_flow(block, &block_environment, execution_environment)
+}
+
+///
+/// Evaluates a single statement, passing any break up to the caller
+///
+fn evaluate_statement_flow(statement: &BoundScript, environment: &Environment, execution_environment: &mut ScriptExecutionEnvironment) -> Result<StatementFlow, Value> {
+    use self::StatementFlow::Continue;
+
     match statement {
         &BoundScript::AllocateVariables(num, ref continuation)  => evaluate_allocate_variables(num, &**continuation, environment, execution_environment),
-        &BoundScript::RunCommand(ref expr)                      => evaluate_expression(expr, environment, execution_environment),
-        &BoundScript::Sequence(ref steps)                       => evaluate_sequence(steps, environment, execution_environment),
-        &BoundScript::Var(index, ref expr, _)                   => evaluate_assignment(index, expr, environment, execution_environment),
-        &BoundScript::Assign(index, ref expr, _)                => evaluate_assignment(index, expr, environment, execution_environment),
+        &BoundScript::RunCommand(ref expr)                      => evaluate_expression(expr, environment, execution_environment).map(Continue),
+        &BoundScript::Sequence(ref steps)                       => evaluate_sequence_flow(steps, environment, execution_environment),
+        &BoundScript::Let(index, ref expr, _)                   => evaluate_assignment(index, expr, environment, execution_environment).map(Continue),
+        &BoundScript::Var(index, ref expr, _)                   => evaluate_assignment(index, expr, environment, execution_environment).map(Continue),
+        &BoundScript::Assign(index, ref expr, _)                => evaluate_assignment(index, expr, environment, execution_environment).map(Continue),
+        &BoundScript::Using(ref expr, ref block)                => evaluate_using(statement, expr, &**block, environment, execution_environment),
+        &BoundScript::Loop(ref block)                           => evaluate_loop(statement, &**block, environment, execution_environment).map(Continue),
+        &BoundScript::While(ref expr, ref block)                => evaluate_while(statement, expr, &**block, environment, execution_environment).map(Continue),
+        &BoundScript::For(index, ref expr, ref block, _)        => evaluate_for(statement, index, expr, &**block, environment, execution_environment).map(Continue),
+        &BoundScript::Break(ref expr)                           => evaluate_break(expr, environment, execution_environment),
+        &BoundScript::Def(ref name, num_arguments, ref body)    => evaluate_def(name, num_arguments, body, environment, execution_environment).map(Continue),
+    }
+}
 
-        _                                                       => Err(generate_script_error(ScriptEvaluationError::StatementNotImplemented, statement))
+///
+/// Evaluates the result of executing a single statement
+///
+/// A break that isn't inside a loop in the statement produces a `BreakOutsideLoop` error.
+///
+pub fn evaluate_statement(statement: &BoundScript, environment: &Environment, execution_environment: &mut ScriptExecutionEnvironment) -> Result<Value, Value> {
+    match evaluate_statement_flow(statement, environment, execution_environment)? {
+        StatementFlow::Continue(value)  => Ok(value),
+        StatementFlow::Break(value)     => Err(generate_break_error(generate_failed_bound_statement(statement), value))
     }
 }
 
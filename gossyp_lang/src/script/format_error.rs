@@ -0,0 +1,153 @@
+//!
+//! The format-error tool turns a parse error into a human-readable message showing where it occurred
+//!
+
+use std::result::Result;
+use std::error::Error;
+
+use serde_json::*;
+use gossyp_base::*;
+
+use super::parse_script_tool::*;
+
+///
+/// Parameters that can be passed to the format-error tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct FormatErrorParameters {
+    /// The script that produced the error
+    pub source: String,
+
+    /// The error to format: either a `ParseError` as returned by parse-script, or an error with a
+    /// `message` and a `position` (as reported by check-script)
+    pub error: Value
+}
+
+impl FormatErrorParameters {
+    pub fn new(source: &str, error: Value) -> FormatErrorParameters {
+        FormatErrorParameters { source: String::from(source), error: error }
+    }
+}
+
+///
+/// Tool that formats an error along with the line of the script where it occurred
+///
+/// The result is a string of the form:
+///
+/// ```text
+/// 3:7: Was expecting '='
+/// let x 2
+///       ^
+/// ```
+///
+/// Errors with no position are assumed to have occurred at the end of the script.
+///
+pub struct FormatErrorTool {
+}
+
+impl FormatErrorTool {
+    ///
+    /// Creates a new format-error tool
+    ///
+    pub fn new() -> FormatErrorTool {
+        FormatErrorTool { }
+    }
+
+    ///
+    /// Retrieves the message and the position (as a byte offset) from an error
+    ///
+    fn message_and_position(error: &Value) -> (String, Option<usize>) {
+        let message = error["message"].as_str()
+            .or_else(|| error["error"].as_str())
+            .unwrap_or("Error");
+
+        let position = if error.get("remaining").is_some() {
+            // ParseErrors store the tokens that were remaining when the error occurred
+            from_value::<ParseError>(error.clone()).ok()
+                .and_then(|parse_error| parse_error.position())
+                .map(|(start, _end)| start as usize)
+        } else {
+            error["position"]["start"].as_u64().map(|start| start as usize)
+        };
+
+        (String::from(message), position)
+    }
+
+    ///
+    /// Formats an error message occurring at a particular byte offset in the source (None to indicate the end of the script)
+    ///
+    pub fn format(source: &str, message: &str, position: Option<usize>) -> String {
+        // Errors at the end of the script point just after the last line with any content
+        let end_of_script   = source.trim_end_matches(|c| c == '\n' || c == '\r').len();
+        let mut position    = position.unwrap_or(end_of_script).min(end_of_script);
+        while !source.is_char_boundary(position) {
+            position -= 1;
+        }
+
+        // Find the line containing the position
+        let line_start      = source[0..position].rfind('\n').map(|pos| pos+1).unwrap_or(0);
+        let line_end        = source[position..].find('\n').map(|pos| pos+position).unwrap_or(source.len());
+        let line            = source[line_start..line_end].trim_end_matches('\r');
+        let line_number     = source[0..line_start].matches('\n').count() + 1;
+
+        // Indent the caret with whitespace matching the line, so tabs line up too
+        let before_caret    = &source[line_start..position];
+        let indent: String  = before_caret.chars().map(|c| if c == '\t' { '\t' } else { ' ' }).collect();
+        let column          = before_caret.chars().count() + 1;
+
+        format!("{}:{}: {}\n{}\n{}^", line_number, column, message, line, indent)
+    }
+}
+
+impl Tool for FormatErrorTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        match from_value::<FormatErrorParameters>(input) {
+            Ok(parameters) => {
+                let (message, position) = FormatErrorTool::message_and_position(&parameters.error);
+                Ok(Value::String(FormatErrorTool::format(&parameters.source, &message, position)))
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to format-error must be of the form { \"source\": <string>, \"error\": <error> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::lex_script_tool::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn caret_is_under_error_column() {
+        let source  = "let = 1\nfoo\nlet x 2";
+        let lexed   = create_lex_script_tool().lex(source);
+        let errors  = ParseScriptTool::parse_all(&lexed).unwrap_err();
+
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<FormatErrorParameters, String>::from(Box::new(FormatErrorTool::new()));
+
+        let result  = tool.invoke(FormatErrorParameters::new(source, to_value(&errors[1]).unwrap()), &env);
+
+        assert!(result == Ok(format!("3:7: {}\nlet x 2\n      ^", errors[1].message)));
+    }
+
+    #[test]
+    fn can_format_check_script_errors() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<FormatErrorParameters, String>::from(Box::new(FormatErrorTool::new()));
+
+        let error   = json![{ "message": "Bad", "position": { "start": 10, "end": 11 } }];
+        let result  = tool.invoke(FormatErrorParameters::new("foo\n\tbar baz\n", error), &env);
+
+        assert!(result == Ok(String::from("2:7: Bad\n\tbar baz\n\t     ^")));
+    }
+
+    #[test]
+    fn errors_without_position_are_at_end_of_script() {
+        assert!(FormatErrorTool::format("foo\nlet x =\n\n", "Missing expression", None) == "2:8: Missing expression\nlet x =\n       ^");
+    }
+}
@@ -0,0 +1,104 @@
+//!
+//! Tools defined by `def` statements in a script
+//!
+
+use std::result::Result;
+use std::sync::*;
+
+use serde_json::*;
+use gossyp_base::*;
+
+use super::bound_script::*;
+use super::evaluate_statement::*;
+use super::script_interpreter::*;
+
+///
+/// A tool that runs the body of a `def` statement
+///
+/// A tool with a single argument receives the whole input in that argument. Tools with more
+/// arguments must be called with an array or a tuple, whose values are assigned to the arguments
+/// in order (missing values are null). The result is the value of the last statement in the body.
+///
+/// A `break` in the body that isn't inside one of its loops is a `BreakOutsideLoop` error: it
+/// never ends a loop in the script that called the tool.
+///
+/// The body runs with the limits of the script that defined the tool, and each call counts
+/// towards its recursion limit while it's running.
+///
+pub struct FunctionTool {
+    /// The number of arguments (which are the first variables of the body)
+    num_arguments: u32,
+
+    /// The body of the tool
+    body: Arc<BoundScript>,
+
+    /// The limits of the script that defined the tool
+    limits: Arc<ScriptLimits>
+}
+
+impl FunctionTool {
+    ///
+    /// Creates a new function tool from a bound body
+    ///
+    pub fn new(num_arguments: u32, body: Arc<BoundScript>, limits: Arc<ScriptLimits>) -> FunctionTool {
+        FunctionTool { num_arguments: num_arguments, body: body, limits: limits }
+    }
+
+    ///
+    /// Returns true if a statement produces an array of results (one per statement)
+    ///
+    fn is_sequence(statement: &BoundScript) -> bool {
+        match statement {
+            &BoundScript::AllocateVariables(_, ref statement)   => FunctionTool::is_sequence(statement),
+            &BoundScript::Sequence(_)                           => true,
+            _                                                   => false
+        }
+    }
+
+    ///
+    /// Stores the input to the tool in the argument variables
+    ///
+    fn set_arguments(&self, input: Value, execution_environment: &mut ScriptExecutionEnvironment) -> Result<(), Value> {
+        execution_environment.allocate_variables(self.num_arguments);
+
+        match (self.num_arguments, input) {
+            (0, _)                      => (),
+            (1, input)                  => execution_environment.set_variable(0, Box::new(input)),
+            (_, Value::Array(values))   => {
+                for (index, value) in values.into_iter().take(self.num_arguments as usize).enumerate() {
+                    execution_environment.set_variable(index as u32, Box::new(value));
+                }
+            },
+            (_, other)                  => return Err(json![{
+                "error":    "Tools with several arguments must be called with an array or a tuple",
+                "input":    other
+            }])
+        }
+
+        Ok(())
+    }
+}
+
+impl Tool for FunctionTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let _call = self.limits.enter_call().map_err(|error| json![{ "error": error }])?;
+
+        let mut execution_environment = ScriptExecutionEnvironment::new();
+        execution_environment.set_limits(self.limits.clone());
+        self.set_arguments(input, &mut execution_environment)?;
+
+        let result = evaluate_statement(&*self.body, environment, &mut execution_environment)?;
+
+        // A sequence produces the result of every statement: the tool only produces the last one
+        match result {
+            Value::Array(mut results)   => {
+                if FunctionTool::is_sequence(&*self.body) {
+                    Ok(results.pop().unwrap_or(Value::Null))
+                } else {
+                    Ok(Value::Array(results))
+                }
+            },
+            other                       => Ok(other)
+        }
+    }
+}
@@ -78,11 +78,45 @@ mod test {
         assert!(lex_tokens("something-something") == vec![ String::from("Identifier") ]);
     }
 
+    #[test]
+    fn operators_other_than_hyphen_end_identifier() {
+        assert!(lex_tokens("x*2") == vec![ String::from("Identifier"), String::from("*"), String::from("Number") ]);
+        assert!(lex_tokens("x+1") == vec![ String::from("Identifier"), String::from("+"), String::from("Number") ]);
+        assert!(lex_tokens("x-1") == vec![ String::from("Identifier") ]);
+    }
+
     #[test]
     fn can_lex_let_keyword() {
         assert!(lex_tokens("let") == vec![ String::from("let") ]);
     }
 
+    #[test]
+    fn can_lex_break_keyword() {
+        assert!(lex_tokens("break") == vec![ String::from("break") ]);
+    }
+
+    #[test]
+    fn can_lex_boolean_keywords() {
+        assert!(lex_tokens("and") == vec![ String::from("and") ]);
+        assert!(lex_tokens("or") == vec![ String::from("or") ]);
+        assert!(lex_tokens("not") == vec![ String::from("not") ]);
+        assert!(lex_tokens("nothing") == vec![ String::from("Identifier") ]);
+    }
+
+    #[test]
+    fn can_lex_constant_keywords() {
+        assert!(lex_tokens("true") == vec![ String::from("true") ]);
+        assert!(lex_tokens("false") == vec![ String::from("false") ]);
+        assert!(lex_tokens("null") == vec![ String::from("null") ]);
+    }
+
+    #[test]
+    fn identifiers_starting_with_constant_keywords_are_identifiers() {
+        assert!(lex_tokens("truthy") == vec![ String::from("Identifier") ]);
+        assert!(lex_tokens("falsehood") == vec![ String::from("Identifier") ]);
+        assert!(lex_tokens("nullable") == vec![ String::from("Identifier") ]);
+    }
+
     #[test]
     fn can_lex_whitespace() {
         assert!(lex_tokens(" ") == vec![ String::from("Whitespace") ]);
@@ -93,6 +127,21 @@ mod test {
         assert!(lex_tokens("+") == vec![ String::from("+") ]);
     }
 
+    #[test]
+    fn can_lex_star_symbol() {
+        assert!(lex_tokens("*") == vec![ String::from("*") ]);
+    }
+
+    #[test]
+    fn can_lex_percent_symbol() {
+        assert!(lex_tokens("%") == vec![ String::from("%") ]);
+    }
+
+    #[test]
+    fn can_lex_minus_symbol() {
+        assert!(lex_tokens("1 - 2") == vec![ String::from("Number"), String::from("Whitespace"), String::from("-"), String::from("Whitespace"), String::from("Number") ]);
+    }
+
     #[test]
     fn can_lex_dot_symbol() {
         assert!(lex_tokens(".") == vec![ String::from(".") ]);
@@ -157,4 +206,26 @@ mod test {
     fn can_lex_decimal_number_beginning_with_dot() {
         assert!(lex_tokens(".21") == vec![ String::from("Number") ]);
     }
+
+    #[test]
+    fn can_lex_hex_number() {
+        assert!(lex_tokens("0x1f") == vec![ String::from("HexNumber") ]);
+    }
+
+    #[test]
+    fn can_lex_binary_number() {
+        assert!(lex_tokens("0b1010") == vec![ String::from("BinaryNumber") ]);
+    }
+
+    #[test]
+    fn can_lex_octal_number() {
+        assert!(lex_tokens("0o17") == vec![ String::from("OctalNumber") ]);
+    }
+
+    #[test]
+    fn can_lex_negative_numbers_in_other_bases() {
+        assert!(lex_tokens("-0x1f") == vec![ String::from("HexNumber") ]);
+        assert!(lex_tokens("-0b1010") == vec![ String::from("BinaryNumber") ]);
+        assert!(lex_tokens("-0o17") == vec![ String::from("OctalNumber") ]);
+    }
 }
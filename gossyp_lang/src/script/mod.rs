@@ -11,15 +11,43 @@ pub mod evaluate_statement;
 pub mod evaluate_expression;
 pub mod tool;
 pub mod evaluate;
+pub mod check_script;
+pub mod script_dependencies;
+pub mod format_error;
+pub mod calc;
+pub mod version;
+pub mod define_script_tool;
+pub mod run_script_with_bindings;
+pub mod run_script_timeout;
+pub mod error_kinds;
+pub mod define_many;
+pub mod run_isolated;
+pub mod safe_run;
+pub mod script_to_dot;
+pub mod function_tool;
 
 use self::lex_script_tool::*;
 use self::parse_script_tool::*;
 use self::script_interpreter::*;
 use self::stateful_eval::*;
+use self::check_script::*;
+use self::script_dependencies::*;
+use self::format_error::*;
+use self::calc::*;
+use self::version::*;
+use self::define_script_tool::*;
+use self::run_script_with_bindings::*;
+use self::run_script_timeout::*;
+use self::error_kinds::*;
+use self::define_many::*;
+use self::run_isolated::*;
+use self::safe_run::*;
+use self::script_to_dot::*;
 use gossyp_base::*;
 use gossyp_base::basic::*;
 
 pub use self::evaluate::*;
+pub use self::define_script_tool::script_to_tool;
 
 ///
 /// ToolSet for dealing with the scripting language
@@ -33,7 +61,21 @@ impl ToolSet for ScriptTools {
             (String::from(tool::LEX_SCRIPT),                    Box::new(create_lex_script_tool())),
             (String::from(tool::PARSE_SCRIPT),                  ParseScriptTool::new_tool()),
             (String::from(tool::EVAL_SCRIPT),                   InterpretedScriptTool::new_script_eval_tool()),
-            (String::from(tool::CREATE_EVALUATOR_WITH_STATE),   Box::new(make_dynamic_tool(create_evaluator_with_state_tool)))
+            (String::from(tool::CREATE_EVALUATOR_WITH_STATE),   Box::new(make_dynamic_tool(create_evaluator_with_state_tool))),
+            (String::from(tool::CHECK_SCRIPT),                  Box::new(CheckScriptTool::new())),
+            (String::from(tool::SCRIPT_DEPENDENCIES),           Box::new(ScriptDependenciesTool::new())),
+            (String::from(tool::CAN_RUN_SCRIPT),                Box::new(CanRunScriptTool::new())),
+            (String::from(tool::FORMAT_ERROR),                  Box::new(FormatErrorTool::new())),
+            (String::from(tool::CALC),                          Box::new(CalcTool::new())),
+            (String::from(tool::VERSION),                       Box::new(VersionTool::new())),
+            (String::from(tool::DEFINE_SCRIPT_TOOL),            Box::new(DefineScriptTool::new())),
+            (String::from(tool::RUN_SCRIPT_WITH_BINDINGS),      Box::new(RunScriptWithBindingsTool::new())),
+            (String::from(tool::RUN_SCRIPT_TIMEOUT),            Box::new(RunScriptTimeoutTool::new())),
+            (String::from(tool::ERROR_KINDS),                   Box::new(ErrorKindsTool::new())),
+            (String::from(tool::DEFINE_MANY),                   Box::new(DefineManyTool::new())),
+            (String::from(tool::RUN_ISOLATED),                  Box::new(RunIsolatedTool::new())),
+            (String::from(tool::SAFE_RUN),                      Box::new(SafeRunTool::new())),
+            (String::from(tool::SCRIPT_TO_DOT),                 Box::new(ScriptToDotTool::new()))
         ]
     }
 }
@@ -25,8 +25,21 @@ impl ParseError {
     fn new<'a>(state: &ParseState<'a>, message: &str) -> ParseError {
         ParseError { message: String::from(message), remaining: state.remaining.to_vec() }
     }
+
+    ///
+    /// Returns the start and end of the token where this error occurred (or None if it occurred at the end of the file)
+    ///
+    pub fn position(&self) -> Option<(i32, i32)> {
+        self.remaining.iter()
+            .filter(|token| ParseState::is_syntax(token))
+            .nth(0)
+            .map(|token| (token.start, token.end))
+    }
 }
 
+/// The precedence of the comparison operators (the arithmetic operators bind more tightly, and the boolean operators less)
+const COMPARISON_PRECEDENCE: u32 = 3;
+
 struct ParseState<'a> {
     remaining: &'a [ScriptToken]
 }
@@ -148,6 +161,10 @@ impl<'a> ParseState<'a> {
             // for identifier in expression { statements }
             self.parse_for()
 
+        } else if self.accept(ScriptLexerToken::Break).is_some() {
+            // break (expression)
+            self.parse_break()
+
         } else if let Some(identifier) = self.accept(ScriptLexerToken::Identifier) {
             // Could be Identifier '=' x to be an assignment
             if self.accept(ScriptLexerToken::symbol("=")).is_some() {
@@ -174,8 +191,10 @@ impl<'a> ParseState<'a> {
         // Turn the initial identifier into an expression
         let identifier_expr = Expression::Identifier(initial_identifier.clone());
 
-        // Starts with an expression specifying the command to run
-        self.parse_expression_rhs(identifier_expr).and_then(move |command_expression| {
+        // Starts with an expression specifying the command to run (which might be an arithmetic expression like 'x + 1')
+        self.parse_expression_rhs(identifier_expr)
+            .and_then(|command_expression| self.parse_binary_operators(command_expression, 0))
+            .and_then(move |command_expression| {
             // Followed by arguments (or an end-of-expression marker)
             if self.accept(ScriptLexerToken::Newline).is_some()
                || self.lookahead_is(ScriptLexerToken::symbol("}"))
@@ -183,7 +202,7 @@ impl<'a> ParseState<'a> {
                 // Newline or EOF ends a command
                 Ok(Script::RunCommand(command_expression))
 
-            } else if !command_expression.is_apply() {
+            } else if !command_expression.is_apply() && !command_expression.is_binary_op() {
                 // Anything else should be an argument expression
                 self.parse_expression().and_then(move |argument_expression| {
                     Ok(Script::RunCommand(Expression::Apply(Box::new((command_expression, argument_expression)))))
@@ -210,13 +229,87 @@ impl<'a> ParseState<'a> {
     ///
     /// Parses an Expression
     ///
+    /// Expressions are operands combined with binary operators: `* / %` bind more tightly than
+    /// `+ -`, which bind more tightly than the comparison operators `== != < <= > >=`. Next is the
+    /// prefix operator `not`, then `and`, then `or`. Operators with the same precedence are
+    /// evaluated from left to right.
+    ///
     fn parse_expression(&mut self) -> Result<Expression, ParseError> {
+        let left_expr = self.parse_operand()?;
+
+        self.parse_binary_operators(left_expr, 0)
+    }
+
+    ///
+    /// Returns the precedence of the binary operator that's next in the input, or None if the next token isn't a binary operator
+    ///
+    fn lookahead_operator_precedence(&self) -> Option<u32> {
+        match self.lookahead() {
+            Some((&ScriptToken { token: ScriptLexerToken::Or, .. }, _))     => Some(1),
+            Some((&ScriptToken { token: ScriptLexerToken::And, .. }, _))    => Some(2),
+
+            Some((&ScriptToken { token: ScriptLexerToken::Symbol(ref symbol), .. }, _)) => {
+                match symbol.as_str() {
+                    "==" | "!=" | "<" | "<=" | ">" | ">="   => Some(COMPARISON_PRECEDENCE),
+                    "+" | "-"                               => Some(COMPARISON_PRECEDENCE+1),
+                    "*" | "/" | "%"                         => Some(COMPARISON_PRECEDENCE+2),
+                    _                                       => None
+                }
+            },
+
+            _ => None
+        }
+    }
+
+    ///
+    /// Parses any binary operators following an operand, where the operators have at least the specified precedence
+    ///
+    fn parse_binary_operators(&mut self, left_expr: Expression, min_precedence: u32) -> Result<Expression, ParseError> {
+        let mut left_expr = left_expr;
+
+        while let Some(precedence) = self.lookahead_operator_precedence() {
+            if precedence < min_precedence {
+                break;
+            }
+
+            // Accept the operator
+            let (operator, remaining)   = self.lookahead().unwrap();
+            self.remaining              = remaining;
+
+            // Operators that bind more tightly are evaluated first: 'a + b * c' is 'a + (b * c)'
+            let mut right_expr = self.parse_operand()?;
+            while let Some(next_precedence) = self.lookahead_operator_precedence() {
+                if next_precedence <= precedence {
+                    break;
+                }
+
+                right_expr = self.parse_binary_operators(right_expr, next_precedence)?;
+            }
+
+            left_expr = Expression::BinaryOp(operator.clone(), Box::new((left_expr, right_expr)));
+        }
+
+        Ok(left_expr)
+    }
+
+    ///
+    /// Parses an operand of a binary operator
+    ///
+    fn parse_operand(&mut self) -> Result<Expression, ParseError> {
+        if let Some(not) = self.accept(ScriptLexerToken::Not) {
+            // 'not' applies to everything up to the next 'and' or 'or'
+            let operand = self.parse_operand()?;
+            let operand = self.parse_binary_operators(operand, COMPARISON_PRECEDENCE)?;
+
+            return Ok(Expression::UnaryOp(not.clone(), Box::new(operand)));
+        }
+
         let left_expr = if self.lookahead_is(ScriptLexerToken::symbol("[")) {
-            self.parse_array_expression(ScriptLexerToken::symbol("["), ScriptLexerToken::symbol("]"))
+            self.parse_array_expression("[", "]")
                 .map(|array_entries| Expression::Array(array_entries))
 
         } else if self.lookahead_is(ScriptLexerToken::symbol("(")) {
-            self.parse_array_expression(ScriptLexerToken::symbol("("), ScriptLexerToken::symbol(")"))
+            self.parse_array_expression("(", ")")
                 .map(|tuple_entries| {
                     if tuple_entries.len() == 1 {
                         // (x) == x
@@ -269,13 +362,18 @@ impl<'a> ParseState<'a> {
     ///
     fn parse_expression_rhs_not_field(&mut self, left_expr: Expression) -> Result<Expression, ParseError> {
         if self.accept(ScriptLexerToken::symbol("[")).is_some() {
-            // a[b] indexing
-            let right_expr = self.parse_expression()?;
+            // a[b] indexing or a[b:c] slicing (where b and c are optional)
+            let start_expr = if self.lookahead_is(ScriptLexerToken::symbol(":")) { None } else { Some(self.parse_expression()?) };
+
+            let array_expr = if self.accept(ScriptLexerToken::symbol(":")).is_some() {
+                let end_expr = if self.lookahead_is(ScriptLexerToken::symbol("]")) { None } else { Some(self.parse_expression()?) };
+                Expression::Slice(Box::new((left_expr, start_expr, end_expr)))
+            } else {
+                Expression::Index(Box::new((left_expr, start_expr.unwrap())))
+            };
 
             if self.accept(ScriptLexerToken::symbol("]")).is_some() {
                 // Got all of a[b]
-                let array_expr = Expression::Index(Box::new((left_expr, right_expr)));
-
                 Ok(self.parse_expression_rhs(array_expr)?)
             } else {
                 // Missing ']'
@@ -284,7 +382,7 @@ impl<'a> ParseState<'a> {
 
         } else if self.lookahead_is(ScriptLexerToken::symbol("(")) {
             // a(b) = 'call command a with parameters b'
-            let parameters  = self.parse_array_expression(ScriptLexerToken::symbol("("), ScriptLexerToken::symbol(")"))?;
+            let parameters  = self.parse_array_expression("(", ")")?;
             let tuple       = if parameters.len() == 1 { parameters[0].clone() } else { Expression::Tuple(parameters) };
             let apply_expr  = Expression::Apply(Box::new((left_expr, tuple)));
 
@@ -305,43 +403,69 @@ impl<'a> ParseState<'a> {
     }
 
     ///
-    /// Parses an array expression
+    /// Skips tokens up to and including the next newline (used to recover after an error)
     ///
-    fn parse_array_expression(&mut self, open_bracket: ScriptLexerToken, close_bracket: ScriptLexerToken) -> Result<Vec<Expression>, ParseError> {
-        // Opening '['
-        if self.accept(open_bracket.clone()).is_none() {
-            // Not an array
-            return Err(ParseError::new(self, "Not an array"));
+    fn skip_line(&mut self) {
+        while let Some((lookahead, remaining)) = self.lookahead() {
+            self.remaining = remaining;
+
+            if lookahead.token == ScriptLexerToken::Newline {
+                break;
+            }
         }
+    }
 
-        let mut components = vec![];
+    ///
+    /// Parses a comma-separated list of items up to a closing bracket (the opening bracket should already have been accepted)
+    ///
+    /// Newlines are ignored around items and a trailing comma before the closing bracket is allowed,
+    /// but every comma must follow an item (so '[,]' is not a valid empty list).
+    ///
+    fn parse_comma_separated<TItem, TParseItem>(&mut self, close_bracket: &str, parse_item: TParseItem) -> Result<Vec<TItem>, ParseError>
+    where TParseItem: Fn(&mut Self) -> Result<TItem, ParseError> {
+        let mut items = vec![];
+
+        // Newlines allowed after the opening bracket
+        self.skip_newlines();
+
+        // List goes until the closing bracket
+        while self.accept(ScriptLexerToken::symbol(close_bracket)).is_none() {
+            if self.lookahead_is(ScriptLexerToken::symbol(",")) {
+                // Commas can only follow an item
+                return Err(ParseError::new(self, "Expected an expression before ','"));
+            }
 
-        // Array goes until the final ']'
-        while self.accept(close_bracket.clone()).is_none() {
-            // Read the next component
-            let next_component = self.parse_expression();
-            
-            // Add to the components
-            match next_component {
-                Err(failure)    => return Err(failure),
-                Ok(component)   => components.push(component)
-            };
+            // Read the next item
+            items.push(parse_item(self)?);
 
-            // Components separated by commas. Newlines are ignored
+            // Items separated by commas. Newlines are ignored
             self.skip_newlines();
 
-            // Followed by a comma or the closing ']'
+            // Followed by a comma or the closing bracket
             if self.accept(ScriptLexerToken::symbol(",")).is_none()
-                && !self.lookahead_is(close_bracket.clone()) {
+                && !self.lookahead_is(ScriptLexerToken::symbol(close_bracket)) {
                 // Expected ','
-                return Err(ParseError::new(self, "Expected ',' or ']'"));
+                return Err(ParseError::new(self, &format!("Expected ',' or '{}'", close_bracket)));
             }
 
             // Newlines allowed after the ','
             self.skip_newlines();
         }
 
-        Ok(components)
+        Ok(items)
+    }
+
+    ///
+    /// Parses an array expression ('[ foo, bar, ... ]') or a tuple ('( foo, bar, ... )')
+    ///
+    fn parse_array_expression(&mut self, open_bracket: &str, close_bracket: &str) -> Result<Vec<Expression>, ParseError> {
+        // Opening '['
+        if self.accept(ScriptLexerToken::symbol(open_bracket)).is_none() {
+            // Not an array
+            return Err(ParseError::new(self, "Not an array"));
+        }
+
+        self.parse_comma_separated(close_bracket, |state| state.parse_expression())
     }
 
     ///
@@ -350,51 +474,50 @@ impl<'a> ParseState<'a> {
     fn parse_map_expression(&mut self) -> Result<Expression, ParseError> {
         // Opening '{'
         if self.accept(ScriptLexerToken::symbol("{")).is_none() {
-            // Not an array
+            // Not a map
             return Err(ParseError::new(self, "Not a map"));
         }
 
-        let mut components = vec![];
-
-        // Array goes until the final '}'
-        while self.accept(ScriptLexerToken::symbol("}")).is_none() {
-            // <expr> : <expr>
-            
+        // <expr> : <expr>, ...
+        let components = self.parse_comma_separated("}", |state| {
             // Parse the key component
-            let key_component = match self.parse_expression() {
-                Err(failure)    => return Err(failure),
-                Ok(component)   => component
-            };
+            let key_component = state.parse_map_key()?;
 
             // ':'
-            if self.accept(ScriptLexerToken::symbol(":")).is_none() {
-                return Err(ParseError::new(self, "Expecting ':'"));
+            if state.accept(ScriptLexerToken::symbol(":")).is_none() {
+                return Err(ParseError::new(state, "Expecting ':'"));
             }
 
             // Parse the value component
-            let value_component = match self.parse_expression() {
-                Err(failure)    => return Err(failure),
-                Ok(component)   => component
-            };
+            let value_component = state.parse_expression()?;
 
-            // Add to the components
-            components.push((key_component, value_component));
+            Ok((key_component, value_component))
+        })?;
 
-            // Components separated by commas. Newlines are ignored
-            self.skip_newlines();
+        Ok(Expression::Map(components))
+    }
 
-            // Followed by a comma or the closing '}'
-            if self.accept(ScriptLexerToken::symbol(",")).is_none()
-                && !self.lookahead_is(ScriptLexerToken::symbol("}")) {
-                // Expected ','
-                return Err(ParseError::new(self, "Expected ',' or ']'"));
-            }
+    ///
+    /// Parses the key of a map expression
+    ///
+    /// A bare identifier such as `{ foo: 1 }` is a name, but a parenthesized one such as
+    /// `{ (foo): 1 }` is an expression to evaluate, like any other key.
+    ///
+    fn parse_map_key(&mut self) -> Result<MapKey, ParseError> {
+        let is_parenthesized    = self.lookahead_is(ScriptLexerToken::symbol("("));
+        let key                 = self.parse_expression()?;
+
+        match key {
+            Expression::Identifier(name) => {
+                if is_parenthesized {
+                    Ok(MapKey::Expression(Expression::Identifier(name)))
+                } else {
+                    Ok(MapKey::Name(name))
+                }
+            },
 
-            // Newlines allowed after the ','
-            self.skip_newlines();
+            key => Ok(MapKey::Expression(key))
         }
-
-        Ok(Expression::Map(components))
     }
 
     ///
@@ -417,10 +540,28 @@ impl<'a> ParseState<'a> {
             // Hex numbers work like normal numbers
             Ok(Expression::Number(number.clone()))
 
+        } else if let Some(number) = self.accept(ScriptLexerToken::BinaryNumber) {
+            // As do binary numbers...
+            Ok(Expression::Number(number.clone()))
+
+        } else if let Some(number) = self.accept(ScriptLexerToken::OctalNumber) {
+            // ... and octal numbers
+            Ok(Expression::Number(number.clone()))
+
         } else if let Some(string) = self.accept(ScriptLexerToken::String) {
             // Simple expression
             Ok(Expression::String(string.clone()))
 
+        } else if let Some(constant) = self.accept(ScriptLexerToken::True) {
+            // Constant values
+            Ok(Expression::Constant(constant.clone()))
+
+        } else if let Some(constant) = self.accept(ScriptLexerToken::False) {
+            Ok(Expression::Constant(constant.clone()))
+
+        } else if let Some(constant) = self.accept(ScriptLexerToken::Null) {
+            Ok(Expression::Constant(constant.clone()))
+
         } else {
             // Syntax error
             Err(ParseError::new(self, "Syntax error (was expecting an expression)"))
@@ -459,7 +600,35 @@ impl<'a> ParseState<'a> {
     }
 
     fn parse_def(&mut self) -> Result<Script, ParseError> {
-        unimplemented!()
+        // def identifier(identifier, ...) { statements }
+        let name = match self.accept(ScriptLexerToken::Identifier) {
+            Some(name)  => name.clone(),
+            None        => return Err(ParseError::new(self, "Was expecting an identifier for the tool name"))
+        };
+
+        if self.accept(ScriptLexerToken::symbol("(")).is_none() {
+            return Err(ParseError::new(self, "Was expecting '('"));
+        }
+
+        let mut arguments = vec![];
+        if self.accept(ScriptLexerToken::symbol(")")).is_none() {
+            loop {
+                match self.accept(ScriptLexerToken::Identifier) {
+                    Some(argument)  => arguments.push(argument.clone()),
+                    None            => return Err(ParseError::new(self, "Was expecting an identifier for an argument"))
+                }
+
+                if self.accept(ScriptLexerToken::symbol(")")).is_some() {
+                    break;
+                } else if self.accept(ScriptLexerToken::symbol(",")).is_none() {
+                    return Err(ParseError::new(self, "Was expecting ',' or ')'"));
+                }
+            }
+        }
+
+        let block = self.parse_statement_block()?;
+
+        Ok(Script::Def(name, arguments, Box::new(block)))
     }
 
     fn parse_if(&mut self) -> Result<Script, ParseError> {
@@ -481,7 +650,14 @@ impl<'a> ParseState<'a> {
         if self.accept(ScriptLexerToken::symbol("{")).is_some() {
             let mut block = vec![];
 
-            while self.accept(ScriptLexerToken::symbol("}")).is_none() {
+            loop {
+                // Statements that don't end at a newline (such as assignments) leave it before the closing '}'
+                while self.accept(ScriptLexerToken::Newline).is_some() { }
+
+                if self.accept(ScriptLexerToken::symbol("}")).is_some() {
+                    break;
+                }
+
                 block.push(self.parse_statement()?)
             }
 
@@ -518,8 +694,31 @@ impl<'a> ParseState<'a> {
         Ok(Script::Loop(Box::new(block)))
     }
 
+    fn parse_break(&mut self) -> Result<Script, ParseError> {
+        // break, or break expr
+        if self.accept(ScriptLexerToken::Newline).is_some()
+           || self.lookahead_is(ScriptLexerToken::symbol("}"))
+           || self.lookahead_is(ScriptLexerToken::EndOfFile) {
+            Ok(Script::Break(None))
+        } else {
+            Ok(Script::Break(Some(self.parse_expression()?)))
+        }
+    }
+
     fn parse_for(&mut self) -> Result<Script, ParseError> {
-        unimplemented!()
+        // for identifier in expr { statements }
+        if let Some(identifier) = self.accept(ScriptLexerToken::Identifier) {
+            if self.accept(ScriptLexerToken::In).is_some() {
+                let collection  = self.parse_expression()?;
+                let block       = self.parse_statement_block()?;
+
+                Ok(Script::For(identifier.clone(), collection, Box::new(block)))
+            } else {
+                Err(ParseError::new(self, "Was expecting 'in'"))
+            }
+        } else {
+            Err(ParseError::new(self, "Was expecting an identifier for the loop variable"))
+        }
     }
 }
 
@@ -537,11 +736,11 @@ impl ParseScriptTool {
     /// Tries to parse a script from the output of the lexer
     ///
     pub fn parse(input: &[LexerMatch]) -> Result<Vec<Script>, ParseError> {
-        // Convert to script tokens
-        let as_script_token: Vec<ScriptToken> = input
+        // Convert to script tokens ('3 -4' is a subtraction rather than two numbers)
+        let as_script_token: Vec<ScriptToken> = split_subtractions(input
             .iter()
             .map(|token| ScriptToken::from_lexer_match(token))
-            .collect();
+            .collect());
 
         // Parse until we reach the end of the file
         let mut parser = ParseState { remaining: &as_script_token };
@@ -564,6 +763,45 @@ impl ParseScriptTool {
 
         Ok(result)
     }
+
+    ///
+    /// Parses a script from the output of the lexer, carrying on after any errors so that every
+    /// error in the script can be reported
+    ///
+    /// After an error, parsing continues from the next line.
+    ///
+    pub fn parse_all(input: &[LexerMatch]) -> Result<Vec<Script>, Vec<ParseError>> {
+        // Convert to script tokens ('3 -4' is a subtraction rather than two numbers)
+        let as_script_token: Vec<ScriptToken> = split_subtractions(input
+            .iter()
+            .map(|token| ScriptToken::from_lexer_match(token))
+            .collect());
+
+        // Parse until we reach the end of the file
+        let mut parser = ParseState { remaining: &as_script_token };
+        let mut result = vec![];
+        let mut errors = vec![];
+
+        while !parser.lookahead_is(ScriptLexerToken::EndOfFile) {
+            match parser.parse_statement() {
+                Ok(next_statement)  => result.push(next_statement),
+                Err(failure)        => {
+                    // Record the error and try again from the next line
+                    errors.push(failure);
+                    parser.skip_line();
+                }
+            }
+
+            // Swallow any trailing newlines
+            parser.skip_newlines();
+        }
+
+        if errors.len() == 0 {
+            Ok(result)
+        } else {
+            Err(errors)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -606,6 +844,122 @@ mod test {
         assert!(match cmd { &Script::RunCommand(Expression::Identifier(_)) => true, _ => false});
     }
 
+    #[test]
+    fn multiplication_binds_more_tightly_than_addition() {
+        let parsed  = parse("let x = 1 + 2 * 3").unwrap();
+
+        assert!(match parsed[0] {
+            Script::Let(_, Expression::BinaryOp(ref add, ref operands)) => {
+                add.matched == "+" && match operands.1 {
+                    Expression::BinaryOp(ref mul, _)    => mul.matched == "*",
+                    _                                   => false
+                }
+            },
+            _ => false
+        });
+    }
+
+    #[test]
+    fn comparison_binds_less_tightly_than_arithmetic() {
+        let parsed  = parse("let x = 1 + 2 <= 3").unwrap();
+
+        assert!(match parsed[0] {
+            Script::Let(_, Expression::BinaryOp(ref compare, ref operands)) => {
+                compare.matched == "<=" && operands.0.is_binary_op()
+            },
+            _ => false
+        });
+    }
+
+    #[test]
+    fn can_parse_constants() {
+        let parsed  = parse("let x = [ true, false, null ]").unwrap();
+
+        assert!(match parsed[0] {
+            Script::Let(_, Expression::Array(ref items)) => {
+                items.iter()
+                    .map(|item| match item { &Expression::Constant(ref constant) => constant.token.clone(), _ => ScriptLexerToken::Unknown })
+                    .collect::<Vec<_>>() == vec![ ScriptLexerToken::True, ScriptLexerToken::False, ScriptLexerToken::Null ]
+            },
+            _ => false
+        });
+    }
+
+    #[test]
+    fn or_binds_less_tightly_than_and_and_not() {
+        let parsed  = parse("let x = not a == b or c and d").unwrap();
+
+        assert!(match parsed[0] {
+            Script::Let(_, Expression::BinaryOp(ref or, ref operands)) => {
+                or.token == ScriptLexerToken::Or
+                    && match operands.0 { Expression::UnaryOp(_, ref operand) => operand.is_binary_op(), _ => false }
+                    && match operands.1 { Expression::BinaryOp(ref and, _) => and.token == ScriptLexerToken::And, _ => false }
+            },
+            _ => false
+        });
+    }
+
+    #[test]
+    fn operators_with_same_precedence_are_left_associative() {
+        let parsed  = parse("let x = 1 - 2 - 3").unwrap();
+
+        assert!(match parsed[0] {
+            Script::Let(_, Expression::BinaryOp(_, ref operands)) => match operands.0 {
+                Expression::BinaryOp(ref sub, _)    => sub.matched == "-",
+                _                                   => false
+            },
+            _ => false
+        });
+    }
+
+    #[test]
+    fn can_parse_arithmetic_command() {
+        let parsed  = parse("x + 1").unwrap();
+
+        assert!(match parsed[0] { Script::RunCommand(Expression::BinaryOp(_, _)) => true, _ => false });
+    }
+
+    #[test]
+    fn arithmetic_does_not_need_spaces_except_for_subtraction() {
+        let multiply    = parse("x*2").unwrap();
+        let add         = parse("x+1").unwrap();
+        let hyphenated  = parse("x-1").unwrap();
+
+        assert!(match multiply[0] {
+            Script::RunCommand(Expression::BinaryOp(ref op, ref operands)) => {
+                op.matched == "*" && match **operands { (Expression::Identifier(ref x), Expression::Number(ref num)) => x.matched == "x" && num.matched == "2", _ => false }
+            },
+            _ => false
+        });
+        assert!(match add[0] { Script::RunCommand(Expression::BinaryOp(ref op, _)) => op.matched == "+", _ => false });
+        assert!(match hyphenated[0] { Script::RunCommand(Expression::Identifier(ref name)) => name.matched == "x-1", _ => false });
+    }
+
+    #[test]
+    fn negative_number_is_literal_unless_it_follows_an_operand() {
+        let negative    = parse("let x = -5").unwrap();
+        let subtraction = parse("let x = 3 -5").unwrap();
+
+        assert!(match negative[0] { Script::Let(_, Expression::Number(ref num)) => num.matched == "-5", _ => false });
+        assert!(match subtraction[0] {
+            Script::Let(_, Expression::BinaryOp(ref sub, ref operands)) => {
+                sub.matched == "-" && match operands.1 { Expression::Number(ref num) => num.matched == "5", _ => false }
+            },
+            _ => false
+        });
+    }
+
+    #[test]
+    fn negative_number_after_string_or_constant_is_subtraction() {
+        let after_string    = parse("let x = \"a\" -5").unwrap();
+        let after_constant  = parse("let x = null -5").unwrap();
+        let after_tool      = parse("print -5").unwrap();
+
+        assert!(match after_string[0] { Script::Let(_, Expression::BinaryOp(ref sub, _)) => sub.matched == "-", _ => false });
+        assert!(match after_constant[0] { Script::Let(_, Expression::BinaryOp(ref sub, _)) => sub.matched == "-", _ => false });
+        assert!(match after_tool[0] { Script::RunCommand(Expression::Apply(_)) => true, _ => false });
+    }
+
     #[test]
     fn can_parse_let_statement() {
         let statement   = "let foo = bar";
@@ -786,6 +1140,19 @@ mod test {
         assert!(match cmd { &Script::RunCommand(Expression::Index(_)) => true, _ => false});
     }
 
+    #[test]
+    fn can_parse_array_slicing() {
+        let parsed  = parse("some-command[1:2]\nsome-command[:2]\nsome-command[1:]\nsome-command[:]").unwrap();
+        let bounds  = parsed.iter()
+            .map(|cmd| match cmd {
+                &Script::RunCommand(Expression::Slice(ref slice)) => Some((slice.1.is_some(), slice.2.is_some())),
+                _ => None
+            })
+            .collect::<Vec<_>>();
+
+        assert!(bounds == vec![ Some((true, true)), Some((false, true)), Some((true, false)), Some((false, false)) ]);
+    }
+
     #[test]
     fn can_parse_array_indexing_with_field_access() {
         let statement   = "some-command[0].foo";
@@ -1055,6 +1422,72 @@ mod test {
         assert!(match cmd { &Script::While(Expression::Identifier(_), _) => true, _ => false});
     }
 
+    #[test]
+    fn can_parse_block_ending_with_assignment() {
+        let statement   = "while foo {\nx = bar\n}";
+        let parsed      = parse(statement);
+
+        assert!(parsed.is_ok());
+
+        let result = parsed.unwrap();
+        assert!(result.len() == 1);
+
+        let ref cmd = result[0];
+        assert!(match cmd { &Script::While(_, ref block) => match **block { Script::Assign(_, _) => true, _ => false }, _ => false});
+    }
+
+    #[test]
+    fn can_parse_for_statement() {
+        let statement   = "for x in foo { bar }";
+        let parsed      = parse(statement);
+
+        assert!(parsed.is_ok());
+
+        let result = parsed.unwrap();
+        assert!(result.len() == 1);
+
+        let ref cmd = result[0];
+        assert!(match cmd { &Script::For(ref name, Expression::Identifier(_), _) => name.matched == "x", _ => false});
+    }
+
+    #[test]
+    fn for_requires_in() {
+        assert!(parse("for x foo { bar }").is_err());
+    }
+
+    #[test]
+    fn can_parse_break_statement() {
+        let parsed = parse("loop { break 42 }").unwrap();
+        assert!(match parsed[0] { Script::Loop(ref block) => match **block { Script::Break(Some(Expression::Number(_))) => true, _ => false }, _ => false });
+
+        let parsed = parse("loop { break }").unwrap();
+        assert!(match parsed[0] { Script::Loop(ref block) => match **block { Script::Break(None) => true, _ => false }, _ => false });
+    }
+
+    #[test]
+    fn can_parse_def_statement() {
+        let parsed = parse("def add_both(a, b) { add (a, b) }").unwrap();
+
+        assert!(parsed.len() == 1);
+        assert!(match parsed[0] {
+            Script::Def(ref name, ref arguments, _) => name.matched == "add_both" && arguments.iter().map(|arg| arg.matched.clone()).collect::<Vec<_>>() == vec![ "a", "b" ],
+            _                                       => false
+        });
+    }
+
+    #[test]
+    fn can_parse_def_without_arguments() {
+        let parsed = parse("def nothing() { foo }").unwrap();
+
+        assert!(match parsed[0] { Script::Def(_, ref arguments, _) => arguments.len() == 0, _ => false });
+    }
+
+    #[test]
+    fn def_requires_argument_list() {
+        assert!(parse("def foo { bar }").is_err());
+        assert!(parse("def foo(a b) { bar }").is_err());
+    }
+
     #[test]
     fn can_parse_loop_statement() {
         let statement   = "loop { bar }";
@@ -1068,4 +1501,79 @@ mod test {
         let ref cmd = result[0];
         assert!(match cmd { &Script::Loop(_) => true, _ => false});
     }
+
+    #[test]
+    fn parse_all_reports_every_error() {
+        let lexed   = lex("let = 1\nfoo\nlet x 2");
+        let parsed  = ParseScriptTool::parse_all(&lexed);
+
+        assert!(parsed.is_err());
+
+        let errors  = parsed.unwrap_err();
+        assert!(errors.len() == 2);
+        assert!(errors[0].position() == Some((4, 5)));
+        assert!(errors[1].position() == Some((18, 19)));
+    }
+
+    #[test]
+    fn parse_all_accepts_valid_script() {
+        let lexed   = lex("let x = 1\nfoo x");
+        let parsed  = ParseScriptTool::parse_all(&lexed);
+
+        assert!(parsed.map(|statements| statements.len()).ok() == Some(2));
+    }
+
+
+    #[test]
+    fn can_parse_array_with_trailing_comma() {
+        let parsed = parse("some-command([ 1, 2, ])");
+
+        assert!(parsed.is_ok());
+
+        let result = parsed.unwrap();
+        assert!(match applies_to(&result[0]) { Some((Expression::Identifier(_), Expression::Array(items))) => items.len() == 2, _ => false });
+    }
+
+    #[test]
+    fn can_parse_tuple_with_trailing_comma() {
+        let parsed = parse("some-command(1, 2,)");
+
+        assert!(parsed.is_ok());
+
+        let result = parsed.unwrap();
+        assert!(match applies_to(&result[0]) { Some((Expression::Identifier(_), Expression::Tuple(items))) => items.len() == 2, _ => false });
+    }
+
+    #[test]
+    fn can_parse_map_with_trailing_comma() {
+        let parsed = parse("some-command {\n    foo: bar,\n    baz: blarg,\n}");
+
+        assert!(parsed.is_ok());
+
+        let result = parsed.unwrap();
+        assert!(match applies_to(&result[0]) { Some((Expression::Identifier(_), Expression::Map(items))) => items.len() == 2, _ => false });
+    }
+
+    #[test]
+    fn bare_map_keys_are_names() {
+        let parsed = parse("some-command { foo: 1, (bar): 2, \"baz\": 3 }");
+
+        assert!(parsed.is_ok());
+
+        let result  = parsed.unwrap();
+        let items   = match applies_to(&result[0]) { Some((_, Expression::Map(items))) => items, _ => vec![] };
+
+        assert!(items.len() == 3);
+        assert!(match items[0].0 { MapKey::Name(ref name) => name.matched == "foo", _ => false });
+        assert!(match items[1].0 { MapKey::Expression(Expression::Identifier(ref name)) => name.matched == "bar", _ => false });
+        assert!(match items[2].0 { MapKey::Expression(Expression::String(_)) => true, _ => false });
+    }
+
+    #[test]
+    fn lone_comma_is_not_an_empty_collection() {
+        assert!(parse("some-command([,])").is_err());
+        assert!(parse("some-command(,)").is_err());
+        assert!(parse("some-command { , }").is_err());
+        assert!(parse("some-command([ 1,, 2 ])").is_err());
+    }
 }
@@ -0,0 +1,209 @@
+//!
+//! The run-isolated tool evaluates a script in an environment containing only a chosen set of tools
+//!
+
+use std::result::Result;
+use std::error::Error;
+use std::time::{Instant, Duration};
+
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+use super::run_script_timeout::*;
+use super::script_interpreter::*;
+
+/// The number of milliseconds an isolated script can run for if no other time is given
+pub const DEFAULT_ISOLATED_MILLIS: u64 = 10000;
+
+/// The number of statements and loop iterations an isolated script can run if no other limit is given
+pub const DEFAULT_ISOLATED_MAX_STEPS: usize = 1000000;
+
+/// How deeply the tools defined by an isolated script can call each other if no other limit is given
+pub const DEFAULT_ISOLATED_MAX_DEPTH: usize = 100;
+
+///
+/// Parameters that can be passed to the run-isolated tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct RunIsolatedParameters {
+    /// The source of the script to run
+    pub source: String,
+
+    /// The names of the tools that the script is allowed to use
+    pub tools: Vec<String>,
+
+    /// The number of milliseconds the script is allowed to run for (`DEFAULT_ISOLATED_MILLIS` if not set)
+    pub millis: Option<u64>,
+
+    /// The number of statements and loop iterations the script can run (`DEFAULT_ISOLATED_MAX_STEPS` if not set)
+    pub max_steps: Option<usize>,
+
+    /// How deeply tools defined by the script can call each other (`DEFAULT_ISOLATED_MAX_DEPTH` if not set)
+    pub max_depth: Option<usize>
+}
+
+impl RunIsolatedParameters {
+    pub fn new(source: &str, tools: Vec<&str>, millis: Option<u64>) -> RunIsolatedParameters {
+        RunIsolatedParameters {
+            source:     String::from(source),
+            tools:      tools.into_iter().map(|name| String::from(name)).collect(),
+            millis:     millis,
+            max_steps:  None,
+            max_depth:  None
+        }
+    }
+}
+
+///
+/// Tool that evaluates a script in a new environment containing only the tools it's allowed to use
+///
+/// The new environment is a snapshot of the allowed tools taken when the tool is called: any
+/// other tool (including `define-tool` and `list-tools`, unless they're in the list) is not
+/// available to the script or to the tools it calls.
+///
+/// The script is always limited, as it's assumed not to be trusted: it's stopped if it runs for
+/// longer than `millis` (in the same way as for `run-script-timeout`), runs more than `max_steps`
+/// statements and loop iterations, or if the tools it defines call each other more than
+/// `max_depth` deep. Each of these has a default if it's not supplied.
+///
+pub struct RunIsolatedTool {
+}
+
+impl RunIsolatedTool {
+    ///
+    /// Creates a new run-isolated tool
+    ///
+    pub fn new() -> RunIsolatedTool {
+        RunIsolatedTool { }
+    }
+
+    ///
+    /// Creates an environment containing only the specified tools from another environment
+    ///
+    pub fn isolated_environment(tools: &Vec<String>, environment: &Environment) -> Result<StaticEnvironment, Value> {
+        let mut allowed_tools = vec![];
+
+        for name in tools.iter() {
+            let tool = get_tool_or_error(environment, name)?;
+
+            allowed_tools.push((name.clone(), tool));
+        }
+
+        Ok(StaticEnvironment::from_toolset(BasicToolSet::from_named_tools(allowed_tools), environment))
+    }
+
+    ///
+    /// Runs a script in an environment containing only the specified tools
+    ///
+    pub fn run(parameters: RunIsolatedParameters, environment: &Environment) -> Result<Value, Value> {
+        let millis      = parameters.millis.unwrap_or(DEFAULT_ISOLATED_MILLIS);
+        let max_steps   = parameters.max_steps.unwrap_or(DEFAULT_ISOLATED_MAX_STEPS);
+        let max_depth   = parameters.max_depth.unwrap_or(DEFAULT_ISOLATED_MAX_DEPTH);
+
+        let limits      = ScriptLimits::new(Some(Instant::now() + Duration::from_millis(millis)), Some(max_steps), Some(max_depth));
+        let isolated    = RunIsolatedTool::isolated_environment(&parameters.tools, environment)?;
+
+        RunScriptTimeoutTool::run_with_limits(&parameters.source, limits, isolated)
+    }
+}
+
+impl Tool for RunIsolatedTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        match from_value::<RunIsolatedParameters>(input) {
+            Ok(parameters) => RunIsolatedTool::run(parameters, environment),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to run-isolated must be of the form { \"source\": <script>, \"tools\": [ <tool_name>, ... ], \"millis\": <number>, \"max_steps\": <number>, \"max_depth\": <number> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_environment() -> DynamicEnvironment {
+        let env = DynamicEnvironment::new();
+
+        assert!(define_pure_tool(&env, "add_one", |x: i32| x+1).is_ok());
+        assert!(define_pure_tool(&env, "secret", |_: Value| "secret").is_ok());
+
+        env
+    }
+
+    #[test]
+    fn allowed_tool_can_be_called() {
+        let env     = test_environment();
+        let result  = RunIsolatedTool::new().invoke_json(to_value(RunIsolatedParameters::new("add_one 1", vec![ "add_one" ], None)).unwrap(), &env);
+
+        assert!(result == Ok(json![[ 2 ]]));
+    }
+
+    #[test]
+    fn disallowed_tool_cannot_be_called() {
+        let env     = test_environment();
+        let result  = RunIsolatedTool::new().invoke_json(to_value(RunIsolatedParameters::new("secret 1", vec![ "add_one" ], None)).unwrap(), &env);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cannot_define_tools_without_define_tool() {
+        let env     = test_environment();
+        let result  = RunIsolatedTool::new().invoke_json(to_value(RunIsolatedParameters::new("define-tool { \"source_name\": \"secret\" }", vec![ "add_one" ], None)).unwrap(), &env);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn isolated_script_stops_at_deadline() {
+        let env = test_environment();
+        assert!(define_pure_tool(&env, "tick", |_: Value| 0).is_ok());
+
+        let result = RunIsolatedTool::new().invoke_json(to_value(RunIsolatedParameters::new("loop {\ntick\n}", vec![ "tick" ], Some(50))).unwrap(), &env);
+
+        assert!(result.unwrap_err()["error"] == json!["DeadlineExceeded"]);
+    }
+
+    #[test]
+    fn isolated_script_stops_at_step_limit() {
+        let env = test_environment();
+        assert!(define_pure_tool(&env, "tick", |_: Value| 0).is_ok());
+
+        let mut parameters      = RunIsolatedParameters::new("loop {\ntick\n}", vec![ "tick" ], None);
+        parameters.max_steps    = Some(100);
+
+        let result = RunIsolatedTool::new().invoke_json(to_value(parameters).unwrap(), &env);
+
+        assert!(result.unwrap_err()["error"] == json!["StepLimitExceeded"]);
+    }
+
+    #[test]
+    fn isolated_script_is_limited_by_default() {
+        let env     = test_environment();
+        let start   = Instant::now();
+        let result  = RunIsolatedTool::new().invoke_json(to_value(RunIsolatedParameters::new("loop {\n}", vec![], None)).unwrap(), &env);
+
+        assert!(result.unwrap_err()["error"] == json!["StepLimitExceeded"]);
+        assert!(start.elapsed() < Duration::from_millis(DEFAULT_ISOLATED_MILLIS + 5000));
+    }
+
+    #[test]
+    fn unimplemented_statement_is_an_error() {
+        let env     = test_environment();
+        let result  = RunIsolatedTool::new().invoke_json(to_value(RunIsolatedParameters::new("if true {\nadd_one 1\n}", vec![ "add_one" ], None)).unwrap(), &env);
+
+        assert!(result.unwrap_err()["error"] == json!["StatementNotImplemented"]);
+    }
+
+    #[test]
+    fn missing_allowed_tool_is_an_error() {
+        let env     = test_environment();
+        let result  = RunIsolatedTool::new().invoke_json(to_value(RunIsolatedParameters::new("add_one 1", vec![ "add_one", "missing" ], None)).unwrap(), &env);
+
+        assert!(result.unwrap_err()["tool_name"] == json!["missing"]);
+    }
+}
@@ -0,0 +1,266 @@
+//!
+//! The run-script-timeout tool evaluates a script, stopping it if it takes too long
+//!
+
+use std::thread;
+use std::result::Result;
+use std::error::Error;
+use std::sync::*;
+use std::sync::mpsc::*;
+use std::time::{Instant, Duration};
+
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+use super::script::*;
+use super::bound_script::*;
+use super::lex_script_tool::*;
+use super::parse_script_tool::*;
+use super::binding_environment::*;
+use super::bind_statement::*;
+use super::evaluate_statement::*;
+use super::script_interpreter::*;
+
+///
+/// Parameters that can be passed to the run-script-timeout tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct RunScriptTimeoutParameters {
+    /// The source of the script to run
+    pub source: String,
+
+    /// The number of milliseconds the script is allowed to run for
+    pub millis: u64
+}
+
+impl RunScriptTimeoutParameters {
+    pub fn new(source: &str, millis: u64) -> RunScriptTimeoutParameters {
+        RunScriptTimeoutParameters { source: String::from(source), millis: millis }
+    }
+}
+
+///
+/// Tool that evaluates a script with a deadline
+///
+/// The script is run on a worker thread. If it's still running when the deadline passes, the
+/// result is an error with the `DeadlineExceeded` code, even if the script is in the middle of
+/// calling a tool. Rust provides no way to stop a thread, so the worker carries on until the
+/// tool it's calling returns (it stops at the next statement after that). Anything the script
+/// did before it was stopped (such as writing output) is not undone, and a tool that is still
+/// running can have further side effects after the error is returned.
+///
+/// If the worker panics (for instance, because a tool it calls panics), the result is an error
+/// with the `EvaluationPanicked` code.
+///
+/// As the worker can outlive the call, the script can't use the environment the tool is called
+/// in: it's run in a copy of the tools listed by `list-tools` when the tool was called. Tools
+/// that the script defines are only defined in this copy.
+///
+pub struct RunScriptTimeoutTool {
+}
+
+impl RunScriptTimeoutTool {
+    ///
+    /// Creates a new run-script-timeout tool
+    ///
+    pub fn new() -> RunScriptTimeoutTool {
+        RunScriptTimeoutTool { }
+    }
+
+    ///
+    /// Creates a copy of the tools in an environment that can be moved to another thread
+    ///
+    /// The tools are the ones listed by the environment's `list-tools` tool: an environment
+    /// without one produces an empty copy.
+    ///
+    pub fn snapshot_environment(environment: &Environment) -> DynamicEnvironment {
+        let snapshot = DynamicEnvironment::new();
+
+        for (name, tool) in environment.tools_iter() {
+            // The snapshot provides its own versions of the tools that manage the environment
+            if !snapshot.has_tool(&name) {
+                snapshot.define(&name, tool);
+            }
+        }
+
+        snapshot
+    }
+
+    ///
+    /// Parses a script and binds it to the tools in an environment
+    ///
+    pub fn bind_source(source: &str, environment: &Environment) -> Result<BoundScript, Value> {
+        let lexed       = create_lex_script_tool().lex(source);
+        let parsed      = ParseScriptTool::parse(&lexed).map_err(|parse_error| to_value(parse_error).unwrap())?;
+
+        let mut binding = BindingEnvironment::from_environment(environment);
+        bind_statement(&Script::Sequence(parsed), &mut *binding)
+    }
+
+    ///
+    /// Runs a script, stopping it if it's still running after a certain time
+    ///
+    pub fn run(source: &str, timeout: Duration, environment: &Environment) -> Result<Value, Value> {
+        // The deadline starts when the tool is called, so it includes the time taken to parse and bind the script
+        let deadline    = Instant::now() + timeout;
+        let bound       = RunScriptTimeoutTool::bind_source(source, environment)?;
+
+        RunScriptTimeoutTool::run_bound_with_deadline(bound, Some(deadline), RunScriptTimeoutTool::snapshot_environment(environment))
+    }
+
+    ///
+    /// Runs a script in an environment, stopping it if it's still running at the deadline (if there is one)
+    ///
+    pub fn run_with_deadline<TEnvironment: 'static+Environment>(source: &str, deadline: Option<Instant>, environment: TEnvironment) -> Result<Value, Value> {
+        RunScriptTimeoutTool::run_with_limits(source, ScriptLimits::new(deadline, None, None), environment)
+    }
+
+    ///
+    /// Runs a script in an environment, stopping it if it exceeds its limits
+    ///
+    pub fn run_with_limits<TEnvironment: 'static+Environment>(source: &str, limits: ScriptLimits, environment: TEnvironment) -> Result<Value, Value> {
+        let bound = RunScriptTimeoutTool::bind_source(source, &environment)?;
+
+        RunScriptTimeoutTool::run_bound_with_limits(bound, limits, environment)
+    }
+
+    ///
+    /// Runs a bound script, on a worker thread if there's a deadline
+    ///
+    pub fn run_bound_with_deadline<TEnvironment: 'static+Environment>(bound: BoundScript, deadline: Option<Instant>, environment: TEnvironment) -> Result<Value, Value> {
+        RunScriptTimeoutTool::run_bound_with_limits(bound, ScriptLimits::new(deadline, None, None), environment)
+    }
+
+    ///
+    /// Runs a bound script with a set of limits, on a worker thread if there's a deadline
+    ///
+    pub fn run_bound_with_limits<TEnvironment: 'static+Environment>(bound: BoundScript, limits: ScriptLimits, environment: TEnvironment) -> Result<Value, Value> {
+        let deadline                    = limits.deadline();
+        let mut execution_environment   = ScriptExecutionEnvironment::new();
+        execution_environment.set_limits(Arc::new(limits));
+
+        let deadline = match deadline {
+            Some(deadline)  => deadline,
+            None            => return evaluate_statement(&bound, &environment, &mut execution_environment)
+        };
+
+        // The worker also checks the deadline, so a script that's abandoned stops at the next statement
+        let (send_result, receive_result) = channel();
+
+        thread::spawn(move || {
+            // The receiver will have gone away if the deadline has passed
+            send_result.send(evaluate_statement(&bound, &environment, &mut execution_environment)).ok();
+        });
+
+        // Wait for the script to finish
+        let now = Instant::now();
+        let remaining = if deadline > now { deadline - now } else { Duration::from_millis(0) };
+
+        // The sender is dropped without sending anything if the worker panics
+        match receive_result.recv_timeout(remaining) {
+            Ok(result)                              => result,
+            Err(RecvTimeoutError::Timeout)          => Err(json![{ "error": ScriptEvaluationError::DeadlineExceeded }]),
+            Err(RecvTimeoutError::Disconnected)     => Err(json![{ "error": ScriptEvaluationError::EvaluationPanicked }])
+        }
+    }
+}
+
+impl Tool for RunScriptTimeoutTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<RunScriptTimeoutParameters>(input);
+
+        match parameters {
+            Ok(RunScriptTimeoutParameters { source, millis }) => RunScriptTimeoutTool::run(&source, Duration::from_millis(millis), environment),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to run-script-timeout must be of the form { \"source\": <script>, \"millis\": <number> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn script_finishing_in_time_returns_result() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "add_one", |x: i32| x+1).is_ok());
+
+        let result = RunScriptTimeoutTool::new().invoke_json(to_value(RunScriptTimeoutParameters::new("add_one 1", 1000)).unwrap(), &env);
+
+        assert!(result == Ok(json![[ 2 ]]));
+    }
+
+    #[test]
+    fn slow_tool_stops_at_deadline() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "slow", |_: Value| { thread::sleep(Duration::from_millis(2000)); 0 }).is_ok());
+
+        let start   = Instant::now();
+        let result  = RunScriptTimeoutTool::new().invoke_json(to_value(RunScriptTimeoutParameters::new("slow", 50)).unwrap(), &env);
+
+        assert!(result.unwrap_err()["error"] == json!["DeadlineExceeded"]);
+        assert!(start.elapsed() < Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn slow_tool_in_loop_stops_at_deadline() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "slow", |_: Value| { thread::sleep(Duration::from_millis(2000)); 0 }).is_ok());
+
+        let start   = Instant::now();
+        let result  = RunScriptTimeoutTool::new().invoke_json(to_value(RunScriptTimeoutParameters::new("loop {\nslow\n}", 50)).unwrap(), &env);
+
+        assert!(result.unwrap_err()["error"] == json!["DeadlineExceeded"]);
+        assert!(start.elapsed() < Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn script_can_use_tools_that_call_other_tools() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "add_one", |x: i32| x+1).is_ok());
+
+        env.define("eval-add-one", Box::new(make_dynamic_tool(|x: Value, env: &Environment| env.get_json_tool("add_one").unwrap().invoke_json(x, env))));
+
+        let result = RunScriptTimeoutTool::new().invoke_json(to_value(RunScriptTimeoutParameters::new("eval-add-one 1", 1000)).unwrap(), &env);
+        assert!(result == Ok(json![[ 2 ]]));
+    }
+
+    #[test]
+    fn infinite_loop_stops_at_deadline() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "tick", |_: Value| 0).is_ok());
+
+        let start   = Instant::now();
+        let result  = RunScriptTimeoutTool::new().invoke_json(to_value(RunScriptTimeoutParameters::new("loop {\ntick\n}", 50)).unwrap(), &env);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err()["error"] == json!["DeadlineExceeded"]);
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert!(start.elapsed() < Duration::from_millis(5000));
+    }
+
+    #[test]
+    fn recursion_stops_at_limit() {
+        let env     = DynamicEnvironment::new();
+        let result  = RunScriptTimeoutTool::run_with_limits("def forever(x) {\nforever x\n}\nforever 1", ScriptLimits::new(None, None, Some(10)), env);
+
+        assert!(result.unwrap_err()["error"] == json!["RecursionLimitExceeded"]);
+    }
+
+    #[test]
+    fn panicking_tool_is_not_a_timeout() {
+        let env = DynamicEnvironment::new();
+        assert!(define_pure_tool(&env, "crash", |_: Value| -> i32 { panic!("Tool crashed") }).is_ok());
+
+        let start   = Instant::now();
+        let result  = RunScriptTimeoutTool::new().invoke_json(to_value(RunScriptTimeoutParameters::new("crash", 5000)).unwrap(), &env);
+
+        assert!(result.unwrap_err()["error"] == json!["EvaluationPanicked"]);
+        assert!(start.elapsed() < Duration::from_millis(5000));
+    }
+}
@@ -0,0 +1,96 @@
+//!
+//! The run-script-with-bindings tool evaluates a script and reports the variables it defined
+//!
+
+use std::result::Result;
+use std::error::Error;
+
+use serde_json::*;
+use gossyp_base::*;
+
+use super::script::*;
+use super::evaluate::*;
+use super::script_interpreter::*;
+
+///
+/// Result of the run-script-with-bindings tool
+///
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct RunScriptWithBindingsResult {
+    /// The result of evaluating the script
+    pub result: Value,
+
+    /// The final values of the variables defined by the script
+    pub bindings: Map<String, Value>
+}
+
+///
+/// Tool that evaluates a script and returns the values of the variables it defined along with its result
+///
+pub struct RunScriptWithBindingsTool {
+}
+
+impl RunScriptWithBindingsTool {
+    ///
+    /// Creates a new run-script-with-bindings tool
+    ///
+    pub fn new() -> RunScriptWithBindingsTool {
+        RunScriptWithBindingsTool { }
+    }
+
+    ///
+    /// Runs a script, returning its result and the variables it defined
+    ///
+    pub fn run(script: Vec<Script>, environment: &Environment) -> Result<RunScriptWithBindingsResult, Value> {
+        let (result, state) = gossyp_eval_with_state(script, ScriptExecutionEnvironment::new(), environment);
+
+        Ok(RunScriptWithBindingsResult {
+            result:     result?,
+            bindings:   state.get_named_variables()
+        })
+    }
+}
+
+impl Tool for RunScriptWithBindingsTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        match from_value::<Vec<Script>>(input) {
+            Ok(script)          => Ok(to_value(RunScriptWithBindingsTool::run(script, environment)?).unwrap()),
+            Err(script_error)   => Err(json![{
+                "error":        "JSON input decode failed",
+                "description":  script_error.description(),
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+    use super::super::lex_script_tool::*;
+    use super::super::parse_script_tool::*;
+
+    #[test]
+    fn reports_let_and_var_bindings() {
+        let env     = DynamicEnvironment::new();
+        let tool    = RunScriptWithBindingsTool::new();
+        let script  = ParseScriptTool::parse(&create_lex_script_tool().lex("let a = 1\nvar b = 2")).unwrap();
+
+        let result  = tool.invoke_json(to_value(script).unwrap(), &env);
+
+        assert!(result == Ok(json![{
+            "result":   [ 1, 2 ],
+            "bindings": { "a": 1, "b": 2 }
+        }]));
+    }
+
+    #[test]
+    fn bindings_have_final_values() {
+        let env     = DynamicEnvironment::new();
+        let script  = ParseScriptTool::parse(&create_lex_script_tool().lex("var b = 2\nb = 3")).unwrap();
+
+        let result  = RunScriptWithBindingsTool::run(script, &env).unwrap();
+
+        assert!(result.bindings.get("b") == Some(&json![ 3 ]));
+    }
+}
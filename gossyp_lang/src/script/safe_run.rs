@@ -0,0 +1,210 @@
+//!
+//! The safe-run tool checks that a script can run in the current environment before running it
+//!
+
+use std::result::Result;
+use std::error::Error;
+
+use serde_json::*;
+use gossyp_base::*;
+
+use super::lex_script_tool::*;
+use super::parse_script_tool::*;
+use super::script_interpreter::*;
+use super::script_dependencies::*;
+use super::version::*;
+use super::evaluate::*;
+
+///
+/// Parameters that can be passed to the safe-run tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct SafeRunParameters {
+    /// The source of the script to run
+    pub source: String,
+
+    /// The oldest version of the scripting language that the script can run with, in the form 'x.y.z'
+    pub min_version: Option<String>
+}
+
+impl SafeRunParameters {
+    pub fn new(source: &str, min_version: Option<&str>) -> SafeRunParameters {
+        SafeRunParameters { source: String::from(source), min_version: min_version.map(|version| String::from(version)) }
+    }
+}
+
+///
+/// Tool that runs a script only if the language version is new enough and every tool it uses is defined
+///
+/// If either check fails, the script isn't run at all and the result is an error describing
+/// every check that failed: `language_version` and `min_version` are included if the version is
+/// too old, and `missing_tools` lists the tools that aren't in the environment.
+///
+/// The script must also be able to run with `min_version`: if it uses a feature that was added
+/// to the language after that version, it's refused with the version it needs in
+/// `required_version`.
+///
+pub struct SafeRunTool {
+}
+
+impl SafeRunTool {
+    ///
+    /// Creates a new safe-run tool
+    ///
+    pub fn new() -> SafeRunTool {
+        SafeRunTool { }
+    }
+
+    ///
+    /// Parses a version of the form 'x.y.z' into its components
+    ///
+    pub fn parse_version(version: &str) -> Result<Vec<u64>, Value> {
+        version.split('.')
+            .map(|component| component.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| json![{
+                "error":    "Versions must be of the form 'x.y.z'",
+                "version":  version
+            }])
+    }
+
+    ///
+    /// Returns true if a version is at least as new as a minimum version
+    ///
+    /// Missing components count as 0, so '1.2' is the same as '1.2.0'.
+    ///
+    pub fn version_at_least(version: &str, min_version: &str) -> Result<bool, Value> {
+        let mut version     = SafeRunTool::parse_version(version)?;
+        let mut min_version = SafeRunTool::parse_version(min_version)?;
+
+        let len = version.len().max(min_version.len());
+        version.resize(len, 0);
+        min_version.resize(len, 0);
+
+        Ok(version >= min_version)
+    }
+
+    ///
+    /// Checks the preconditions for a script and runs it if they pass
+    ///
+    pub fn run(parameters: &SafeRunParameters, environment: &Environment) -> Result<Value, Value> {
+        let mut failures = Map::new();
+
+        // The language must be new enough
+        if let Some(ref min_version) = parameters.min_version {
+            if !SafeRunTool::version_at_least(LANGUAGE_VERSION, min_version)? {
+                failures.insert(String::from("min_version"), json![ min_version ]);
+                failures.insert(String::from("language_version"), json![ LANGUAGE_VERSION ]);
+            }
+        }
+
+        // Every tool used by the script must be defined
+        let lexed   = create_lex_script_tool().lex(&parameters.source);
+        let parsed  = ParseScriptTool::parse(&lexed).map_err(|parse_error| to_value(parse_error).unwrap())?;
+        let missing = CanRunScriptTool::missing_tools(&parsed, environment);
+
+        // The script must not use features that are newer than the version it claims to need
+        if let (&Some(ref min_version), Some(required)) = (&parameters.min_version, required_version(&parsed)) {
+            if !SafeRunTool::version_at_least(min_version, required)? {
+                failures.insert(String::from("min_version"), json![ min_version ]);
+                failures.insert(String::from("required_version"), json![ required ]);
+            }
+        }
+
+        if missing.len() > 0 {
+            failures.insert(String::from("missing_tools"), json![ missing ]);
+        }
+
+        if failures.len() > 0 {
+            failures.insert(String::from("error"), json![ "Script preconditions failed" ]);
+            return Err(Value::Object(failures));
+        }
+
+        // Preconditions passed: run the script
+        gossyp_eval_with_state(parsed, ScriptExecutionEnvironment::new(), environment).0
+    }
+}
+
+impl Tool for SafeRunTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        match from_value::<SafeRunParameters>(input) {
+            Ok(parameters) => SafeRunTool::run(&parameters, environment),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to safe-run must be of the form { \"source\": <script>, \"min_version\": \"x.y.z\" }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    fn test_environment() -> DynamicEnvironment {
+        let env = DynamicEnvironment::new();
+        env.define("add_one", Box::new(make_pure_tool(|x: i32| x+1)));
+
+        env
+    }
+
+    #[test]
+    fn runs_script_when_preconditions_pass() {
+        let env     = test_environment();
+        let tool    = TypedTool::<SafeRunParameters, Value>::from(Box::new(SafeRunTool::new()));
+
+        assert!(tool.invoke(SafeRunParameters::new("add_one 1", Some("0.1")), &env) == Ok(json![[ 2 ]]));
+        assert!(tool.invoke(SafeRunParameters::new("add_one 1", None), &env) == Ok(json![[ 2 ]]));
+    }
+
+    #[test]
+    fn version_too_old_is_reported() {
+        let env     = test_environment();
+        let tool    = TypedTool::<SafeRunParameters, Value>::from(Box::new(SafeRunTool::new()));
+
+        assert!(tool.invoke(SafeRunParameters::new("add_one 1", Some("999.0.0")), &env) == Err(json![{
+            "error":            "Script preconditions failed",
+            "min_version":      "999.0.0",
+            "language_version": LANGUAGE_VERSION
+        }]));
+    }
+
+    #[test]
+    fn script_needing_newer_version_is_refused() {
+        let env     = test_environment();
+        let tool    = TypedTool::<SafeRunParameters, Value>::from(Box::new(SafeRunTool::new()));
+
+        let result  = tool.invoke(SafeRunParameters::new("def next(x) {\nadd_one x\n}\nnext 1", Some("0.2.0")), &env);
+
+        assert!(result == Err(json![{
+            "error":            "Script preconditions failed",
+            "min_version":      "0.2.0",
+            "required_version": "0.7.0"
+        }]));
+        assert!(tool.invoke(SafeRunParameters::new("def next(x) {\nadd_one x\n}\nnext 1", Some("0.7.0")), &env) == Ok(json![[ null, 2 ]]));
+    }
+
+    #[test]
+    fn missing_dependency_is_reported_without_running_script() {
+        let env     = test_environment();
+        let tool    = TypedTool::<SafeRunParameters, Value>::from(Box::new(SafeRunTool::new()));
+
+        // Nothing is run, even though the first line could be
+        let result  = tool.invoke(SafeRunParameters::new("add_one 1\nmissing-tool 2", None), &env);
+
+        assert!(result == Err(json![{
+            "error":            "Script preconditions failed",
+            "missing_tools":    [ "missing-tool" ]
+        }]));
+    }
+
+    #[test]
+    fn versions_compare_by_component() {
+        assert!(SafeRunTool::version_at_least("0.10.0", "0.9.1") == Ok(true));
+        assert!(SafeRunTool::version_at_least("1.2", "1.2.0") == Ok(true));
+        assert!(SafeRunTool::version_at_least("1.2.0", "1.2.1") == Ok(false));
+        assert!(SafeRunTool::version_at_least("1.2.0", "one").is_err());
+    }
+}
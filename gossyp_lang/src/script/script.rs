@@ -13,6 +13,8 @@ pub enum ScriptLexerToken {
     String,
     Number,
     HexNumber,
+    BinaryNumber,
+    OctalNumber,
 
     Newline,
     Whitespace,
@@ -29,6 +31,13 @@ pub enum ScriptLexerToken {
     For,
     In,
     Def,
+    Break,
+    And,
+    Or,
+    Not,
+    True,
+    False,
+    Null,
 
     Symbol(String)
 }
@@ -81,6 +90,46 @@ impl ScriptToken {
         ScriptToken { token: ScriptLexerToken::Number, start: 0, end: num.len() as i32, matched: String::from(num) }
     }
 
+    ///
+    /// True if this token is a number literal (in any base)
+    ///
+    pub fn is_number(&self) -> bool {
+        match self.token {
+            ScriptLexerToken::Number        |
+            ScriptLexerToken::HexNumber     |
+            ScriptLexerToken::BinaryNumber  |
+            ScriptLexerToken::OctalNumber   => true,
+            _                               => false
+        }
+    }
+
+    ///
+    /// Returns true if this token is a string or a constant (which can't be applied to a value the way a tool can)
+    ///
+    pub fn is_operand_literal(&self) -> bool {
+        match self.token {
+            ScriptLexerToken::String        |
+            ScriptLexerToken::True          |
+            ScriptLexerToken::False         |
+            ScriptLexerToken::Null          => true,
+            _                               => false
+        }
+    }
+
+    ///
+    /// If this token is a negative number, splits it into a '-' symbol and the number without its sign
+    ///
+    pub fn split_negative_number(&self) -> Option<(ScriptToken, ScriptToken)> {
+        if self.is_number() && self.matched.starts_with('-') {
+            let minus   = ScriptToken::new(ScriptLexerToken::symbol("-"), self.start, self.start+1, String::from("-"));
+            let number  = ScriptToken::new(self.token.clone(), self.start+1, self.end, String::from(&self.matched[1..]));
+
+            Some((minus, number))
+        } else {
+            None
+        }
+    }
+
     ///
     /// Creates a new script token from a generic `LexerMatch` object
     ///
@@ -98,13 +147,22 @@ impl ScriptToken {
             "for"           => ScriptLexerToken::For,
             "in"            => ScriptLexerToken::In,
             "def"           => ScriptLexerToken::Def,
-
-            "." | "," | ":" | "+" | "-" | "*" | "/" | "|" | "&" | "=" | "==" | "!=" | ">" | "<" | "<=" | ">=" | "!" | "?" | "||" | "&&" | "(" | ")" | "{" | "}" | "[" | "]"
+            "break"         => ScriptLexerToken::Break,
+            "and"           => ScriptLexerToken::And,
+            "or"            => ScriptLexerToken::Or,
+            "not"           => ScriptLexerToken::Not,
+            "true"          => ScriptLexerToken::True,
+            "false"         => ScriptLexerToken::False,
+            "null"          => ScriptLexerToken::Null,
+
+            "." | "," | ":" | "+" | "-" | "*" | "/" | "%" | "|" | "&" | "=" | "==" | "!=" | ">" | "<" | "<=" | ">=" | "!" | "?" | "||" | "&&" | "(" | ")" | "{" | "}" | "[" | "]"
                             => ScriptLexerToken::Symbol(lexer_match.token.clone()),
             
             "String"        => ScriptLexerToken::String,
             "Number"        => ScriptLexerToken::Number,
             "HexNumber"     => ScriptLexerToken::HexNumber,
+            "BinaryNumber"  => ScriptLexerToken::BinaryNumber,
+            "OctalNumber"   => ScriptLexerToken::OctalNumber,
             "Identifier"    => ScriptLexerToken::Identifier,
             "Newline"       => ScriptLexerToken::Newline,
             "Whitespace"    => ScriptLexerToken::Whitespace,
@@ -117,6 +175,46 @@ impl ScriptToken {
     }
 }
 
+///
+/// Splits negative numbers that follow an operand into a subtraction
+///
+/// The lexer reads `3 -4` as two numbers, but a number can't follow a number, a string, a
+/// constant, a closing bracket or a closing parenthesis, so these are really `3 - 4`. A negative
+/// number anywhere else (such as `let x = -4` or `print -4`) is left alone.
+///
+/// Identifiers aren't operands here: an identifier might be a tool, so `print -4` passes `-4` to
+/// `print`. To subtract a number from a variable, put a space after the minus: `x - 4`. Hyphens
+/// can appear in identifiers, so `x-4` is a single name rather than a subtraction. Other operators
+/// don't need spaces: `x+4` and `x*4` are arithmetic.
+///
+pub fn split_subtractions(tokens: Vec<ScriptToken>) -> Vec<ScriptToken> {
+    let mut result: Vec<ScriptToken> = vec![];
+
+    for token in tokens {
+        let follows_operand = {
+            let last_syntax = result.iter().rev()
+                .filter(|last| last.token != ScriptLexerToken::Whitespace && last.token != ScriptLexerToken::Comment)
+                .nth(0);
+
+            match last_syntax {
+                Some(last)  => last.is_number() || last.is_operand_literal() || last.token == ScriptLexerToken::symbol(")") || last.token == ScriptLexerToken::symbol("]"),
+                None        => false
+            }
+        };
+
+        match token.split_negative_number() {
+            Some((minus, number)) if follows_operand => {
+                result.push(minus);
+                result.push(number);
+            },
+
+            _ => result.push(token)
+        }
+    }
+
+    result
+}
+
 ///
 /// Representation of a parsed script
 ///
@@ -146,11 +244,17 @@ pub enum Script {
     /// while expr { stuff }
     While(Expression, Box<Script>),
 
+    /// for a in expr { stuff }
+    For(ScriptToken, Expression, Box<Script>),
+
+    /// break (expr)
+    Break(Option<Expression>),
+
     /// using expr { stuff }
     Using(Expression, Box<Script>),
 
-    /// def tool pattern { stuff }
-    Def(ScriptToken, Expression, Box<Script>)
+    /// def tool(arguments) { stuff }
+    Def(ScriptToken, Vec<ScriptToken>, Box<Script>)
 }
 
 ///
@@ -167,6 +271,9 @@ pub enum Expression {
     /// 12.3
     Number(ScriptToken),
 
+    /// true, false or null (the token is the keyword)
+    Constant(ScriptToken),
+
     /// [ foo, bar, baz ]
     Array(Vec<Expression>),
 
@@ -174,7 +281,7 @@ pub enum Expression {
     Tuple(Vec<Expression>),
 
     /// { a: b, c: d }
-    Map(Vec<(Expression, Expression)>),
+    Map(Vec<(MapKey, Expression)>),
 
     // -- Evaluatable expressions
 
@@ -184,11 +291,32 @@ pub enum Expression {
     /// a[b]
     Index(Box<(Expression, Expression)>),
 
+    /// a[b:c] (either bound can be left out)
+    Slice(Box<(Expression, Option<Expression>, Option<Expression>)>),
+
     /// a.b
     FieldAccess(Box<(Expression, Expression)>),
 
     /// a (parameters)
-    Apply(Box<(Expression, Expression)>)
+    Apply(Box<(Expression, Expression)>),
+
+    /// a + b (the token is the operator)
+    BinaryOp(ScriptToken, Box<(Expression, Expression)>),
+
+    /// not a (the token is the operator)
+    UnaryOp(ScriptToken, Box<Expression>)
+}
+
+///
+/// The key of an entry in a map expression
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum MapKey {
+    /// A bare identifier, which is used as a literal string (`{ foo: 1 }`)
+    Name(ScriptToken),
+
+    /// Any other expression, which is evaluated to get the key (`{ "foo": 1 }` or `{ (foo): 1 }`)
+    Expression(Expression)
 }
 
 impl Expression {
@@ -222,4 +350,14 @@ impl Expression {
             _                       => false
         }
     }
+
+    ///
+    /// True if this is a BinaryOp expression
+    ///
+    pub fn is_binary_op(&self) -> bool {
+        match self {
+            &Expression::BinaryOp(_, _) => true,
+            _                           => false
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,295 @@
+//!
+//! The script-dependencies tool finds the names of the tools that a script uses
+//!
+
+use std::result::Result;
+use std::error::Error;
+use std::collections::BTreeSet;
+
+use serde_json::*;
+use gossyp_base::*;
+
+use super::script::*;
+use super::lex_script_tool::*;
+use super::parse_script_tool::*;
+
+///
+/// Tool that reports the names of the tools that are invoked by a script
+///
+/// Identifiers in command position or that have parameters applied to them are counted as tools.
+/// So are any other identifiers in expressions, as evaluating a tool name calls the tool. Names
+/// of variables declared by the script and of tools it defines with `def` are excluded. The result
+/// is a sorted array of distinct names.
+///
+pub struct ScriptDependenciesTool {
+}
+
+///
+/// Walks a script, collecting the names of the tools it uses
+///
+struct DependencyCollector {
+    /// Names used in tool position
+    tools: BTreeSet<String>,
+
+    /// Names declared as variables
+    variables: BTreeSet<String>,
+
+    /// Names of tools defined by the script
+    definitions: BTreeSet<String>
+}
+
+impl DependencyCollector {
+    fn new() -> DependencyCollector {
+        DependencyCollector { tools: BTreeSet::new(), variables: BTreeSet::new(), definitions: BTreeSet::new() }
+    }
+
+    ///
+    /// Adds the tools used by an expression
+    ///
+    fn add_expression(&mut self, expr: &Expression) {
+        match expr {
+            &Expression::String(_)                  |
+            &Expression::Number(_)                  |
+            &Expression::Constant(_)                => { },
+            &Expression::Identifier(ref name)       => { self.tools.insert(name.matched.clone()); },
+
+            &Expression::Array(ref items)           |
+            &Expression::Tuple(ref items)           => { for item in items.iter() { self.add_expression(item); } },
+            &Expression::Map(ref items)             => {
+                for &(ref key, ref value) in items.iter() {
+                    // Names used as keys are literal strings rather than tools
+                    match key {
+                        &MapKey::Name(_)                => { },
+                        &MapKey::Expression(ref key)    => self.add_expression(key)
+                    }
+
+                    self.add_expression(value);
+                }
+            },
+
+            &Expression::Index(ref pair)            |
+            &Expression::Apply(ref pair)            |
+            &Expression::BinaryOp(_, ref pair)      => {
+                let (ref lhs, ref rhs) = **pair;
+                self.add_expression(lhs);
+                self.add_expression(rhs);
+            },
+
+            &Expression::UnaryOp(_, ref operand)    => self.add_expression(operand),
+
+            &Expression::Slice(ref slice)           => {
+                let (ref lhs, ref start, ref end) = **slice;
+                self.add_expression(lhs);
+                if let &Some(ref start) = start { self.add_expression(start); }
+                if let &Some(ref end) = end { self.add_expression(end); }
+            },
+
+            &Expression::FieldAccess(ref pair)      => {
+                // The right-hand side is a field name rather than a tool
+                let (ref lhs, _) = **pair;
+                self.add_expression(lhs);
+            }
+        }
+    }
+
+    ///
+    /// Adds the tools used by a statement
+    ///
+    fn add_script(&mut self, script: &Script) {
+        match script {
+            &Script::RunCommand(ref expr)                       => self.add_expression(expr),
+            &Script::Sequence(ref statements)                   => { for statement in statements.iter() { self.add_script(statement); } },
+
+            &Script::Let(ref name, ref expr)                    |
+            &Script::Var(ref name, ref expr)                    => {
+                self.variables.insert(name.matched.clone());
+                self.add_expression(expr);
+            },
+            &Script::Assign(_, ref expr)                        => self.add_expression(expr),
+            &Script::For(ref name, ref expr, ref block)         => {
+                self.variables.insert(name.matched.clone());
+                self.add_expression(expr);
+                self.add_script(block);
+            },
+
+            &Script::If(ref expr, ref then_block, ref else_block) => {
+                self.add_expression(expr);
+                self.add_script(then_block);
+                if let &Some(ref else_block) = else_block {
+                    self.add_script(else_block);
+                }
+            },
+            &Script::Loop(ref block)                            => self.add_script(block),
+            &Script::Break(ref expr)                            => { if let &Some(ref expr) = expr { self.add_expression(expr); } },
+            &Script::While(ref expr, ref block)                 |
+            &Script::Using(ref expr, ref block)                 => {
+                self.add_expression(expr);
+                self.add_script(block);
+            },
+            &Script::Def(ref name, ref arguments, ref block)    => {
+                self.definitions.insert(name.matched.clone());
+                for argument in arguments.iter() {
+                    self.variables.insert(argument.matched.clone());
+                }
+                self.add_script(block);
+            }
+        }
+    }
+
+    ///
+    /// Returns the tools that were found (excluding names that turned out to be variables or tools defined by the script)
+    ///
+    fn dependencies(self) -> Vec<String> {
+        let variables   = self.variables;
+        let definitions = self.definitions;
+
+        self.tools.into_iter()
+            .filter(|name| !variables.contains(name) && !definitions.contains(name))
+            .collect()
+    }
+}
+
+impl ScriptDependenciesTool {
+    ///
+    /// Creates a new script-dependencies tool
+    ///
+    pub fn new() -> ScriptDependenciesTool {
+        ScriptDependenciesTool { }
+    }
+
+    ///
+    /// Finds the names of the tools used by a parsed script
+    ///
+    pub fn dependencies(script: &[Script]) -> Vec<String> {
+        let mut collector = DependencyCollector::new();
+
+        for statement in script.iter() {
+            collector.add_script(statement);
+        }
+
+        collector.dependencies()
+    }
+}
+
+impl Tool for ScriptDependenciesTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let script  = from_value::<String>(input).map_err(|erm| json![{
+            "error":        "script-dependencies must be called with a string",
+            "description":  erm.description()
+        }])?;
+
+        let lexed   = create_lex_script_tool().lex(&script);
+        let parsed  = ParseScriptTool::parse(&lexed).map_err(|parse_error| to_value(parse_error).unwrap())?;
+
+        Ok(json![ ScriptDependenciesTool::dependencies(&parsed) ])
+    }
+}
+
+///
+/// Tool that reports which of the tools used by a script are missing from the environment
+///
+/// The result is an array of the names of the missing tools, which is empty if the script can be run
+///
+pub struct CanRunScriptTool {
+}
+
+impl CanRunScriptTool {
+    ///
+    /// Creates a new can-run-script tool
+    ///
+    pub fn new() -> CanRunScriptTool {
+        CanRunScriptTool { }
+    }
+
+    ///
+    /// Finds the tools used by a parsed script that are missing from an environment
+    ///
+    pub fn missing_tools(script: &[Script], environment: &Environment) -> Vec<String> {
+        ScriptDependenciesTool::dependencies(script)
+            .into_iter()
+            .filter(|name| !environment.has_tool(name))
+            .collect()
+    }
+}
+
+impl Tool for CanRunScriptTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let script  = from_value::<String>(input).map_err(|erm| json![{
+            "error":        "can-run-script must be called with a string",
+            "description":  erm.description()
+        }])?;
+
+        let lexed   = create_lex_script_tool().lex(&script);
+        let parsed  = ParseScriptTool::parse(&lexed).map_err(|parse_error| to_value(parse_error).unwrap())?;
+
+        Ok(json![ CanRunScriptTool::missing_tools(&parsed, environment) ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn finds_tools_used_by_script() {
+        let env     = EmptyEnvironment::new();
+        let tool    = ScriptDependenciesTool::new();
+
+        let result  = tool.invoke_json(json!["print \"Hello\"\nvar x = read-line\nprint x\nx"], &env);
+
+        assert!(result == Ok(json![[ "print", "read-line" ]]));
+    }
+
+    #[test]
+    fn finds_applied_tools_in_expressions() {
+        let env     = EmptyEnvironment::new();
+        let tool    = ScriptDependenciesTool::new();
+
+        let result  = tool.invoke_json(json!["print { \"sum\": add(1, 2) }\nlist-tools"], &env);
+
+        assert!(result == Ok(json![[ "add", "list-tools", "print" ]]));
+    }
+
+    #[test]
+    fn excludes_tools_defined_by_script() {
+        let env     = EmptyEnvironment::new();
+        let tool    = ScriptDependenciesTool::new();
+
+        let result  = tool.invoke_json(json!["def double(x) {\nadd (x, x)\n}\ndouble 2"], &env);
+
+        assert!(result == Ok(json![[ "add" ]]));
+    }
+
+    #[test]
+    fn reports_missing_tools() {
+        let env     = DynamicEnvironment::new();
+        let tool    = CanRunScriptTool::new();
+
+        env.define("print", Box::new(make_pure_tool(|_: Value| ())));
+
+        assert!(tool.invoke_json(json!["var x = read-line\nprint x"], &env) == Ok(json![[ "read-line" ]]));
+    }
+
+    #[test]
+    fn no_missing_tools_if_environment_is_complete() {
+        let env     = DynamicEnvironment::new();
+        let tool    = CanRunScriptTool::new();
+
+        env.define("print", Box::new(make_pure_tool(|_: Value| ())));
+        env.define("read-line", Box::new(make_pure_tool(|_: ()| "line")));
+
+        assert!(tool.invoke_json(json!["var x = read-line\nprint x"], &env) == Ok(json![[]]));
+    }
+
+
+    #[test]
+    fn bare_map_keys_are_not_dependencies() {
+        let env     = EmptyEnvironment::new();
+        let tool    = ScriptDependenciesTool::new();
+
+        let result  = tool.invoke_json(json!["print { foo: bar, (baz): 1 }"], &env);
+
+        assert!(result == Ok(json![[ "bar", "baz", "print" ]]));
+    }
+}
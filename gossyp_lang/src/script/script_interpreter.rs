@@ -4,6 +4,10 @@
 //!
 
 use std::result::Result;
+use std::collections::HashMap;
+use std::time::Instant;
+use std::sync::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use serde_json::*;
 
 use gossyp_base::{Tool, Environment};
@@ -66,7 +70,99 @@ pub enum ScriptEvaluationError {
     WasExpectingAVariable,
 
     /// Named variable was not found
-    VariableNameNotFound
+    VariableNameNotFound,
+
+    /// A number literal could not be parsed (for example, because it's too large)
+    MalformedNumber,
+
+    /// The expression in a using statement must evaluate to a map of names to tool names
+    UsingMustEvaluateToAMapOfToolNames,
+
+    /// The script was still running when its deadline passed
+    DeadlineExceeded,
+
+    /// The expression in a for statement must evaluate to an array
+    ForMustIterateOverAnArray,
+
+    /// A break statement was evaluated outside of a loop
+    BreakOutsideLoop,
+
+    /// In a field access expression like foo.bar, foo must be an object
+    FieldAccessMustApplyToAnObject,
+
+    /// The operands of an arithmetic operator must both be numbers (or both be strings for '+')
+    OperandTypeMismatch,
+
+    /// The right-hand side of a '/' or '%' operator was zero
+    DivisionByZero,
+
+    /// The result of an arithmetic operator is too large to be represented as a number
+    ResultIsNotAFiniteNumber,
+
+    /// The script ran more statements and loop iterations than it's allowed to
+    StepLimitExceeded,
+
+    /// Tools defined by the script called each other more deeply than they're allowed to
+    RecursionLimitExceeded,
+
+    /// The thread evaluating the script panicked before it produced a result
+    EvaluationPanicked
+}
+
+impl ScriptEvaluationError {
+    ///
+    /// Returns the error that follows this one in the list of all errors
+    ///
+    /// This match is exhaustive so that new errors can't be added without also adding them to
+    /// the list returned by `all()`
+    ///
+    fn next(&self) -> Option<ScriptEvaluationError> {
+        use self::ScriptEvaluationError::*;
+
+        match self {
+            &ExpressionNotImplemented           => Some(StatementNotImplemented),
+            &StatementNotImplemented            => Some(ToolNameNotFound),
+            &ToolNameNotFound                   => Some(ExpressionDoesNotEvaluateToTool),
+            &ExpressionDoesNotEvaluateToTool    => Some(MapKeysMustEvaluateToAString),
+            &MapKeysMustEvaluateToAString       => Some(IndexMustApplyToAnArrayOrAMap),
+            &IndexMustApplyToAnArrayOrAMap      => Some(ArrayIndexMustBeANumber),
+            &ArrayIndexMustBeANumber            => Some(MapIndexMustBeAString),
+            &MapIndexMustBeAString              => Some(IndexOutOfBounds),
+            &IndexOutOfBounds                   => Some(ObjectValueNotPresent),
+            &ObjectValueNotPresent              => Some(FieldMustBeIdentifier),
+            &FieldMustBeIdentifier              => Some(VariableNameAlreadyInUse),
+            &VariableNameAlreadyInUse           => Some(WasExpectingAVariable),
+            &WasExpectingAVariable              => Some(VariableNameNotFound),
+            &VariableNameNotFound               => Some(MalformedNumber),
+            &MalformedNumber                    => Some(UsingMustEvaluateToAMapOfToolNames),
+            &UsingMustEvaluateToAMapOfToolNames => Some(DeadlineExceeded),
+            &DeadlineExceeded                   => Some(ForMustIterateOverAnArray),
+            &ForMustIterateOverAnArray          => Some(BreakOutsideLoop),
+            &BreakOutsideLoop                   => Some(FieldAccessMustApplyToAnObject),
+            &FieldAccessMustApplyToAnObject     => Some(OperandTypeMismatch),
+            &OperandTypeMismatch                => Some(DivisionByZero),
+            &DivisionByZero                     => Some(ResultIsNotAFiniteNumber),
+            &ResultIsNotAFiniteNumber           => Some(StepLimitExceeded),
+            &StepLimitExceeded                  => Some(RecursionLimitExceeded),
+            &RecursionLimitExceeded             => Some(EvaluationPanicked),
+            &EvaluationPanicked                 => None
+        }
+    }
+
+    ///
+    /// Returns every kind of script evaluation error
+    ///
+    pub fn all() -> Vec<ScriptEvaluationError> {
+        let mut result  = vec![];
+        let mut next    = Some(ScriptEvaluationError::ExpressionNotImplemented);
+
+        while let Some(error) = next {
+            next = error.next();
+            result.push(error);
+        }
+
+        result
+    }
 }
 
 impl InterpretedScriptTool {
@@ -103,12 +199,118 @@ impl Tool for InterpretedScriptTool {
     }
 }
 
+///
+/// Limits on how much work a script is allowed to do
+///
+/// Tools defined by `def` statements share the limits of the script that defined them, so the
+/// work they do counts towards the same totals even if they're called from somewhere else.
+///
+pub struct ScriptLimits {
+    /// The time by which the script must finish, if it has one
+    deadline: Option<Instant>,
+
+    /// The number of statements and loop iterations the script can run, if it's limited
+    max_steps: Option<usize>,
+
+    /// The number of calls to tools defined by the script that can be running at once, if it's limited
+    max_depth: Option<usize>,
+
+    /// The number of steps that have been run so far
+    steps: AtomicUsize,
+
+    /// The number of calls to tools defined by the script that are currently running
+    depth: AtomicUsize
+}
+
+///
+/// Represents a call to a tool defined by a script, which is finished when this is dropped
+///
+pub struct ScriptCallDepth<'a> {
+    limits: &'a ScriptLimits
+}
+
+impl ScriptLimits {
+    ///
+    /// Creates a new set of limits
+    ///
+    pub fn new(deadline: Option<Instant>, max_steps: Option<usize>, max_depth: Option<usize>) -> ScriptLimits {
+        ScriptLimits {
+            deadline:   deadline,
+            max_steps:  max_steps,
+            max_depth:  max_depth,
+            steps:      AtomicUsize::new(0),
+            depth:      AtomicUsize::new(0)
+        }
+    }
+
+    ///
+    /// Creates limits that allow a script to run for as long as it likes
+    ///
+    pub fn unlimited() -> ScriptLimits {
+        ScriptLimits::new(None, None, None)
+    }
+
+    ///
+    /// The time by which the script must finish, if it has one
+    ///
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    ///
+    /// True if there's a deadline and it has passed
+    ///
+    pub fn deadline_exceeded(&self) -> bool {
+        self.deadline.map(|deadline| Instant::now() >= deadline).unwrap_or(false)
+    }
+
+    ///
+    /// Counts a step, returning an error if the script has run out of time or steps
+    ///
+    pub fn step(&self) -> Result<(), ScriptEvaluationError> {
+        if self.deadline_exceeded() {
+            return Err(ScriptEvaluationError::DeadlineExceeded);
+        }
+
+        let steps = self.steps.fetch_add(1, Ordering::SeqCst) + 1;
+        match self.max_steps {
+            Some(max_steps) if steps > max_steps    => Err(ScriptEvaluationError::StepLimitExceeded),
+            _                                       => Ok(())
+        }
+    }
+
+    ///
+    /// Starts a call to a tool defined by the script, returning an error if too many are already running
+    ///
+    pub fn enter_call(&self) -> Result<ScriptCallDepth, ScriptEvaluationError> {
+        let depth = self.depth.fetch_add(1, Ordering::SeqCst) + 1;
+        let call  = ScriptCallDepth { limits: self };
+
+        match self.max_depth {
+            Some(max_depth) if depth > max_depth    => Err(ScriptEvaluationError::RecursionLimitExceeded),
+            _                                       => Ok(call)
+        }
+    }
+}
+
+impl<'a> Drop for ScriptCallDepth<'a> {
+    fn drop(&mut self) {
+        self.limits.depth.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 ///
 /// Represents an execution environment for a running script
 ///
 pub struct ScriptExecutionEnvironment {
     /// Current values of the variables in this environment
     variable_values: Vec<Box<Value>>,
+
+    /// The names that the variables were bound to (used when evaluating further scripts with the same state)
+    variable_names: HashMap<String, u32>,
+
+    /// The limits on how much work the script can do
+    limits: Arc<ScriptLimits>
 }
 
 impl ScriptExecutionEnvironment {
@@ -116,7 +318,85 @@ impl ScriptExecutionEnvironment {
     /// Creates a new script execution environment
     ///
     pub fn new() -> ScriptExecutionEnvironment {
-        ScriptExecutionEnvironment { variable_values: vec![] }
+        ScriptExecutionEnvironment { variable_values: vec![], variable_names: HashMap::new(), limits: Arc::new(ScriptLimits::unlimited()) }
+    }
+
+    ///
+    /// Returns the number of variables allocated in this environment
+    ///
+    pub fn get_number_of_variables(&self) -> u32 {
+        self.variable_values.len() as u32
+    }
+
+    ///
+    /// Retrieves the names of the variables in this environment
+    ///
+    pub fn get_variable_names(&self) -> &HashMap<String, u32> {
+        &self.variable_names
+    }
+
+    ///
+    /// Retrieves the current values of the named variables in this environment
+    ///
+    pub fn get_named_variables(&self) -> Map<String, Value> {
+        let mut result = Map::new();
+
+        for (name, pos) in self.variable_names.iter() {
+            if (*pos as usize) < self.variable_values.len() {
+                result.insert(name.clone(), self.get_variable(*pos).clone());
+            }
+        }
+
+        result
+    }
+
+    ///
+    /// Sets the names of the variables in this environment
+    ///
+    pub fn set_variable_names(&mut self, variable_names: HashMap<String, u32>) {
+        self.variable_names = variable_names;
+    }
+
+    ///
+    /// Sets the time by which scripts evaluated in this environment must finish
+    ///
+    /// The deadline is checked before each statement in a sequence and each iteration of a loop,
+    /// so a script that is waiting for a tool to finish will only stop once the tool returns.
+    /// This replaces any other limits set for this environment.
+    ///
+    pub fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.limits = Arc::new(ScriptLimits::new(deadline, None, None));
+    }
+
+    ///
+    /// Sets the limits for scripts evaluated in this environment
+    ///
+    /// Like the deadline, the step limit is checked before each statement in a sequence and each
+    /// iteration of a loop.
+    ///
+    pub fn set_limits(&mut self, limits: Arc<ScriptLimits>) {
+        self.limits = limits;
+    }
+
+    ///
+    /// Retrieves the limits for scripts evaluated in this environment
+    ///
+    pub fn get_limits(&self) -> Arc<ScriptLimits> {
+        self.limits.clone()
+    }
+
+    ///
+    /// True if this environment has a deadline and it has passed
+    ///
+    pub fn deadline_exceeded(&self) -> bool {
+        self.limits.deadline_exceeded()
+    }
+
+    ///
+    /// Counts a step of the script, returning an error if it has exceeded its limits
+    ///
+    pub fn step(&self) -> Result<(), ScriptEvaluationError> {
+        self.limits.step()
     }
 
     ///
@@ -0,0 +1,306 @@
+//!
+//! The script-to-dot tool describes the syntax tree of a script as a GraphViz graph
+//!
+
+use std::result::Result;
+use std::error::Error;
+
+use serde_json::*;
+use gossyp_base::*;
+
+use super::script::*;
+use super::lex_script_tool::*;
+use super::parse_script_tool::*;
+
+///
+/// Tool that parses a script and returns its syntax tree in GraphViz DOT format
+///
+/// Each statement and expression becomes a node labelled with its kind (and its name or value,
+/// where it has one), with an edge to each of its children. The root node is the sequence of
+/// statements in the script.
+///
+pub struct ScriptToDotTool {
+}
+
+///
+/// Builds up the nodes and edges of a DOT graph
+///
+struct DotGraph {
+    /// The label of each node
+    nodes: Vec<String>,
+
+    /// The edges between the nodes, as (parent, child) indexes
+    edges: Vec<(usize, usize)>
+}
+
+impl DotGraph {
+    ///
+    /// Creates an empty graph
+    ///
+    fn new() -> DotGraph {
+        DotGraph { nodes: vec![], edges: vec![] }
+    }
+
+    ///
+    /// Adds a node, returning its index
+    ///
+    fn add_node(&mut self, label: String, parent: Option<usize>) -> usize {
+        let index = self.nodes.len();
+
+        self.nodes.push(label);
+        if let Some(parent) = parent {
+            self.edges.push((parent, index));
+        }
+
+        index
+    }
+
+    ///
+    /// Adds a statement and its children to the graph
+    ///
+    fn add_script(&mut self, script: &Script, parent: Option<usize>) {
+        match script {
+            &Script::RunCommand(ref command) => {
+                let node = self.add_node(String::from("RunCommand"), parent);
+                self.add_expression(command, node);
+            },
+
+            &Script::Sequence(ref statements) => {
+                let node = self.add_node(String::from("Sequence"), parent);
+                statements.iter().for_each(|statement| self.add_script(statement, Some(node)));
+            },
+
+            &Script::Let(ref name, ref value) => {
+                let node = self.add_node(format!("Let {}", name.matched), parent);
+                self.add_expression(value, node);
+            },
+
+            &Script::Var(ref name, ref value) => {
+                let node = self.add_node(format!("Var {}", name.matched), parent);
+                self.add_expression(value, node);
+            },
+
+            &Script::Assign(ref name, ref value) => {
+                let node = self.add_node(format!("Assign {}", name.matched), parent);
+                self.add_expression(value, node);
+            },
+
+            &Script::If(ref condition, ref then_script, ref else_script) => {
+                let node = self.add_node(String::from("If"), parent);
+                self.add_expression(condition, node);
+                self.add_script(then_script, Some(node));
+                else_script.as_ref().map(|else_script| self.add_script(else_script, Some(node)));
+            },
+
+            &Script::Loop(ref body) => {
+                let node = self.add_node(String::from("Loop"), parent);
+                self.add_script(body, Some(node));
+            },
+
+            &Script::While(ref condition, ref body) => {
+                let node = self.add_node(String::from("While"), parent);
+                self.add_expression(condition, node);
+                self.add_script(body, Some(node));
+            },
+
+            &Script::For(ref name, ref values, ref body) => {
+                let node = self.add_node(format!("For {}", name.matched), parent);
+                self.add_expression(values, node);
+                self.add_script(body, Some(node));
+            },
+
+            &Script::Break(ref value) => {
+                let node = self.add_node(String::from("Break"), parent);
+                value.as_ref().map(|value| self.add_expression(value, node));
+            },
+
+            &Script::Using(ref tools, ref body) => {
+                let node = self.add_node(String::from("Using"), parent);
+                self.add_expression(tools, node);
+                self.add_script(body, Some(node));
+            },
+
+            &Script::Def(ref name, ref arguments, ref body) => {
+                let arguments   = arguments.iter().map(|argument| argument.matched.clone()).collect::<Vec<_>>();
+                let node        = self.add_node(format!("Def {}({})", name.matched, arguments.join(", ")), parent);
+                self.add_script(body, Some(node));
+            }
+        }
+    }
+
+    ///
+    /// Adds an expression and its children to the graph
+    ///
+    fn add_expression(&mut self, expression: &Expression, parent: usize) {
+        let parent = Some(parent);
+
+        match expression {
+            &Expression::String(ref value)      => { self.add_node(format!("String {}", value.matched), parent); },
+            &Expression::Number(ref value)      => { self.add_node(format!("Number {}", value.matched), parent); },
+            &Expression::Constant(ref value)    => { self.add_node(format!("Constant {}", value.matched), parent); },
+            &Expression::Identifier(ref name)   => { self.add_node(format!("Identifier {}", name.matched), parent); },
+
+            &Expression::Array(ref items) => {
+                let node = self.add_node(String::from("Array"), parent);
+                items.iter().for_each(|item| self.add_expression(item, node));
+            },
+
+            &Expression::Tuple(ref items) => {
+                let node = self.add_node(String::from("Tuple"), parent);
+                items.iter().for_each(|item| self.add_expression(item, node));
+            },
+
+            &Expression::Map(ref entries) => {
+                let node = self.add_node(String::from("Map"), parent);
+
+                for &(ref key, ref value) in entries.iter() {
+                    let entry = self.add_node(String::from("Entry"), Some(node));
+
+                    match key {
+                        &MapKey::Name(ref name)         => { self.add_node(format!("Name {}", name.matched), Some(entry)); },
+                        &MapKey::Expression(ref key)    => self.add_expression(key, entry)
+                    }
+
+                    self.add_expression(value, entry);
+                }
+            },
+
+            &Expression::Index(ref index) => {
+                let node = self.add_node(String::from("Index"), parent);
+                self.add_expression(&index.0, node);
+                self.add_expression(&index.1, node);
+            },
+
+            &Expression::Slice(ref slice) => {
+                let node = self.add_node(String::from("Slice"), parent);
+                self.add_expression(&slice.0, node);
+                slice.1.as_ref().map(|start| self.add_expression(start, node));
+                slice.2.as_ref().map(|end| self.add_expression(end, node));
+            },
+
+            &Expression::FieldAccess(ref access) => {
+                let node = self.add_node(String::from("FieldAccess"), parent);
+                self.add_expression(&access.0, node);
+                self.add_expression(&access.1, node);
+            },
+
+            &Expression::Apply(ref apply) => {
+                let node = self.add_node(String::from("Apply"), parent);
+                self.add_expression(&apply.0, node);
+                self.add_expression(&apply.1, node);
+            },
+
+            &Expression::BinaryOp(ref operator, ref operands) => {
+                let node = self.add_node(format!("BinaryOp {}", operator.matched), parent);
+                self.add_expression(&operands.0, node);
+                self.add_expression(&operands.1, node);
+            },
+
+            &Expression::UnaryOp(ref operator, ref operand) => {
+                let node = self.add_node(format!("UnaryOp {}", operator.matched), parent);
+                self.add_expression(operand, node);
+            }
+        }
+    }
+
+    ///
+    /// Quotes a label so it can be used in a DOT file
+    ///
+    fn quote(label: &str) -> String {
+        let escaped = label
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n");
+
+        format!("\"{}\"", escaped)
+    }
+
+    ///
+    /// Writes out the graph in DOT format
+    ///
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph script {\n");
+
+        for (index, label) in self.nodes.iter().enumerate() {
+            dot.push_str(&format!("    n{} [label={}];\n", index, DotGraph::quote(label)));
+        }
+
+        for &(parent, child) in self.edges.iter() {
+            dot.push_str(&format!("    n{} -> n{};\n", parent, child));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl ScriptToDotTool {
+    ///
+    /// Creates a new script-to-dot tool
+    ///
+    pub fn new() -> ScriptToDotTool {
+        ScriptToDotTool { }
+    }
+
+    ///
+    /// Generates the DOT graph for a parsed script
+    ///
+    pub fn script_to_dot(script: &[Script]) -> String {
+        let mut graph = DotGraph::new();
+        graph.add_script(&Script::Sequence(script.to_vec()), None);
+
+        graph.to_dot()
+    }
+
+    ///
+    /// Parses a script and generates the DOT graph of its syntax tree
+    ///
+    pub fn source_to_dot(source: &str) -> Result<String, Value> {
+        let lexed   = create_lex_script_tool().lex(source);
+        let parsed  = ParseScriptTool::parse(&lexed).map_err(|parse_error| to_value(parse_error).unwrap())?;
+
+        Ok(ScriptToDotTool::script_to_dot(&parsed))
+    }
+}
+
+impl Tool for ScriptToDotTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        match from_value::<String>(input) {
+            Ok(source)  => Ok(Value::String(ScriptToDotTool::source_to_dot(&source)?)),
+            Err(erm)    => Err(json![{
+                "error":        "script-to-dot must be called with a string",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn if_script_generates_a_node_for_each_part() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<&str, String>::from(Box::new(ScriptToDotTool::new()));
+        let dot     = tool.invoke("if x {\nprint \"yes\"\n}", &env).unwrap();
+
+        // Sequence -> If -> (Identifier x, RunCommand -> Apply -> (Identifier print, String "yes"))
+        assert!(dot.starts_with("digraph script {\n"));
+        assert!(dot.matches("[label=").count() == 7);
+        assert!(dot.matches(" -> ").count() == 6);
+
+        assert!(dot.contains("n1 [label=\"If\"];"));
+        assert!(dot.contains("[label=\"String \\\"yes\\\"\"];"));
+        assert!(dot.contains("n0 -> n1;"));
+    }
+
+    #[test]
+    fn parse_errors_are_reported() {
+        let env     = EmptyEnvironment::new();
+        let tool    = ScriptToDotTool::new();
+
+        assert!(tool.invoke_json(json![ "if {" ], &env).is_err());
+    }
+}
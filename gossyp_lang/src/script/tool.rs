@@ -2,3 +2,17 @@ pub const LEX_SCRIPT: &'static str                  = "lex-script";
 pub const PARSE_SCRIPT: &'static str                = "parse-script";
 pub const EVAL_SCRIPT: &'static str                 = "eval-script";
 pub const CREATE_EVALUATOR_WITH_STATE: &'static str = "create-evaluator-with-state";
+pub const CHECK_SCRIPT: &'static str                = "check-script";
+pub const SCRIPT_DEPENDENCIES: &'static str         = "script-dependencies";
+pub const CAN_RUN_SCRIPT: &'static str              = "can-run-script";
+pub const FORMAT_ERROR: &'static str                = "format-error";
+pub const CALC: &'static str                        = "calc";
+pub const VERSION: &'static str                     = "version";
+pub const DEFINE_SCRIPT_TOOL: &'static str          = "define-script-tool";
+pub const RUN_SCRIPT_WITH_BINDINGS: &'static str    = "run-script-with-bindings";
+pub const RUN_SCRIPT_TIMEOUT: &'static str          = "run-script-timeout";
+pub const ERROR_KINDS: &'static str                 = "error-kinds";
+pub const DEFINE_MANY: &'static str                 = "define-many";
+pub const RUN_ISOLATED: &'static str                = "run-isolated";
+pub const SAFE_RUN: &'static str                    = "safe-run";
+pub const SCRIPT_TO_DOT: &'static str               = "script-to-dot";
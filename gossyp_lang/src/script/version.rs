@@ -0,0 +1,266 @@
+//!
+//! The version tool reports the version of gossyp and of its scripting language
+//!
+
+use std::result::Result;
+use std::collections::BTreeSet;
+
+use serde_json::*;
+use gossyp_base::*;
+
+use super::script::*;
+
+///
+/// The version of the scripting language
+///
+/// This is updated whenever the grammar changes, so scripts can check that the language they
+/// are written in is supported. The change should also be added to `FEATURE_VERSIONS`.
+///
+pub const LANGUAGE_VERSION: &'static str = "0.14.0";
+
+///
+/// The features that have been added to the language since version 0.2.0, with the version they first appeared in
+///
+pub const FEATURE_VERSIONS: &'static [(&'static str, &'static str)] = &[
+    ("loop",            "0.3.0"),
+    ("while",           "0.4.0"),
+    ("for",             "0.5.0"),
+    ("break",           "0.6.0"),
+    ("def",             "0.7.0"),
+    ("arithmetic",      "0.8.0"),
+    ("comparison",      "0.9.0"),
+    ("logic",           "0.10.0"),
+    ("constants",       "0.11.0"),
+    ("signed-numbers",  "0.12.0"),
+    ("slice",           "0.13.0"),
+    ("field-access",    "0.14.0")
+];
+
+///
+/// Adds the features from `FEATURE_VERSIONS` used by an expression
+///
+fn add_expression_features(expr: &Expression, features: &mut BTreeSet<&'static str>) {
+    match expr {
+        &Expression::String(_)                  |
+        &Expression::Identifier(_)              => { },
+        &Expression::Constant(_)                => { features.insert("constants"); },
+        &Expression::Number(ref num)            => { if num.matched.starts_with('-') { features.insert("signed-numbers"); } },
+
+        &Expression::Array(ref items)           |
+        &Expression::Tuple(ref items)           => { for item in items.iter() { add_expression_features(item, features); } },
+        &Expression::Map(ref items)             => {
+            for &(ref key, ref value) in items.iter() {
+                if let &MapKey::Expression(ref key) = key { add_expression_features(key, features); }
+                add_expression_features(value, features);
+            }
+        },
+
+        &Expression::Index(ref pair)            |
+        &Expression::Apply(ref pair)            => {
+            let (ref lhs, ref rhs) = **pair;
+            add_expression_features(lhs, features);
+            add_expression_features(rhs, features);
+        },
+
+        &Expression::FieldAccess(ref pair)      => {
+            let (ref lhs, ref rhs) = **pair;
+            features.insert("field-access");
+            add_expression_features(lhs, features);
+            add_expression_features(rhs, features);
+        },
+
+        &Expression::Slice(ref slice)           => {
+            let (ref lhs, ref start, ref end) = **slice;
+            features.insert("slice");
+            add_expression_features(lhs, features);
+            if let &Some(ref start) = start { add_expression_features(start, features); }
+            if let &Some(ref end) = end { add_expression_features(end, features); }
+        },
+
+        &Expression::BinaryOp(ref op, ref pair) => {
+            let (ref lhs, ref rhs) = **pair;
+            features.insert(match op.matched.as_str() {
+                "==" | "!=" | "<" | "<=" | ">" | ">="   => "comparison",
+                "and" | "or"                            => "logic",
+                _                                       => "arithmetic"
+            });
+            add_expression_features(lhs, features);
+            add_expression_features(rhs, features);
+        },
+
+        &Expression::UnaryOp(_, ref operand)    => {
+            // 'not' is the only unary operator
+            features.insert("logic");
+            add_expression_features(operand, features);
+        }
+    }
+}
+
+///
+/// Adds the features from `FEATURE_VERSIONS` used by a statement
+///
+fn add_script_features(script: &Script, features: &mut BTreeSet<&'static str>) {
+    match script {
+        &Script::RunCommand(ref expr)                           |
+        &Script::Let(_, ref expr)                               |
+        &Script::Var(_, ref expr)                               |
+        &Script::Assign(_, ref expr)                            => add_expression_features(expr, features),
+        &Script::Sequence(ref statements)                       => { for statement in statements.iter() { add_script_features(statement, features); } },
+
+        &Script::If(ref expr, ref then_block, ref else_block)   => {
+            add_expression_features(expr, features);
+            add_script_features(then_block, features);
+            if let &Some(ref else_block) = else_block { add_script_features(else_block, features); }
+        },
+
+        &Script::Using(ref expr, ref block)                     => {
+            add_expression_features(expr, features);
+            add_script_features(block, features);
+        },
+
+        &Script::Loop(ref block)                                => {
+            features.insert("loop");
+            add_script_features(block, features);
+        },
+
+        &Script::While(ref expr, ref block)                     => {
+            features.insert("while");
+            add_expression_features(expr, features);
+            add_script_features(block, features);
+        },
+
+        &Script::For(_, ref expr, ref block)                    => {
+            features.insert("for");
+            add_expression_features(expr, features);
+            add_script_features(block, features);
+        },
+
+        &Script::Break(ref expr)                                => {
+            features.insert("break");
+            if let &Some(ref expr) = expr { add_expression_features(expr, features); }
+        },
+
+        &Script::Def(_, _, ref body)                            => {
+            features.insert("def");
+            add_script_features(body, features);
+        }
+    }
+}
+
+///
+/// Finds the names of the features from `FEATURE_VERSIONS` that a script uses
+///
+pub fn features_used(script: &Vec<Script>) -> BTreeSet<&'static str> {
+    let mut features = BTreeSet::new();
+
+    for statement in script.iter() {
+        add_script_features(statement, &mut features);
+    }
+
+    features
+}
+
+///
+/// Returns the oldest language version that has every feature a script uses, or None if it only uses features from before `FEATURE_VERSIONS`
+///
+pub fn required_version(script: &Vec<Script>) -> Option<&'static str> {
+    let features = features_used(script);
+
+    // Features are listed from oldest to newest
+    FEATURE_VERSIONS.iter()
+        .filter(|&&(feature, _)| features.contains(feature))
+        .map(|&(_, version)| version)
+        .last()
+}
+
+///
+/// Result of the version tool
+///
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct VersionResult {
+    /// The version of the gossyp_lang crate
+    #[serde(rename = "crate")]
+    pub crate_version: String,
+
+    /// The version of the scripting language
+    pub language: String
+}
+
+///
+/// Tool that returns the crate and language versions
+///
+pub struct VersionTool {
+}
+
+impl VersionTool {
+    ///
+    /// Creates a new version tool
+    ///
+    pub fn new() -> VersionTool {
+        VersionTool { }
+    }
+
+    ///
+    /// Retrieves the current version
+    ///
+    pub fn version() -> VersionResult {
+        VersionResult {
+            crate_version:  String::from(env!("CARGO_PKG_VERSION")),
+            language:       String::from(LANGUAGE_VERSION)
+        }
+    }
+}
+
+impl Tool for VersionTool {
+    fn invoke_json(&self, _input: Value, _environment: &Environment) -> Result<Value, Value> {
+        Ok(to_value(VersionTool::version()).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn returns_crate_and_language_version() {
+        let env     = EmptyEnvironment::new();
+        let tool    = VersionTool::new();
+
+        let result  = tool.invoke_json(Value::Null, &env);
+
+        assert!(result == Ok(json![{ "crate": env!("CARGO_PKG_VERSION"), "language": LANGUAGE_VERSION }]));
+    }
+
+    #[test]
+    fn language_version_includes_newest_feature() {
+        assert!(FEATURE_VERSIONS.last().map(|&(_, version)| version) == Some(LANGUAGE_VERSION));
+    }
+
+    #[test]
+    fn finds_features_used_by_script() {
+        let script = vec![
+            Script::Def(ScriptToken::identifier("stop"), vec![], Box::new(Script::Break(Some(Expression::BinaryOp(ScriptToken::new(ScriptLexerToken::symbol("<"), 0, 1, String::from("<")), Box::new((Expression::number("1"), Expression::number("-2")))))))),
+            Script::RunCommand(Expression::identifier("stop"))
+        ];
+
+        assert!(features_used(&script) == vec![ "break", "comparison", "def", "signed-numbers" ].into_iter().collect());
+        assert!(required_version(&script) == Some("0.12.0"));
+    }
+
+    #[test]
+    fn field_access_requires_newest_version() {
+        let script = vec![ Script::RunCommand(Expression::FieldAccess(Box::new((Expression::identifier("x"), Expression::identifier("a"))))) ];
+
+        assert!(features_used(&script) == vec![ "field-access" ].into_iter().collect());
+        assert!(required_version(&script) == Some("0.14.0"));
+    }
+
+    #[test]
+    fn script_without_new_features_has_no_required_version() {
+        let script = vec![ Script::RunCommand(Expression::Apply(Box::new((Expression::identifier("print"), Expression::string("\"Hello\""))))) ];
+
+        assert!(features_used(&script).len() == 0);
+        assert!(required_version(&script) == None);
+    }
+}
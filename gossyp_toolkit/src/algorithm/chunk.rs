@@ -0,0 +1,108 @@
+//!
+//! The chunk tool divides an array into groups of a fixed size
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Parameters that can be passed to the chunk tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct ChunkParameters {
+    /// The values to divide into chunks
+    values: Vec<Value>,
+
+    /// The number of values in each chunk
+    size: i64
+}
+
+impl ChunkParameters {
+    pub fn new(values: Vec<Value>, size: i64) -> ChunkParameters {
+        ChunkParameters { values: values, size: size }
+    }
+}
+
+///
+/// Tool that divides an array into chunks of a fixed size
+///
+/// Every chunk except the last will contain exactly `size` values. The last chunk contains
+/// whatever values remain.
+///
+pub struct ChunkTool {
+}
+
+impl ChunkTool {
+    ///
+    /// Creates a new chunk tool
+    ///
+    pub fn new() -> ChunkTool {
+        ChunkTool { }
+    }
+
+    ///
+    /// Divides an array into chunks
+    ///
+    pub fn chunk(values: Vec<Value>, size: i64) -> Result<Vec<Vec<Value>>, Value> {
+        if size <= 0 {
+            Err(json![{
+                "error":    "The size passed to chunk must be greater than 0",
+                "size":     size
+            }])
+        } else {
+            Ok(values.chunks(size as usize).map(|chunk| chunk.to_vec()).collect())
+        }
+    }
+}
+
+impl Tool for ChunkTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<ChunkParameters>(input);
+
+        match parameters {
+            Ok(ChunkParameters { values, size }) => ChunkTool::chunk(values, size).map(|chunks| json![ chunks ]),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to chunk must be of the form { \"values\": <array>, \"size\": <number> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn can_chunk_evenly() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<ChunkParameters, Value>::from(Box::new(ChunkTool::new()));
+
+        let result  = tool.invoke(ChunkParameters::new(vec![ json![1], json![2], json![3], json![4] ], 2), &env);
+
+        assert!(result == Ok(json![[ [1, 2], [3, 4] ]]));
+    }
+
+    #[test]
+    fn last_chunk_can_be_shorter() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<ChunkParameters, Value>::from(Box::new(ChunkTool::new()));
+
+        let result  = tool.invoke(ChunkParameters::new(vec![ json![1], json![2], json![3], json![4], json![5] ], 2), &env);
+
+        assert!(result == Ok(json![[ [1, 2], [3, 4], [5] ]]));
+    }
+
+    #[test]
+    fn zero_size_is_error() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<ChunkParameters, Value>::from(Box::new(ChunkTool::new()));
+
+        assert!(tool.invoke(ChunkParameters::new(vec![ json![1] ], 0), &env).is_err());
+        assert!(tool.invoke(ChunkParameters::new(vec![ json![1] ], -2), &env).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//!
+//! The clamp and between tools check numbers against a range
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Parameters that can be passed to the clamp and between tools
+///
+#[derive(Serialize, Deserialize)]
+pub struct RangeParameters {
+    /// The value to check
+    pub value: Number,
+
+    /// The lowest value in the range
+    pub min: Number,
+
+    /// The highest value in the range
+    pub max: Number
+}
+
+impl RangeParameters {
+    pub fn new(value: Number, min: Number, max: Number) -> RangeParameters {
+        RangeParameters { value: value, min: min, max: max }
+    }
+}
+
+///
+/// Tool that limits a number to a range
+///
+/// The result is `min` if the value is less than `min`, `max` if it's greater than `max`, and the
+/// value itself otherwise.
+///
+pub struct ClampTool {
+}
+
+///
+/// Tool that returns true if a number is within a range (including the values at either end)
+///
+pub struct BetweenTool {
+}
+
+///
+/// Decodes the parameters to a range tool, checking that the range is valid
+///
+fn range_parameters(input: Value, tool_name: &str) -> Result<RangeParameters, Value> {
+    let parameters = from_value::<RangeParameters>(input).map_err(|erm| json![{
+        "error":        format!("Parameters to {} must be of the form {{ \"value\": <number>, \"min\": <number>, \"max\": <number> }}", tool_name),
+        "description":  erm.description()
+    }])?;
+
+    if as_f64(&parameters.min) > as_f64(&parameters.max) {
+        Err(json![{
+            "error":    "The minimum value of a range must not be greater than the maximum value",
+            "min":      parameters.min,
+            "max":      parameters.max
+        }])
+    } else {
+        Ok(parameters)
+    }
+}
+
+///
+/// Converts a JSON number to a float
+///
+#[inline]
+fn as_f64(number: &Number) -> f64 {
+    number.as_f64().unwrap_or(0.0)
+}
+
+impl ClampTool {
+    ///
+    /// Creates a new clamp tool
+    ///
+    pub fn new() -> ClampTool {
+        ClampTool { }
+    }
+
+    ///
+    /// Limits a number to a range
+    ///
+    pub fn clamp(value: Number, min: Number, max: Number) -> Number {
+        if as_f64(&value) < as_f64(&min) {
+            min
+        } else if as_f64(&value) > as_f64(&max) {
+            max
+        } else {
+            value
+        }
+    }
+}
+
+impl BetweenTool {
+    ///
+    /// Creates a new between tool
+    ///
+    pub fn new() -> BetweenTool {
+        BetweenTool { }
+    }
+
+    ///
+    /// True if a number is in a range
+    ///
+    pub fn between(value: &Number, min: &Number, max: &Number) -> bool {
+        as_f64(value) >= as_f64(min) && as_f64(value) <= as_f64(max)
+    }
+}
+
+impl Tool for ClampTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let RangeParameters { value, min, max } = range_parameters(input, "clamp")?;
+
+        Ok(Value::Number(ClampTool::clamp(value, min, max)))
+    }
+}
+
+impl Tool for BetweenTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let RangeParameters { value, min, max } = range_parameters(input, "between")?;
+
+        Ok(Value::Bool(BetweenTool::between(&value, &min, &max)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    fn clamp(value: Value) -> Result<Value, Value> {
+        ClampTool::new().invoke_json(json![{ "value": value, "min": 1, "max": 10 }], &EmptyEnvironment::new())
+    }
+
+    #[test]
+    fn clamp_below_range() {
+        assert!(clamp(json![ -5 ]) == Ok(json![ 1 ]));
+    }
+
+    #[test]
+    fn clamp_within_range() {
+        assert!(clamp(json![ 4.5 ]) == Ok(json![ 4.5 ]));
+    }
+
+    #[test]
+    fn clamp_above_range() {
+        assert!(clamp(json![ 11 ]) == Ok(json![ 10 ]));
+    }
+
+    #[test]
+    fn clamp_non_numeric_is_error() {
+        assert!(clamp(json![ "5" ]).is_err());
+    }
+
+    #[test]
+    fn between_includes_ends() {
+        let env     = EmptyEnvironment::new();
+        let tool    = BetweenTool::new();
+
+        assert!(tool.invoke_json(json![{ "value": 1, "min": 1, "max": 10 }], &env) == Ok(json![ true ]));
+        assert!(tool.invoke_json(json![{ "value": 10, "min": 1, "max": 10 }], &env) == Ok(json![ true ]));
+        assert!(tool.invoke_json(json![{ "value": 10.5, "min": 1, "max": 10 }], &env) == Ok(json![ false ]));
+        assert!(tool.invoke_json(json![{ "value": null, "min": 1, "max": 10 }], &env).is_err());
+    }
+
+    #[test]
+    fn inverted_range_is_error() {
+        let env     = EmptyEnvironment::new();
+
+        assert!(ClampTool::new().invoke_json(json![{ "value": 5, "min": 10, "max": 1 }], &env).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use std::result::Result;
+use std::cmp::Ordering;
 use serde_json::*;
 use gossyp_base::*;
 
@@ -17,174 +18,122 @@ impl CompareTool {
         CompareTool { }
     }
 
-    fn compare_array(array_values: &Vec<Value>, right: &Value) -> i32 {
-        match right {
-            &Value::Array(ref right_array) => {
-                if array_values.len() < right_array.len() {
-                    // Lengths differ
-                    -1
-                } else if array_values.len() > right_array.len() {
-                    // Lengths differ
-                    1
-                } else {
-                    // Check the values in the array
-                    for index in 0..array_values.len() {
-                        let compare = CompareTool::compare_values(&array_values[index], &right_array[index]);
-                        if compare != 0 {
-                            return compare;
-                        }
-                    }
-
-                    // Arrays are identical if we reach here
-                    0
-                }
-            },
-            &Value::Bool(_)     => -1,
-            &Value::Null        => -1,
-            &Value::Number(_)   => -1,
-            &Value::Object(_)   => -1,
-            &Value::String(_)   => -1
-        }
-    }
-
-    fn compare_bool(val: bool, right: &Value) -> i32 {
-        match right {
-            &Value::Array(_)        => 1,
-            &Value::Bool(right_val) => {
-                if val < right_val {
-                    -1
-                } else if val > right_val {
-                    1
-                } else {
-                    0
-                }
-            },
-            &Value::Null            => -1,
-            &Value::Number(_)       => -1,
-            &Value::Object(_)       => -1,
-            &Value::String(_)       => -1
+    ///
+    /// Returns the position of the type of a value in the ordering between values of different types
+    ///
+    /// Values of different types are ordered by type alone, giving the total order:
+    ///
+    /// `array < bool < null < number < object < string`
+    ///
+    /// Values of the same type are then compared by their contents.
+    ///
+    pub fn type_rank(value: &Value) -> u8 {
+        match value {
+            &Value::Array(_)    => 0,
+            &Value::Bool(_)     => 1,
+            &Value::Null        => 2,
+            &Value::Number(_)   => 3,
+            &Value::Object(_)   => 4,
+            &Value::String(_)   => 5
         }
     }
 
-    fn compare_null(right: &Value) -> i32 {
-        match right {
-            &Value::Array(_)    => 1,
-            &Value::Bool(_)     => 1,
-            &Value::Null        => 0,
-            &Value::Number(_)   => -1,
-            &Value::Object(_)   => -1,
-            &Value::String(_)   => -1
+    ///
+    /// Converts a Rust ordering into the -1, 0 or 1 returned by this tool
+    ///
+    fn ordering_to_i32(ordering: Ordering) -> i32 {
+        match ordering {
+            Ordering::Less      => -1,
+            Ordering::Equal     => 0,
+            Ordering::Greater   => 1
         }
     }
 
-    fn compare_number(num: &Number, right: &Value) -> i32 {
-        match right {
-            &Value::Array(_)                => 1,
-            &Value::Bool(_)                 => 1,
-            &Value::Null                    => 1,
-            &Value::Number(ref right_num)   => {
-                if let (Some(lnum), Some(rnum)) = (num.as_i64(), right_num.as_i64()) {
-                    // Try comparing as integers first
-                    if lnum < rnum {
-                        -1
-                    } else if lnum > rnum {
-                        1
-                    } else {
-                        0
-                    }
-                } else if let (Some(lnum), Some(rnum)) = (num.as_u64(), right_num.as_u64()) {
-                    // Possible that one side can only be represented as a u64, so try that too
-                    if lnum < rnum {
-                        -1
-                    } else if lnum > rnum {
-                        1
-                    } else {
-                        0
-                    }
-                } else if let (Some(lnum), Some(rnum)) = (num.as_f64(), right_num.as_f64()) {
-                    // If we can't compare as integers, try comparing as floats
-                    if lnum < rnum {
-                        -1
-                    } else if lnum > rnum {
-                        1
-                    } else {
-                        0
-                    }
-                } else {
-                    // Numbers don't have a common format!
-                    0
+    fn compare_array(array_values: &Vec<Value>, right_array: &Vec<Value>) -> i32 {
+        if array_values.len() < right_array.len() {
+            // Lengths differ
+            -1
+        } else if array_values.len() > right_array.len() {
+            // Lengths differ
+            1
+        } else {
+            // Check the values in the array
+            for index in 0..array_values.len() {
+                let compare = CompareTool::compare_values(&array_values[index], &right_array[index]);
+                if compare != 0 {
+                    return compare;
                 }
-            },
-            &Value::Object(_)               => -1,
-            &Value::String(_)               => -1
+            }
+
+            // Arrays are identical if we reach here
+            0
         }
     }
 
-    fn compare_object(obj: &Map<String, Value>, right: &Value) -> i32 {
-        match right {
-            &Value::Array(_)                => 1,
-            &Value::Bool(_)                 => 1,
-            &Value::Null                    => 1,
-            &Value::Number(_)               => 1,
-            &Value::Object(ref right_obj)   => {
-                let left_keys: Vec<&String>     = obj.keys().collect();
-                let right_keys: Vec<&String>    = right_obj.keys().collect();
-
-                if left_keys.len() < right_keys.len() {
-                    -1
-                } else if left_keys.len() > right_keys.len() {
-                    1
-                } else {
-                    for index in 0..left_keys.len() {
-                        if left_keys[index] < right_keys[index] {
-                            return -1;
-                        } else if left_keys[index] > right_keys[index] {
-                            return 1;
-                        } else {
-                            let compare = CompareTool::compare_values(&obj[left_keys[index]], &right_obj[left_keys[index]]);
-                            if compare != 0 {
-                                return compare;
-                            }
-                        }
-                    }
-
-                    0
-                }
-            },
-            &Value::String(_)               => -1
+    fn compare_number(num: &Number, right_num: &Number) -> i32 {
+        if let (Some(lnum), Some(rnum)) = (num.as_i64(), right_num.as_i64()) {
+            // Try comparing as integers first
+            CompareTool::ordering_to_i32(lnum.cmp(&rnum))
+        } else if let (Some(lnum), Some(rnum)) = (num.as_u64(), right_num.as_u64()) {
+            // Possible that one side can only be represented as a u64, so try that too
+            CompareTool::ordering_to_i32(lnum.cmp(&rnum))
+        } else if let (Some(lnum), Some(rnum)) = (num.as_f64(), right_num.as_f64()) {
+            // If we can't compare as integers, try comparing as floats
+            if lnum < rnum {
+                -1
+            } else if lnum > rnum {
+                1
+            } else {
+                0
+            }
+        } else {
+            // Numbers don't have a common format!
+            0
         }
     }
 
-    fn compare_string(s: &String, right: &Value) -> i32 {
-        match right {
-            &Value::Array(_)            => 1,
-            &Value::Bool(_)             => 1,
-            &Value::Null                => 1,
-            &Value::Number(_)           => 1,
-            &Value::Object(_)           => 1,
-            &Value::String(ref right_s) => {
-                if s < right_s {
-                    -1
-                } else if s > right_s {
-                    1
+    fn compare_object(obj: &Map<String, Value>, right_obj: &Map<String, Value>) -> i32 {
+        let left_keys: Vec<&String>     = obj.keys().collect();
+        let right_keys: Vec<&String>    = right_obj.keys().collect();
+
+        if left_keys.len() < right_keys.len() {
+            -1
+        } else if left_keys.len() > right_keys.len() {
+            1
+        } else {
+            for index in 0..left_keys.len() {
+                if left_keys[index] < right_keys[index] {
+                    return -1;
+                } else if left_keys[index] > right_keys[index] {
+                    return 1;
                 } else {
-                    0
+                    let compare = CompareTool::compare_values(&obj[left_keys[index]], &right_obj[left_keys[index]]);
+                    if compare != 0 {
+                        return compare;
+                    }
                 }
             }
+
+            0
         }
     }
 
     ///
     /// Compares two JSON values
     ///
-    fn compare_values(left: &Value, right: &Value) -> i32 {
-        match left {
-            &Value::Array(ref array_values) => CompareTool::compare_array(array_values, right),
-            &Value::Bool(val)               => CompareTool::compare_bool(val, right),
-            &Value::Null                    => CompareTool::compare_null(right),
-            &Value::Number(ref num)         => CompareTool::compare_number(num, right),
-            &Value::Object(ref obj)         => CompareTool::compare_object(obj, right),
-            &Value::String(ref s)           => CompareTool::compare_string(s, right)
+    /// See `type_rank` for how values of different types are ordered.
+    ///
+    pub fn compare_values(left: &Value, right: &Value) -> i32 {
+        match (left, right) {
+            (&Value::Array(ref left), &Value::Array(ref right))     => CompareTool::compare_array(left, right),
+            (&Value::Bool(left), &Value::Bool(right))               => CompareTool::ordering_to_i32(left.cmp(&right)),
+            (&Value::Null, &Value::Null)                            => 0,
+            (&Value::Number(ref left), &Value::Number(ref right))   => CompareTool::compare_number(left, right),
+            (&Value::Object(ref left), &Value::Object(ref right))   => CompareTool::compare_object(left, right),
+            (&Value::String(ref left), &Value::String(ref right))   => CompareTool::ordering_to_i32(left.cmp(right)),
+
+            // Values of different types are ordered by their type
+            (left, right) => CompareTool::ordering_to_i32(CompareTool::type_rank(left).cmp(&CompareTool::type_rank(right)))
         }
     }
 }
@@ -405,4 +354,28 @@ mod test {
             "foo": "bar"
         }] ] ], &env) == Ok(json![1]));
     }
+
+    #[test]
+    fn types_have_a_total_order() {
+        // One value of each type, in ascending order
+        let ordered = vec![ json![[ 1 ]], json![ true ], Value::Null, json![ 1 ], json![{ "a": 1 }], json![ "a" ] ];
+
+        for left in 0..ordered.len() {
+            for right in 0..ordered.len() {
+                let expected = if left < right { -1 } else if left > right { 1 } else { 0 };
+
+                assert!(CompareTool::compare_values(&ordered[left], &ordered[right]) == expected);
+            }
+        }
+    }
+
+    #[test]
+    fn type_order_does_not_depend_on_contents() {
+        // The largest value of a type is still less than the smallest value of the next type
+        assert!(CompareTool::compare_values(&json![[ "z", "z", "z" ]], &json![ false ]) == -1);
+        assert!(CompareTool::compare_values(&json![ true ], &Value::Null) == -1);
+        assert!(CompareTool::compare_values(&Value::Null, &json![ -1000 ]) == -1);
+        assert!(CompareTool::compare_values(&json![ 1e300 ], &json![{}]) == -1);
+        assert!(CompareTool::compare_values(&json![{ "z": "z" }], &json![ "" ]) == -1);
+    }
 }
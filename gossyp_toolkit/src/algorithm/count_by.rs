@@ -0,0 +1,148 @@
+//!
+//! The count-by tool counts how many times each distinct value occurs in an array
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Parameters that can be passed to the count-by tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct CountByParameters {
+    /// Values to count
+    values: Vec<Value>,
+
+    /// If present, the values are objects and this is the field whose value should be counted
+    by: Option<String>
+}
+
+impl CountByParameters {
+    pub fn new<'a>(values: Vec<Value>, by: Option<&'a str>) -> CountByParameters {
+        CountByParameters { values: values, by: by.map(|s| String::from(s)) }
+    }
+}
+
+///
+/// Tool that produces a histogram of the values in an array
+///
+/// The result is an object mapping each distinct value to the number of times it occurs. Strings
+/// are used as keys directly, other values are converted to their JSON representation. The keys
+/// are always ordered, so the result is deterministic.
+///
+pub struct CountByTool {
+}
+
+impl CountByTool {
+    ///
+    /// Creates a new count-by tool
+    ///
+    pub fn new() -> CountByTool {
+        CountByTool { }
+    }
+
+    ///
+    /// Returns the key to use for a value in the histogram
+    ///
+    fn key_for_value(value: &Value) -> String {
+        match value {
+            &Value::String(ref s)   => s.clone(),
+            other                   => to_string(other).unwrap_or(String::from("<Error>"))
+        }
+    }
+
+    ///
+    /// Counts the values in an array
+    ///
+    pub fn count<'a, TValues: Iterator<Item=&'a Value>>(values: TValues) -> Map<String, Value> {
+        let mut counts: Map<String, Value> = Map::new();
+
+        for value in values {
+            let key         = CountByTool::key_for_value(value);
+            let last_count  = counts.get(&key).and_then(|count| count.as_u64()).unwrap_or(0);
+
+            counts.insert(key, json![ last_count+1 ]);
+        }
+
+        counts
+    }
+
+    ///
+    /// Counts the values of a particular field in an array of objects
+    ///
+    pub fn count_field(values: &Vec<Value>, field: &str) -> Result<Map<String, Value>, Value> {
+        // Every value must be an object; fields that are missing are counted as null
+        let mut field_values = vec![];
+
+        for (index, value) in values.iter().enumerate() {
+            match value {
+                &Value::Object(ref obj) => field_values.push(obj.get(field).unwrap_or(&Value::Null)),
+                _                       => return Err(json![{
+                    "error":    "Values must be objects when counting by field",
+                    "index":    index
+                }])
+            }
+        }
+
+        Ok(CountByTool::count(field_values.into_iter()))
+    }
+}
+
+impl Tool for CountByTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<CountByParameters>(input);
+
+        match parameters {
+            Ok(CountByParameters { values, by: None })          => Ok(Value::Object(CountByTool::count(values.iter()))),
+            Ok(CountByParameters { values, by: Some(field) })   => CountByTool::count_field(&values, &field).map(|counts| Value::Object(counts)),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to count-by must be of the form { \"values\": <array>, \"by\": <field_name> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn can_count_values_with_duplicates() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<CountByParameters, Value>::from(Box::new(CountByTool::new()));
+
+        let result  = tool.invoke(CountByParameters::new(vec![ json!["a"], json!["b"], json!["a"], json![1], json![1], json!["a"] ], None), &env);
+
+        assert!(result == Ok(json![{ "1": 2, "a": 3, "b": 1 }]));
+    }
+
+    #[test]
+    fn can_count_by_field() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<CountByParameters, Value>::from(Box::new(CountByTool::new()));
+
+        let result  = tool.invoke(CountByParameters::new(vec![
+            json![{ "colour": "red", "size": 1 }],
+            json![{ "colour": "blue", "size": 2 }],
+            json![{ "colour": "red", "size": 3 }],
+            json![{ "size": 4 }]
+        ], Some("colour")), &env);
+
+        assert!(result == Ok(json![{ "blue": 1, "null": 1, "red": 2 }]));
+    }
+
+    #[test]
+    fn counting_non_objects_by_field_is_error() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<CountByParameters, Value>::from(Box::new(CountByTool::new()));
+
+        let result  = tool.invoke(CountByParameters::new(vec![ json![{ "colour": "red" }], json![2] ], Some("colour")), &env);
+
+        assert!(result.is_err());
+    }
+}
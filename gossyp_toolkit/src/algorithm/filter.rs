@@ -0,0 +1,147 @@
+//!
+//! The filter tool keeps the values in an array that pass a predicate tool
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+///
+/// Parameters that can be passed to the filter tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct FilterParameters {
+    /// Values to filter
+    values: Vec<Value>,
+
+    /// Name of the tool that decides whether or not to keep each value
+    predicate: String
+}
+
+impl FilterParameters {
+    pub fn new(values: Vec<Value>, predicate: &str) -> FilterParameters {
+        FilterParameters { values: values, predicate: String::from(predicate) }
+    }
+}
+
+///
+/// Tool that filters an array using a predicate tool
+///
+/// The predicate is invoked with each value in turn, and the value is kept if the predicate
+/// returns a truthy value (anything other than null, false, 0, an empty string or an empty
+/// array). If the predicate fails for any value, the whole filter fails with the index of that
+/// value.
+///
+pub struct FilterTool {
+}
+
+impl FilterTool {
+    ///
+    /// Creates a new filter tool
+    ///
+    pub fn new() -> FilterTool {
+        FilterTool { }
+    }
+
+    ///
+    /// Filters an array of JSON values
+    ///
+    pub fn filter(values: Vec<Value>, predicate: &Box<Tool>, environment: &Environment) -> Result<Vec<Value>, Value> {
+        let mut result = vec![];
+
+        for (index, value) in values.into_iter().enumerate() {
+            let keep = predicate.invoke_json(value.clone(), environment)
+                .map_err(|predicate_error| json![{
+                    "error":        "Predicate failed",
+                    "index":        index,
+                    "description":  predicate_error
+                }])?;
+
+            if is_truthy(&keep) {
+                result.push(value);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl Tool for FilterTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<FilterParameters>(input);
+
+        match parameters {
+            Ok(FilterParameters { values, predicate }) => {
+                let predicate_tool = environment.get_json_tool(&predicate).map_err(|retrieve_error| json![{
+                    "error":        "Predicate tool not found",
+                    "tool_name":    predicate,
+                    "description":  retrieve_error.message()
+                }])?;
+
+                FilterTool::filter(values, &predicate_tool, environment).map(|values| Value::Array(values))
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to filter must be of the form { \"values\": <array>, \"predicate\": <tool_name> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_environment() -> DynamicEnvironment {
+        let env = DynamicEnvironment::new();
+
+        env.define("is-even", Box::new(make_pure_tool(|x: i32| x%2 == 0)));
+        env.define("odd-or-null", Box::new(make_pure_tool(|x: i32| if x%2 != 0 { json![ x ] } else { Value::Null })));
+        env.define("small-only", Box::new(make_tool(|x: i32| -> Result<bool, &'static str> { if x < 10 { Ok(true) } else { Err("Too big") } })));
+
+        env
+    }
+
+    #[test]
+    fn keeps_values_that_pass_predicate() {
+        let env     = test_environment();
+        let tool    = TypedTool::<FilterParameters, Vec<i32>>::from(Box::new(FilterTool::new()));
+
+        assert!(tool.invoke(FilterParameters::new(vec![ json![1], json![2], json![3], json![4] ], "is-even"), &env) == Ok(vec![ 2, 4 ]));
+    }
+
+    #[test]
+    fn predicate_result_is_truthy() {
+        let env     = test_environment();
+        let tool    = FilterTool::new();
+
+        assert!(tool.invoke_json(json![{ "values": [ 1, 2, 3, 4 ], "predicate": "odd-or-null" }], &env) == Ok(json![[ 1, 3 ]]));
+    }
+
+    #[test]
+    fn predicate_error_includes_index() {
+        let env     = test_environment();
+        let tool    = FilterTool::new();
+
+        let result  = tool.invoke_json(json![{ "values": [ 1, 2, 30, 4 ], "predicate": "small-only" }], &env);
+
+        assert!(result == Err(json![{
+            "error":        "Predicate failed",
+            "index":        2,
+            "description":  "Too big"
+        }]));
+    }
+
+    #[test]
+    fn missing_predicate_is_error() {
+        let env     = test_environment();
+        let tool    = FilterTool::new();
+
+        let result  = tool.invoke_json(json![{ "values": [ 1 ], "predicate": "missing" }], &env);
+
+        assert!(result.unwrap_err()["tool_name"] == json![ "missing" ]);
+    }
+}
@@ -1,9 +1,25 @@
 pub mod compare;
 pub mod sort;
+pub mod count_by;
+pub mod transpose;
+pub mod repeat;
+pub mod chunk;
+pub mod set_operations;
+pub mod clamp;
+pub mod topo_sort;
+pub mod filter;
 pub mod tool;
 
 pub use self::compare::*;
 pub use self::sort::*;
+pub use self::count_by::*;
+pub use self::transpose::*;
+pub use self::repeat::*;
+pub use self::chunk::*;
+pub use self::set_operations::*;
+pub use self::clamp::*;
+pub use self::topo_sort::*;
+pub use self::filter::*;
 
 use gossyp_base::*;
 use gossyp_base::basic::*;
@@ -23,7 +39,18 @@ impl<'a> ToolSet for &'a AlgorithmTools {
     fn create_tools(self, _environment: &Environment) -> Vec<(String, Box<Tool>)> {
         vec![
             (String::from(self::tool::COMPARE_VALUES),  Box::new(CompareTool::new())),
-            (String::from(self::tool::SORT),            Box::new(SortTool::new()))
+            (String::from(self::tool::SORT),            Box::new(SortTool::new())),
+            (String::from(self::tool::COUNT_BY),        Box::new(CountByTool::new())),
+            (String::from(self::tool::TRANSPOSE),       Box::new(TransposeTool::new())),
+            (String::from(self::tool::REPEAT),          Box::new(RepeatTool::new())),
+            (String::from(self::tool::CHUNK),           Box::new(ChunkTool::new())),
+            (String::from(self::tool::UNION),           Box::new(SetOperationTool::new(SetOperation::Union))),
+            (String::from(self::tool::INTERSECT),       Box::new(SetOperationTool::new(SetOperation::Intersect))),
+            (String::from(self::tool::DIFFERENCE),      Box::new(SetOperationTool::new(SetOperation::Difference))),
+            (String::from(self::tool::CLAMP),           Box::new(ClampTool::new())),
+            (String::from(self::tool::BETWEEN),         Box::new(BetweenTool::new())),
+            (String::from(self::tool::TOPO_SORT),       Box::new(TopoSortTool::new())),
+            (String::from(self::tool::FILTER),          Box::new(FilterTool::new()))
         ]
     }
 }
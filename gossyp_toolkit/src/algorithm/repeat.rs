@@ -0,0 +1,122 @@
+//!
+//! The repeat tool builds an array containing several copies of a value
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// The largest array the repeat tool will create by default
+///
+pub const DEFAULT_MAX_REPEAT_COUNT: u64 = 1_000_000;
+
+///
+/// Parameters that can be passed to the repeat tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct RepeatParameters {
+    /// The value to repeat
+    value: Value,
+
+    /// The number of copies to make
+    count: i64
+}
+
+impl RepeatParameters {
+    pub fn new(value: Value, count: i64) -> RepeatParameters {
+        RepeatParameters { value: value, count: count }
+    }
+}
+
+///
+/// Tool that creates an array with a number of copies of a value
+///
+pub struct RepeatTool {
+    /// The largest count this tool will accept
+    max_count: u64
+}
+
+impl RepeatTool {
+    ///
+    /// Creates a new repeat tool with the default limit on the size of the array it can create
+    ///
+    pub fn new() -> RepeatTool {
+        RepeatTool::with_max_count(DEFAULT_MAX_REPEAT_COUNT)
+    }
+
+    ///
+    /// Creates a new repeat tool that will refuse to create arrays larger than a particular size
+    ///
+    pub fn with_max_count(max_count: u64) -> RepeatTool {
+        RepeatTool { max_count: max_count }
+    }
+
+    ///
+    /// Creates an array containing count copies of a value
+    ///
+    pub fn repeat(&self, value: Value, count: i64) -> Result<Vec<Value>, Value> {
+        if count < 0 {
+            Err(json![{
+                "error":    "The count passed to repeat must not be negative",
+                "count":    count
+            }])
+        } else if (count as u64) > self.max_count {
+            Err(json![{
+                "error":        "The count passed to repeat is too large",
+                "count":        count,
+                "max_count":    self.max_count
+            }])
+        } else {
+            Ok(vec![value; count as usize])
+        }
+    }
+}
+
+impl Tool for RepeatTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<RepeatParameters>(input);
+
+        match parameters {
+            Ok(RepeatParameters { value, count }) => self.repeat(value, count).map(|values| Value::Array(values)),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to repeat must be of the form { \"value\": <value>, \"count\": <number> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn can_repeat_value() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<RepeatParameters, Value>::from(Box::new(RepeatTool::new()));
+
+        assert!(tool.invoke(RepeatParameters::new(json![{ "a": 1 }], 3), &env) == Ok(json![[ { "a": 1 }, { "a": 1 }, { "a": 1 } ]]));
+        assert!(tool.invoke(RepeatParameters::new(json!["x"], 0), &env) == Ok(json![[]]));
+    }
+
+    #[test]
+    fn negative_count_is_error() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<RepeatParameters, Value>::from(Box::new(RepeatTool::new()));
+
+        assert!(tool.invoke(RepeatParameters::new(json!["x"], -1), &env).is_err());
+    }
+
+    #[test]
+    fn count_over_limit_is_error() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<RepeatParameters, Value>::from(Box::new(RepeatTool::with_max_count(10)));
+
+        assert!(tool.invoke(RepeatParameters::new(json!["x"], 10), &env).is_ok());
+        assert!(tool.invoke(RepeatParameters::new(json!["x"], 11), &env).is_err());
+    }
+}
@@ -0,0 +1,119 @@
+//!
+//! The union, intersect and difference tools perform set operations on arrays
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+use super::compare::*;
+
+///
+/// The set operations that can be performed by the set operation tool
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SetOperation {
+    /// Values that are in either array
+    Union,
+
+    /// Values that are in both arrays
+    Intersect,
+
+    /// Values that are in the first array but not the second
+    Difference
+}
+
+///
+/// Tool that performs a set operation on a pair of arrays
+///
+/// Values are considered equal if `compare-values` says they are. The result contains each value
+/// only once, in the order it was first seen.
+///
+pub struct SetOperationTool {
+    operation: SetOperation
+}
+
+impl SetOperationTool {
+    ///
+    /// Creates a new tool that performs the specified set operation
+    ///
+    pub fn new(operation: SetOperation) -> SetOperationTool {
+        SetOperationTool { operation: operation }
+    }
+
+    ///
+    /// True if an array contains a particular value
+    ///
+    fn contains(values: &Vec<Value>, value: &Value) -> bool {
+        values.iter().any(|existing| CompareTool::compare_values(existing, value) == 0)
+    }
+
+    ///
+    /// Performs a set operation on two arrays
+    ///
+    pub fn apply(operation: SetOperation, left: Vec<Value>, right: Vec<Value>) -> Vec<Value> {
+        // Decide which values are candidates for the result
+        let candidates = match operation {
+            SetOperation::Union         => left.into_iter().chain(right.into_iter()).collect::<Vec<_>>(),
+            SetOperation::Intersect     => left.into_iter().filter(|value| SetOperationTool::contains(&right, value)).collect(),
+            SetOperation::Difference    => left.into_iter().filter(|value| !SetOperationTool::contains(&right, value)).collect()
+        };
+
+        // Remove any duplicates
+        let mut result = vec![];
+        for value in candidates {
+            if !SetOperationTool::contains(&result, &value) {
+                result.push(value);
+            }
+        }
+
+        result
+    }
+}
+
+impl Tool for SetOperationTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        match from_value::<(Vec<Value>, Vec<Value>)>(input) {
+            Ok((left, right))   => Ok(Value::Array(SetOperationTool::apply(self.operation, left, right))),
+
+            Err(erm)            => Err(json![{
+                "error":        "Set operations must be called with two arrays",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    fn invoke(operation: SetOperation, left: Value, right: Value) -> Result<Value, Value> {
+        let env     = EmptyEnvironment::new();
+        let tool    = SetOperationTool::new(operation);
+
+        tool.invoke_json(json![[ left, right ]], &env)
+    }
+
+    #[test]
+    fn union_removes_duplicates() {
+        assert!(invoke(SetOperation::Union, json![[ 1, 2, 2, "a" ]], json![[ "a", 3, 1, { "b": 4 }, 3 ]]) == Ok(json![[ 1, 2, "a", 3, { "b": 4 } ]]));
+    }
+
+    #[test]
+    fn intersect_keeps_first_order() {
+        assert!(invoke(SetOperation::Intersect, json![[ 3, 1, 2, 1, [ 4 ] ]], json![[ 1, [ 4 ], 3, 3 ]]) == Ok(json![[ 3, 1, [ 4 ] ]]));
+    }
+
+    #[test]
+    fn difference_removes_values_in_second_array() {
+        assert!(invoke(SetOperation::Difference, json![[ 1, 2, 2, 3, 4 ]], json![[ 3, 1, 1 ]]) == Ok(json![[ 2, 4 ]]));
+    }
+
+    #[test]
+    fn non_array_input_is_error() {
+        assert!(invoke(SetOperation::Union, json![ 1 ], json![[ 1 ]]).is_err());
+    }
+}
@@ -30,6 +30,15 @@ impl SortParameters {
 ///
 /// Tool that can be used to sort JSON arrays
 ///
+/// The input is either an array or an object of the form `{ "array": <array>, "compare_tool": <tool_name> }`.
+/// `values` and `by` can be used in place of `array` and `compare_tool`. The compare tool is
+/// passed a two-element array and should return a negative number, 0 or a positive number; if
+/// none is supplied, values are ordered by the compare-values tool.
+///
+/// Set `descending` to true to sort from largest to smallest. Sorting is stable (values that
+/// compare as equal stay in the order they were supplied in, even when sorting in descending
+/// order) unless `stable` is set to false.
+///
 pub struct SortTool {
     /// Default comparison tool
     default_compare_tool: Box<Tool>
@@ -46,9 +55,15 @@ impl SortTool {
     ///
     /// Sorts an array of JSON values
     ///
-    pub fn sort(mut array: Vec<Value>, compare_tool: &Box<Tool>, environment: &Environment) -> Vec<Value> {
-        // Sort the array using the comparison tool for ordering
-        array.sort_by(|v1, v2| {
+    pub fn sort(array: Vec<Value>, compare_tool: &Box<Tool>, environment: &Environment) -> Vec<Value> {
+        SortTool::sort_with_options(array, compare_tool, false, true, environment)
+    }
+
+    ///
+    /// Sorts an array of JSON values, optionally in descending order or with an unstable sort
+    ///
+    pub fn sort_with_options(mut array: Vec<Value>, compare_tool: &Box<Tool>, descending: bool, stable: bool, environment: &Environment) -> Vec<Value> {
+        let compare = |v1: &Value, v2: &Value| {
             // Perform the comparison
             // We ignore errors from the comparison tool
             let compare_result  = compare_tool.invoke_json(json![ [ v1, v2 ] ], environment).unwrap_or(Value::Number(Number::from_f64(0.0).unwrap()));
@@ -56,14 +71,24 @@ impl SortTool {
 
             // Tool should return a number indicating ordering
             // (For now, we assume it's indicating equality in the case where it gives an error or a value other than a number)
-            if ordering < 0.0 {
+            let ordering = if ordering < 0.0 {
                 Ordering::Less
             } else if ordering > 0.0 {
                 Ordering::Greater
             } else {
                 Ordering::Equal
-            }
-        });
+            };
+
+            // Reversing the comparison rather than the result keeps equal values in their original order
+            if descending { ordering.reverse() } else { ordering }
+        };
+
+        // Sort the array using the comparison tool for ordering
+        if stable {
+            array.sort_by(compare);
+        } else {
+            array.sort_unstable_by(compare);
+        }
 
         array
     }
@@ -79,33 +104,40 @@ impl SortTool {
 impl Tool for SortTool {
     fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
         if let Value::Object(sort_parameters) = input {
-            if let Some(&Value::Array(ref array)) = sort_parameters.get("array") { 
+            // 'values' and 'by' are alternative names for 'array' and 'compare_tool'
+            let array               = sort_parameters.get("array").or_else(|| sort_parameters.get("values"));
+            let compare_tool_name   = sort_parameters.get("compare_tool").or_else(|| sort_parameters.get("by"));
+            let descending          = sort_parameters.get("descending").and_then(|descending| descending.as_bool()).unwrap_or(false);
+            let stable              = sort_parameters.get("stable").and_then(|stable| stable.as_bool()).unwrap_or(true);
+
+            if let Some(&Value::Array(ref array)) = array { 
                 // We either sort using the default ordering (ie, CompareTool) or a custom tool
-                if let Some(&Value::String(ref compare_tool_name)) = sort_parameters.get("compare_tool") {
+                if let Some(&Value::String(ref compare_tool_name)) = compare_tool_name {
                     // Tool comes from the current environment
                     let compare_tool = environment.get_json_tool(&compare_tool_name);
 
                     // Sort if we successfully fetched a tool, error if we did not
                     match compare_tool {
                         Ok(compare_tool) => {
-                            Ok(Value::Array(SortTool::sort(array.clone(), &compare_tool, environment)))
+                            Ok(Value::Array(SortTool::sort_with_options(array.clone(), &compare_tool, descending, stable, environment)))
                         },
 
                         Err(retrieve_error) => {
                             Err(json![ {
                                 "error":        "Compare tool not found",
+                                "tool_name":    compare_tool_name,
                                 "description":  retrieve_error.message()
                             } ])
                         }
                     }
                 } else {
                     // No compare tool name supplied
-                    Ok(Value::Array(SortTool::sort(array.clone(), &self.default_compare_tool, environment)))
+                    Ok(Value::Array(SortTool::sort_with_options(array.clone(), &self.default_compare_tool, descending, stable, environment)))
                 }
             } else {
                 // Parameters are an object but there's no array
                 Err(json![ {
-                    "error": "Parameters to sort-tool must be an array or of the form { \"array\": <array>, \"compare_tool\": <tool_name> }" 
+                    "error": "Parameters to sort-tool must be an array or of the form { \"array\": <array>, \"compare_tool\": <tool_name> } or { \"values\": <array>, \"by\": <tool_name> }" 
                 }])
             }
         } else if let Value::Array(array) = input {
@@ -113,7 +145,7 @@ impl Tool for SortTool {
             Ok(Value::Array(self.sort_default(array, environment)))
         } else {
             Err(json![ {
-                "error": "Parameters to sort-tool must be an array or of the form { \"array\": <array>, \"compare_tool\": <tool_name> }" 
+                "error": "Parameters to sort-tool must be an array or of the form { \"array\": <array>, \"compare_tool\": <tool_name> } or { \"values\": <array>, \"by\": <tool_name> }" 
             }])
         }
     }
@@ -160,4 +192,60 @@ mod test{
 
         assert!(tool.invoke(SortParameters::new(vec![ json![2], json![5], json![3], json![1], json![4] ], Some("missing")), &env).is_err());
     }
+
+    #[test]
+    fn can_sort_using_values_and_by() {
+        let env     = DynamicEnvironment::new();
+        let tool    = SortTool::new();
+
+        env.define("by-length", Box::new(make_pure_tool(|(a, b): (String, String)| -> i32 {
+            a.len() as i32 - b.len() as i32
+        })));
+
+        assert!(tool.invoke_json(json![{ "values": [ "ccc", "ab", "b" ], "by": "by-length" }], &env) == Ok(json![[ "b", "ab", "ccc" ]]));
+        assert!(tool.invoke_json(json![{ "values": [ "ccc", "ab", "b" ] }], &env) == Ok(json![[ "ab", "b", "ccc" ]]));
+    }
+
+    #[test]
+    fn missing_by_tool_is_error() {
+        let env     = EmptyEnvironment::new();
+        let tool    = SortTool::new();
+        let result  = tool.invoke_json(json![{ "values": [ 3, 1, 2 ], "by": "missing" }], &env);
+
+        assert!(result.clone().unwrap_err()["error"] == json![ "Compare tool not found" ]);
+        assert!(result.unwrap_err()["tool_name"] == json![ "missing" ]);
+    }
+
+    #[test]
+    fn can_sort_descending() {
+        let env     = EmptyEnvironment::new();
+        let tool    = SortTool::new();
+
+        assert!(tool.invoke_json(json![{ "values": [ 2, 5, 3, 1, 4 ], "descending": true }], &env) == Ok(json![[ 5, 4, 3, 2, 1 ]]));
+        assert!(tool.invoke_json(json![{ "values": [ 2, 5, 3, 1, 4 ], "descending": false }], &env) == Ok(json![[ 1, 2, 3, 4, 5 ]]));
+    }
+
+    #[test]
+    fn stable_sort_keeps_equal_keys_in_order() {
+        let env     = DynamicEnvironment::new();
+        let tool    = SortTool::new();
+
+        env.define("by-key", Box::new(make_pure_tool(|(a, b): (Value, Value)| -> i32 {
+            CompareTool::compare_values(&a["key"], &b["key"])
+        })));
+
+        let values = json![[
+            { "key": 2, "name": "a" },
+            { "key": 1, "name": "b" },
+            { "key": 2, "name": "c" },
+            { "key": 1, "name": "d" },
+            { "key": 2, "name": "e" }
+        ]];
+
+        let names = |result: Result<Value, Value>| result.unwrap().as_array().unwrap().iter().map(|value| value["name"].clone()).collect::<Vec<_>>();
+
+        assert!(names(tool.invoke_json(json![{ "values": values, "by": "by-key", "stable": true }], &env)) == vec![ "b", "d", "a", "c", "e" ]);
+        assert!(names(tool.invoke_json(json![{ "values": values, "by": "by-key", "stable": true, "descending": true }], &env)) == vec![ "a", "c", "e", "b", "d" ]);
+        assert!(names(tool.invoke_json(json![{ "values": values, "by": "by-key" }], &env)) == vec![ "b", "d", "a", "c", "e" ]);
+    }
 }
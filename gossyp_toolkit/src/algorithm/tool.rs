@@ -1,3 +1,14 @@
 
 pub const COMPARE_VALUES: &'static str  = "compare-values";
 pub const SORT: &'static str            = "sort";
+pub const COUNT_BY: &'static str        = "count-by";
+pub const TRANSPOSE: &'static str       = "transpose";
+pub const REPEAT: &'static str          = "repeat";
+pub const CHUNK: &'static str           = "chunk";
+pub const UNION: &'static str           = "union";
+pub const INTERSECT: &'static str       = "intersect";
+pub const DIFFERENCE: &'static str      = "difference";
+pub const CLAMP: &'static str           = "clamp";
+pub const BETWEEN: &'static str         = "between";
+pub const TOPO_SORT: &'static str       = "topo-sort";
+pub const FILTER: &'static str          = "filter";
@@ -0,0 +1,225 @@
+//!
+//! The topo-sort tool orders the nodes of a dependency graph so that every node comes after the nodes it depends on
+//!
+
+use std::result::Result;
+use std::error::Error;
+use std::collections::{HashMap, HashSet, VecDeque};
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Parameters that can be passed to the topo-sort tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct TopoSortParameters {
+    /// The nodes in the graph
+    nodes: Vec<String>,
+
+    /// The edges in the graph: `[a, b]` means that `a` must come before `b`
+    edges: Vec<(String, String)>
+}
+
+impl TopoSortParameters {
+    pub fn new(nodes: Vec<&str>, edges: Vec<(&str, &str)>) -> TopoSortParameters {
+        TopoSortParameters {
+            nodes: nodes.into_iter().map(|node| String::from(node)).collect(),
+            edges: edges.into_iter().map(|(from, to)| (String::from(from), String::from(to))).collect()
+        }
+    }
+}
+
+///
+/// Tool that sorts the nodes of a graph into an order where every edge points forwards
+///
+/// This uses Kahn's algorithm: nodes are output in the order they become ready, starting with the
+/// nodes that have no dependencies in the order they were supplied in. If the graph contains a
+/// cycle, the result is an error listing the nodes in one of the cycles, in the order the edges
+/// visit them. Each node can only be listed once.
+///
+pub struct TopoSortTool {
+}
+
+impl TopoSortTool {
+    ///
+    /// Creates a new topo-sort tool
+    ///
+    pub fn new() -> TopoSortTool {
+        TopoSortTool { }
+    }
+
+    ///
+    /// Sorts a set of nodes into dependency order
+    ///
+    pub fn sort(nodes: &Vec<String>, edges: &Vec<(String, String)>) -> Result<Vec<String>, Value> {
+        let mut known_nodes = HashSet::new();
+
+        for node in nodes.iter() {
+            if !known_nodes.insert(node) {
+                return Err(json![{
+                    "error":    "Node appears more than once in the graph",
+                    "node":     node
+                }]);
+            }
+        }
+
+        // Count the incoming edges for each node
+        let mut in_degree       = nodes.iter().map(|node| (node, 0)).collect::<HashMap<_, _>>();
+        let mut successors      = HashMap::new();
+        let mut predecessors    = HashMap::new();
+
+        for &(ref from, ref to) in edges.iter() {
+            for node in [ from, to ].iter() {
+                if !known_nodes.contains(node) {
+                    return Err(json![{
+                        "error":    "Edge refers to a node that is not in the graph",
+                        "edge":     [ from, to ],
+                        "node":     node
+                    }]);
+                }
+            }
+
+            *in_degree.get_mut(to).unwrap() += 1;
+            successors.entry(from).or_insert_with(|| vec![]).push(to);
+            predecessors.entry(to).or_insert_with(|| vec![]).push(from);
+        }
+
+        // Repeatedly take nodes that have no remaining dependencies
+        let mut ready   = nodes.iter().filter(|node| in_degree[node] == 0).collect::<VecDeque<_>>();
+        let mut sorted  = vec![];
+
+        while let Some(node) = ready.pop_front() {
+            sorted.push(node.clone());
+
+            for successor in successors.get(node).unwrap_or(&vec![]) {
+                let degree = in_degree.get_mut(successor).unwrap();
+                *degree -= 1;
+
+                if *degree == 0 {
+                    ready.push_back(successor);
+                }
+            }
+        }
+
+        if sorted.len() == nodes.len() {
+            Ok(sorted)
+        } else {
+            match TopoSortTool::find_cycle(nodes, &in_degree, &predecessors) {
+                Some(cycle) => Err(json![{
+                    "error":    "Graph contains a cycle",
+                    "cycle":    cycle
+                }]),
+
+                None        => Err(json![{
+                    "error":    "Graph contains a cycle"
+                }])
+            }
+        }
+    }
+
+    ///
+    /// Finds a cycle amongst the nodes that Kahn's algorithm couldn't sort
+    ///
+    /// Every unsorted node has a predecessor that is also unsorted, so following predecessors
+    /// from any of them must eventually revisit a node. This returns None if there are no
+    /// unsorted nodes to start from.
+    ///
+    fn find_cycle(nodes: &Vec<String>, in_degree: &HashMap<&String, i32>, predecessors: &HashMap<&String, Vec<&String>>) -> Option<Vec<String>> {
+        let is_unsorted = |node: &String| in_degree[node] > 0;
+
+        let mut path    = vec![];
+        let mut visited = HashMap::new();
+        let mut node    = nodes.iter().filter(|node| is_unsorted(node)).nth(0)?;
+
+        while !visited.contains_key(node) {
+            visited.insert(node, path.len());
+            path.push(node);
+
+            node = predecessors.get(node)?.iter().filter(|predecessor| is_unsorted(predecessor)).nth(0)?;
+        }
+
+        // The path was built by walking edges backwards, so reverse the cycle to put it in edge order
+        let mut cycle = vec![ node.clone() ];
+        cycle.extend(path[visited[node]+1..].iter().rev().map(|node| (*node).clone()));
+
+        Some(cycle)
+    }
+}
+
+impl Tool for TopoSortTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<TopoSortParameters>(input);
+
+        match parameters {
+            Ok(TopoSortParameters { nodes, edges }) => TopoSortTool::sort(&nodes, &edges).map(|sorted| json![ sorted ]),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to topo-sort must be of the form { \"nodes\": [ <name> ], \"edges\": [ [ <from>, <to> ] ] }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn can_sort_linear_chain() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<TopoSortParameters, Value>::from(Box::new(TopoSortTool::new()));
+
+        let result  = tool.invoke(TopoSortParameters::new(vec![ "c", "a", "b" ], vec![ ("a", "b"), ("b", "c") ]), &env);
+
+        assert!(result == Ok(json![[ "a", "b", "c" ]]));
+    }
+
+    #[test]
+    fn can_sort_diamond() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<TopoSortParameters, Value>::from(Box::new(TopoSortTool::new()));
+
+        let result  = tool.invoke(TopoSortParameters::new(vec![ "d", "c", "b", "a" ], vec![ ("a", "b"), ("a", "c"), ("b", "d"), ("c", "d") ]), &env);
+
+        assert!(result == Ok(json![[ "a", "b", "c", "d" ]]));
+    }
+
+    #[test]
+    fn cycle_is_error() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TopoSortTool::new();
+
+        let result  = tool.invoke_json(json![{
+            "nodes": [ "start", "a", "b", "c", "end" ],
+            "edges": [ [ "start", "a" ], [ "a", "b" ], [ "b", "c" ], [ "c", "a" ], [ "c", "end" ] ]
+        }], &env);
+
+        assert!(result == Err(json![{
+            "error":    "Graph contains a cycle",
+            "cycle":    [ "a", "b", "c" ]
+        }]));
+    }
+
+    #[test]
+    fn duplicate_node_is_error() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TopoSortTool::new();
+
+        let result  = tool.invoke_json(json![{ "nodes": [ "a", "b", "b" ], "edges": [ [ "a", "b" ] ] }], &env);
+
+        assert!(result == Err(json![{
+            "error":    "Node appears more than once in the graph",
+            "node":     "b"
+        }]));
+    }
+
+    #[test]
+    fn unknown_node_is_error() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TopoSortTool::new();
+
+        assert!(tool.invoke_json(json![{ "nodes": [ "a" ], "edges": [ [ "a", "b" ] ] }], &env).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+//!
+//! The transpose tool swaps the rows and columns of a 2D array
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Tool that transposes an array of equal-length arrays
+///
+pub struct TransposeTool {
+}
+
+impl TransposeTool {
+    ///
+    /// Creates a new transpose tool
+    ///
+    pub fn new() -> TransposeTool {
+        TransposeTool { }
+    }
+
+    ///
+    /// Transposes a matrix, which must be rectangular
+    ///
+    pub fn transpose(rows: Vec<Vec<Value>>) -> Result<Vec<Vec<Value>>, Value> {
+        // The first row determines the number of columns
+        let num_columns = rows.get(0).map(|row| row.len()).unwrap_or(0);
+
+        for (index, row) in rows.iter().enumerate() {
+            if row.len() != num_columns {
+                return Err(json![{
+                    "error":            "All rows passed to transpose must be the same length",
+                    "row":              index,
+                    "length":           row.len(),
+                    "expected_length":  num_columns
+                }]);
+            }
+        }
+
+        // Move the values into their new positions
+        let mut columns: Vec<Vec<Value>> = (0..num_columns).map(|_| Vec::with_capacity(rows.len())).collect();
+
+        for row in rows {
+            for (column, value) in columns.iter_mut().zip(row.into_iter()) {
+                column.push(value);
+            }
+        }
+
+        Ok(columns)
+    }
+}
+
+impl Tool for TransposeTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let rows = from_value::<Vec<Vec<Value>>>(input);
+
+        match rows {
+            Ok(rows) => TransposeTool::transpose(rows).map(|columns| json![ columns ]),
+
+            Err(erm) => Err(json![{
+                "error":        "Transpose must be called with an array of arrays",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn can_transpose_square_matrix() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<Vec<Vec<i32>>, Vec<Vec<i32>>>::from(Box::new(TransposeTool::new()));
+
+        assert!(tool.invoke(vec![ vec![1, 2], vec![3, 4] ], &env) == Ok(vec![ vec![1, 3], vec![2, 4] ]));
+    }
+
+    #[test]
+    fn can_transpose_rectangular_matrix() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TransposeTool::new();
+
+        assert!(tool.invoke_json(json![[ [1, 2, 3], ["a", "b", "c"] ]], &env) == Ok(json![[ [1, "a"], [2, "b"], [3, "c"] ]]));
+    }
+
+    #[test]
+    fn ragged_matrix_is_error() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TransposeTool::new();
+
+        let result  = tool.invoke_json(json![[ [1, 2], [3, 4], [5] ]], &env);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err()["row"] == json![2]);
+    }
+}
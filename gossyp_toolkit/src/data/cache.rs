@@ -0,0 +1,286 @@
+//!
+//! The cache tools store values that scripts want to keep around between calls
+//!
+
+use std::result::Result;
+use std::error::Error;
+use std::sync::*;
+use std::collections::HashMap;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Parameters that can be passed to the cache-get tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct CacheGetParameters {
+    /// The key of the value to retrieve
+    pub key: Value
+}
+
+///
+/// Parameters that can be passed to the cache-set tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct CacheSetParameters {
+    /// The key to store the value under
+    pub key: Value,
+
+    /// The value to store
+    pub value: Value
+}
+
+///
+/// Parameters that can be passed to the cache-clear tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct CacheClearParameters {
+    /// The key to remove (or None to remove everything)
+    pub key: Option<Value>
+}
+
+impl CacheGetParameters {
+    pub fn new(key: Value) -> CacheGetParameters {
+        CacheGetParameters { key: key }
+    }
+}
+
+impl CacheSetParameters {
+    pub fn new(key: Value, value: Value) -> CacheSetParameters {
+        CacheSetParameters { key: key, value: value }
+    }
+}
+
+impl CacheClearParameters {
+    pub fn new(key: Option<Value>) -> CacheClearParameters {
+        CacheClearParameters { key: key }
+    }
+}
+
+///
+/// The values stored in a cache, shared between the cache tools
+///
+/// Keys can be any JSON value: two keys are the same if they serialize to the same JSON.
+///
+#[derive(Clone)]
+pub struct Cache {
+    values: Arc<Mutex<HashMap<String, Value>>>
+}
+
+///
+/// Tool that retrieves a value from the cache, returning null if there isn't one
+///
+pub struct CacheGetTool {
+    cache: Cache
+}
+
+///
+/// Tool that stores a value in the cache (replacing any value that was already stored for its key)
+///
+pub struct CacheSetTool {
+    cache: Cache
+}
+
+///
+/// Tool that removes a single key from the cache, or every key if none is specified
+///
+/// The input can be null or `{ "key": <key> }`. As a null key can't be told apart from a missing
+/// one, `{ "key": null }` also clears every key.
+///
+pub struct CacheClearTool {
+    cache: Cache
+}
+
+impl Cache {
+    ///
+    /// Creates a new empty cache
+    ///
+    pub fn new() -> Cache {
+        Cache { values: Arc::new(Mutex::new(HashMap::new())) }
+    }
+
+    ///
+    /// Retrieves the value stored for a key
+    ///
+    pub fn get(&self, key: &Value) -> Option<Value> {
+        self.values.lock().unwrap().get(&key.to_string()).cloned()
+    }
+
+    ///
+    /// Stores a value for a key
+    ///
+    pub fn set(&self, key: &Value, value: Value) {
+        self.values.lock().unwrap().insert(key.to_string(), value);
+    }
+
+    ///
+    /// Removes the value stored for a key
+    ///
+    pub fn remove(&self, key: &Value) {
+        self.values.lock().unwrap().remove(&key.to_string());
+    }
+
+    ///
+    /// Removes every value from the cache
+    ///
+    pub fn clear(&self) {
+        self.values.lock().unwrap().clear();
+    }
+}
+
+impl CacheGetTool {
+    ///
+    /// Creates a new cache-get tool
+    ///
+    pub fn new(cache: Cache) -> CacheGetTool {
+        CacheGetTool { cache: cache }
+    }
+}
+
+impl CacheSetTool {
+    ///
+    /// Creates a new cache-set tool
+    ///
+    pub fn new(cache: Cache) -> CacheSetTool {
+        CacheSetTool { cache: cache }
+    }
+}
+
+impl CacheClearTool {
+    ///
+    /// Creates a new cache-clear tool
+    ///
+    pub fn new(cache: Cache) -> CacheClearTool {
+        CacheClearTool { cache: cache }
+    }
+}
+
+impl Tool for CacheGetTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<CacheGetParameters>(input);
+
+        match parameters {
+            Ok(CacheGetParameters { key }) => Ok(self.cache.get(&key).unwrap_or(Value::Null)),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to cache-get must be of the form { \"key\": <key> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+impl Tool for CacheSetTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<CacheSetParameters>(input);
+
+        match parameters {
+            Ok(CacheSetParameters { key, value }) => {
+                self.cache.set(&key, value);
+                Ok(Value::Null)
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to cache-set must be of the form { \"key\": <key>, \"value\": <value> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+impl Tool for CacheClearTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        // Null input clears everything
+        let parameters = match input {
+            Value::Null => Ok(CacheClearParameters { key: None }),
+            input       => from_value::<CacheClearParameters>(input)
+        };
+
+        match parameters {
+            Ok(CacheClearParameters { key: Some(key) }) => {
+                self.cache.remove(&key);
+                Ok(Value::Null)
+            },
+
+            Ok(CacheClearParameters { key: None }) => {
+                self.cache.clear();
+                Ok(Value::Null)
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to cache-clear must be null or of the form { \"key\": <key> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    fn cache_tools() -> (CacheGetTool, CacheSetTool, CacheClearTool) {
+        let cache = Cache::new();
+
+        (CacheGetTool::new(cache.clone()), CacheSetTool::new(cache.clone()), CacheClearTool::new(cache))
+    }
+
+    #[test]
+    fn can_set_and_get() {
+        let env                 = EmptyEnvironment::new();
+        let (get, set, _clear)  = cache_tools();
+
+        assert!(get.invoke_json(json![{ "key": "answer" }], &env) == Ok(Value::Null));
+        assert!(set.invoke_json(json![{ "key": "answer", "value": 42 }], &env) == Ok(Value::Null));
+        assert!(set.invoke_json(json![{ "key": [ 1, 2 ], "value": { "sum": 3 } }], &env) == Ok(Value::Null));
+
+        assert!(get.invoke_json(json![{ "key": "answer" }], &env) == Ok(json![ 42 ]));
+        assert!(get.invoke_json(json![{ "key": [ 1, 2 ] }], &env) == Ok(json![{ "sum": 3 }]));
+        assert!(get.invoke_json(json![{ "key": [ 2, 1 ] }], &env) == Ok(Value::Null));
+    }
+
+    #[test]
+    fn set_replaces_value() {
+        let env                 = EmptyEnvironment::new();
+        let (get, set, _clear)  = cache_tools();
+        let get                 = TypedTool::<CacheGetParameters, Value>::from(Box::new(get));
+        let set                 = TypedTool::<CacheSetParameters, ()>::from(Box::new(set));
+
+        set.invoke(CacheSetParameters::new(json![ "answer" ], json![ 1 ]), &env).unwrap();
+        set.invoke(CacheSetParameters::new(json![ "answer" ], json![ 2 ]), &env).unwrap();
+
+        assert!(get.invoke(CacheGetParameters::new(json![ "answer" ]), &env) == Ok(json![ 2 ]));
+    }
+
+    #[test]
+    fn can_clear_one_key() {
+        let env                 = EmptyEnvironment::new();
+        let (get, set, clear)   = cache_tools();
+
+        set.invoke_json(json![{ "key": "a", "value": 1 }], &env).unwrap();
+        set.invoke_json(json![{ "key": "b", "value": 2 }], &env).unwrap();
+
+        assert!(clear.invoke_json(json![{ "key": "a" }], &env) == Ok(Value::Null));
+
+        assert!(get.invoke_json(json![{ "key": "a" }], &env) == Ok(Value::Null));
+        assert!(get.invoke_json(json![{ "key": "b" }], &env) == Ok(json![ 2 ]));
+    }
+
+    #[test]
+    fn clear_without_key_empties_cache() {
+        let env                 = EmptyEnvironment::new();
+        let (get, set, clear)   = cache_tools();
+
+        set.invoke_json(json![{ "key": "a", "value": 1 }], &env).unwrap();
+        set.invoke_json(json![{ "key": "b", "value": 2 }], &env).unwrap();
+
+        assert!(clear.invoke_json(Value::Null, &env) == Ok(Value::Null));
+        assert!(get.invoke_json(json![{ "key": "a" }], &env) == Ok(Value::Null));
+        assert!(get.invoke_json(json![{ "key": "b" }], &env) == Ok(Value::Null));
+
+        set.invoke_json(json![{ "key": "a", "value": 1 }], &env).unwrap();
+        assert!(clear.invoke_json(json![{}], &env) == Ok(Value::Null));
+        assert!(get.invoke_json(json![{ "key": "a" }], &env) == Ok(Value::Null));
+    }
+}
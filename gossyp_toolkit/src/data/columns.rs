@@ -0,0 +1,185 @@
+//!
+//! The columns and rows tools convert between arrays of objects and objects of arrays
+//!
+
+use std::result::Result;
+use std::error::Error;
+use std::collections::BTreeSet;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Tool that converts an array of objects (rows) into an object of arrays (columns)
+///
+/// Every field that appears in any row becomes a column. Rows that are missing a field have
+/// null in that column, so every column is the same length as the original array. The columns
+/// are always ordered by field name and the values keep the order of the rows, so the result is
+/// deterministic.
+///
+pub struct ColumnsTool {
+}
+
+///
+/// Tool that converts an object of arrays (columns) into an array of objects (rows)
+///
+/// This is the inverse of the columns tool. All the columns must be the same length.
+///
+pub struct RowsTool {
+}
+
+impl ColumnsTool {
+    ///
+    /// Creates a new columns tool
+    ///
+    pub fn new() -> ColumnsTool {
+        ColumnsTool { }
+    }
+
+    ///
+    /// Converts a set of rows into a set of columns
+    ///
+    pub fn columns(rows: Vec<Map<String, Value>>) -> Map<String, Value> {
+        // Every field that's in any row becomes a column
+        let fields: BTreeSet<String> = rows.iter()
+            .flat_map(|row| row.keys().cloned())
+            .collect();
+
+        // Read the field values from each row in turn, using null for fields that are missing
+        let mut columns: Vec<Vec<Value>> = fields.iter().map(|_| Vec::with_capacity(rows.len())).collect();
+
+        for mut row in rows {
+            for (field, column) in fields.iter().zip(columns.iter_mut()) {
+                column.push(row.remove(field).unwrap_or(Value::Null));
+            }
+        }
+
+        fields.into_iter()
+            .zip(columns.into_iter().map(|column| Value::Array(column)))
+            .collect()
+    }
+}
+
+impl RowsTool {
+    ///
+    /// Creates a new rows tool
+    ///
+    pub fn new() -> RowsTool {
+        RowsTool { }
+    }
+
+    ///
+    /// Converts a set of columns into a set of rows
+    ///
+    pub fn rows(columns: Map<String, Value>) -> Result<Vec<Map<String, Value>>, Value> {
+        let mut num_rows = None;
+
+        for (field, column) in columns.iter() {
+            let length = match column {
+                &Value::Array(ref values)   => values.len(),
+                _                           => return Err(json![{
+                    "error":    "The columns passed to rows must all be arrays",
+                    "column":   field
+                }])
+            };
+
+            // The first column determines the number of rows
+            let expected_length = *num_rows.get_or_insert(length);
+
+            if length != expected_length {
+                return Err(json![{
+                    "error":            "All columns passed to rows must be the same length",
+                    "column":           field,
+                    "length":           length,
+                    "expected_length":  expected_length
+                }]);
+            }
+        }
+
+        // Move the values into their rows
+        let mut rows: Vec<Map<String, Value>> = (0..num_rows.unwrap_or(0)).map(|_| Map::new()).collect();
+
+        for (field, column) in columns {
+            if let Value::Array(values) = column {
+                for (row, value) in rows.iter_mut().zip(values.into_iter()) {
+                    row.insert(field.clone(), value);
+                }
+            }
+        }
+
+        Ok(rows)
+    }
+}
+
+impl Tool for ColumnsTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let rows = from_value::<Vec<Map<String, Value>>>(input);
+
+        match rows {
+            Ok(rows) => Ok(Value::Object(ColumnsTool::columns(rows))),
+
+            Err(erm) => Err(json![{
+                "error":        "Columns must be called with an array of objects",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+impl Tool for RowsTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let columns = from_value::<Map<String, Value>>(input);
+
+        match columns {
+            Ok(columns) => RowsTool::rows(columns).map(|rows| json![ rows ]),
+
+            Err(erm) => Err(json![{
+                "error":        "Rows must be called with an object of arrays",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn can_convert_rows_to_columns_and_back() {
+        let env     = EmptyEnvironment::new();
+        let columns = ColumnsTool::new();
+        let rows    = RowsTool::new();
+
+        let original    = json![[ { "name": "a", "size": 1 }, { "name": "b", "size": 2 }, { "name": "c", "size": 3 } ]];
+        let as_columns  = columns.invoke_json(original.clone(), &env);
+
+        assert!(as_columns == Ok(json![{ "name": [ "a", "b", "c" ], "size": [ 1, 2, 3 ] }]));
+        assert!(rows.invoke_json(as_columns.unwrap(), &env) == Ok(original));
+    }
+
+    #[test]
+    fn missing_fields_are_null() {
+        let env     = EmptyEnvironment::new();
+        let tool    = ColumnsTool::new();
+
+        let result  = tool.invoke_json(json![[ { "name": "a" }, { "size": 2 }, { "name": "c", "size": 3 } ]], &env);
+
+        assert!(result == Ok(json![{ "name": [ "a", null, "c" ], "size": [ null, 2, 3 ] }]));
+    }
+
+    #[test]
+    fn ragged_columns_are_error() {
+        let env     = EmptyEnvironment::new();
+        let tool    = RowsTool::new();
+
+        let result  = tool.invoke_json(json![{ "name": [ "a", "b" ], "size": [ 1 ] }], &env);
+
+        assert!(result == Err(json![{
+            "error":            "All columns passed to rows must be the same length",
+            "column":           "size",
+            "length":           1,
+            "expected_length":  2
+        }]));
+    }
+}
@@ -0,0 +1,149 @@
+//!
+//! The deep-map-values tool applies a tool to every scalar value in a nested structure
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+///
+/// Parameters that can be passed to the deep-map-values tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct DeepMapValuesParameters {
+    /// The value to transform
+    pub value: Value,
+
+    /// The name of the tool to apply to each scalar value
+    pub tool: String
+}
+
+impl DeepMapValuesParameters {
+    pub fn new(value: Value, tool: &str) -> DeepMapValuesParameters {
+        DeepMapValuesParameters { value: value, tool: String::from(tool) }
+    }
+}
+
+///
+/// Tool that applies a tool to every scalar value in a nested structure
+///
+/// Objects and arrays are traversed and rebuilt with the same shape, so only the values at the
+/// leaves are changed. If the tool fails, the error contains the path of the value that it
+/// failed on, with the keys and indexes separated by '.'.
+///
+pub struct DeepMapValuesTool {
+}
+
+impl DeepMapValuesTool {
+    ///
+    /// Creates a new deep-map-values tool
+    ///
+    pub fn new() -> DeepMapValuesTool {
+        DeepMapValuesTool { }
+    }
+
+    ///
+    /// Returns the path to an item within a container
+    ///
+    fn item_path(path: &str, item: &str) -> String {
+        if path.len() == 0 {
+            String::from(item)
+        } else {
+            format!("{}.{}", path, item)
+        }
+    }
+
+    ///
+    /// Applies a tool to the scalar values in a value, which is found at the specified path
+    ///
+    pub fn map_values(value: Value, tool: &Tool, path: &str, environment: &Environment) -> Result<Value, Value> {
+        match value {
+            Value::Array(items) => {
+                let mut result = vec![];
+
+                for (index, item) in items.into_iter().enumerate() {
+                    let item_path = DeepMapValuesTool::item_path(path, &index.to_string());
+                    result.push(DeepMapValuesTool::map_values(item, tool, &item_path, environment)?);
+                }
+
+                Ok(Value::Array(result))
+            },
+
+            Value::Object(fields) => {
+                let mut result = Map::new();
+
+                for (key, item) in fields.into_iter() {
+                    let item_path = DeepMapValuesTool::item_path(path, &key);
+                    result.insert(key, DeepMapValuesTool::map_values(item, tool, &item_path, environment)?);
+                }
+
+                Ok(Value::Object(result))
+            },
+
+            scalar => tool.invoke_json(scalar, environment).map_err(|tool_error| json![{
+                "error":        "Tool failed while mapping values",
+                "path":         path,
+                "tool_error":   tool_error
+            }])
+        }
+    }
+}
+
+impl Tool for DeepMapValuesTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<DeepMapValuesParameters>(input);
+
+        match parameters {
+            Ok(DeepMapValuesParameters { value, tool }) => {
+                let tool = get_tool_or_error(environment, &tool)?;
+
+                DeepMapValuesTool::map_values(value, &*tool, "", environment)
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to deep-map-values must be of the form { \"value\": <value>, \"tool\": <tool_name> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_environment() -> StaticEnvironment {
+        StaticEnvironment::from_tool("double", Box::new(make_pure_tool(|x: i32| x*2)))
+    }
+
+    #[test]
+    fn can_map_nested_values() {
+        let env     = test_environment();
+        let tool    = TypedTool::<DeepMapValuesParameters, Value>::from(Box::new(DeepMapValuesTool::new()));
+
+        let result  = tool.invoke(DeepMapValuesParameters::new(json![{
+            "a": 1,
+            "b": [ 2, { "c": 3 }, [] ],
+            "d": {}
+        }], "double"), &env);
+
+        assert!(result == Ok(json![{
+            "a": 2,
+            "b": [ 4, { "c": 6 }, [] ],
+            "d": {}
+        }]));
+    }
+
+    #[test]
+    fn error_contains_path() {
+        let env     = test_environment();
+        let tool    = TypedTool::<DeepMapValuesParameters, Value>::from(Box::new(DeepMapValuesTool::new()));
+
+        let result  = tool.invoke(DeepMapValuesParameters::new(json![{ "a": [ 1, "two" ] }], "double"), &env);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err()["path"] == json!["a.1"]);
+    }
+}
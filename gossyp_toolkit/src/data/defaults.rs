@@ -0,0 +1,126 @@
+//!
+//! The defaults tool fills in the fields that are missing from an object
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Parameters that can be passed to the defaults tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct DefaultsParameters {
+    /// The object to fill in
+    value: Map<String, Value>,
+
+    /// The default values for any fields that are missing from the value
+    defaults: Map<String, Value>,
+
+    /// If false, fields that are explicitly null are left alone (the default is to replace them)
+    null_is_absent: Option<bool>
+}
+
+impl DefaultsParameters {
+    pub fn new(value: Map<String, Value>, defaults: Map<String, Value>, null_is_absent: Option<bool>) -> DefaultsParameters {
+        DefaultsParameters { value: value, defaults: defaults, null_is_absent: null_is_absent }
+    }
+}
+
+///
+/// Tool that fills in missing fields in an object from a set of defaults
+///
+/// This is a shallow merge: fields that are present in the value are never replaced, even if
+/// they are objects themselves.
+///
+pub struct DefaultsTool {
+}
+
+impl DefaultsTool {
+    ///
+    /// Creates a new defaults tool
+    ///
+    pub fn new() -> DefaultsTool {
+        DefaultsTool { }
+    }
+
+    ///
+    /// Adds any fields from a set of defaults that are absent in a value
+    ///
+    pub fn apply_defaults(mut value: Map<String, Value>, defaults: Map<String, Value>, null_is_absent: bool) -> Map<String, Value> {
+        for (key, default_value) in defaults {
+            let is_absent = match value.get(&key) {
+                None                => true,
+                Some(&Value::Null)  => null_is_absent,
+                Some(_)             => false
+            };
+
+            if is_absent {
+                value.insert(key, default_value);
+            }
+        }
+
+        value
+    }
+}
+
+impl Tool for DefaultsTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<DefaultsParameters>(input);
+
+        match parameters {
+            Ok(DefaultsParameters { value, defaults, null_is_absent }) => {
+                Ok(Value::Object(DefaultsTool::apply_defaults(value, defaults, null_is_absent.unwrap_or(true))))
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to defaults must be of the form { \"value\": <object>, \"defaults\": <object> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    fn object(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map)  => map,
+            _                   => panic!("Not an object")
+        }
+    }
+
+    #[test]
+    fn present_keys_are_not_replaced() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<DefaultsParameters, Value>::from(Box::new(DefaultsTool::new()));
+
+        let result  = tool.invoke(DefaultsParameters::new(object(json![{ "port": 8080, "host": { "name": "a" } }]), object(json![{ "port": 80, "host": { "name": "b", "ip": "::1" } }]), None), &env);
+
+        assert!(result == Ok(json![{ "port": 8080, "host": { "name": "a" } }]));
+    }
+
+    #[test]
+    fn missing_keys_are_filled_in() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<DefaultsParameters, Value>::from(Box::new(DefaultsTool::new()));
+
+        let result  = tool.invoke(DefaultsParameters::new(object(json![{ "port": 8080, "host": null }]), object(json![{ "host": "localhost", "verbose": false }]), None), &env);
+
+        assert!(result == Ok(json![{ "port": 8080, "host": "localhost", "verbose": false }]));
+    }
+
+    #[test]
+    fn explicit_null_can_be_kept() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<DefaultsParameters, Value>::from(Box::new(DefaultsTool::new()));
+
+        let result  = tool.invoke(DefaultsParameters::new(object(json![{ "host": null }]), object(json![{ "host": "localhost", "verbose": false }]), Some(false)), &env);
+
+        assert!(result == Ok(json![{ "host": null, "verbose": false }]));
+    }
+}
@@ -0,0 +1,118 @@
+//!
+//! The fingerprint tool generates a hash of a JSON value
+//!
+
+use std::result::Result;
+use serde_json::*;
+use gossyp_base::*;
+
+/// Initial value for the FNV-1a hash
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+
+/// Multiplier for the FNV-1a hash
+const FNV_PRIME: u64        = 0x100000001b3;
+
+///
+/// Tool that returns a fingerprint for a value
+///
+/// The fingerprint is a 64-bit FNV-1a hash of the value's canonical form (its JSON representation
+/// with the keys of every object in sorted order), returned as a string of 16 hex digits. Values
+/// that are structurally equal always have the same fingerprint, whatever order their keys were
+/// in, and the fingerprint does not change between runs or platforms.
+///
+/// Numbers are hashed using their JSON representation, so `1` and `1.0` have different fingerprints.
+/// This is not a cryptographic hash.
+///
+pub struct FingerprintTool {
+}
+
+impl FingerprintTool {
+    ///
+    /// Creates a new fingerprint tool
+    ///
+    pub fn new() -> FingerprintTool {
+        FingerprintTool { }
+    }
+
+    ///
+    /// Writes out the canonical form of a value
+    ///
+    fn write_canonical(value: &Value, target: &mut String) {
+        match value {
+            &Value::Array(ref values) => {
+                target.push('[');
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 { target.push(','); }
+                    FingerprintTool::write_canonical(value, target);
+                }
+                target.push(']');
+            },
+
+            &Value::Object(ref fields) => {
+                let mut keys: Vec<&String> = fields.keys().collect();
+                keys.sort();
+
+                target.push('{');
+                for (index, key) in keys.into_iter().enumerate() {
+                    if index > 0 { target.push(','); }
+                    target.push_str(&to_string(key).unwrap());
+                    target.push(':');
+                    FingerprintTool::write_canonical(&fields[key], target);
+                }
+                target.push('}');
+            },
+
+            other => target.push_str(&to_string(other).unwrap())
+        }
+    }
+
+    ///
+    /// Computes the fingerprint of a value
+    ///
+    pub fn fingerprint(value: &Value) -> String {
+        let mut canonical = String::new();
+        FingerprintTool::write_canonical(value, &mut canonical);
+
+        let hash = canonical.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ (byte as u64)).wrapping_mul(FNV_PRIME));
+
+        format!("{:016x}", hash)
+    }
+}
+
+impl Tool for FingerprintTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        Ok(Value::String(FingerprintTool::fingerprint(&input)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn key_order_does_not_change_fingerprint() {
+        let first   = from_str::<Value>(r#"{ "a": 1, "b": { "c": [1, 2], "d": "x" } }"#).unwrap();
+        let second  = from_str::<Value>(r#"{ "b": { "d": "x", "c": [1, 2] }, "a": 1 }"#).unwrap();
+
+        assert!(FingerprintTool::fingerprint(&first) == FingerprintTool::fingerprint(&second));
+    }
+
+    #[test]
+    fn changed_value_changes_fingerprint() {
+        let env     = EmptyEnvironment::new();
+        let tool    = FingerprintTool::new();
+
+        let first   = tool.invoke_json(json![{ "a": 1, "b": [ 1, 2 ] }], &env).unwrap();
+        let second  = tool.invoke_json(json![{ "a": 1, "b": [ 2, 1 ] }], &env).unwrap();
+
+        assert!(first != second);
+        assert!(first.as_str().map(|hex| hex.len()) == Some(16));
+    }
+
+    #[test]
+    fn fingerprint_is_stable() {
+        // FNV-1a hash of the string 'null'
+        assert!(FingerprintTool::fingerprint(&Value::Null) == "5b9bc4ba528108e4");
+    }
+}
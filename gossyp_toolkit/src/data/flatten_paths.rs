@@ -0,0 +1,378 @@
+//!
+//! The flatten-paths and unflatten-paths tools convert between nested values and objects mapping paths to values
+//!
+
+use std::result::Result;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// A part of a path to a value
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PathComponent {
+    /// A field in an object
+    Key(String),
+
+    /// An item in an array
+    Index(usize)
+}
+
+///
+/// Tool that converts a nested value into an object mapping the paths of its leaf values to the values themselves
+///
+/// Paths are made up of object keys separated by '.' and array indexes in square brackets, so
+/// `{ "a": { "b": [ 1 ] } }` becomes `{ "a.b[0]": 1 }`. A '.', '[', ']', '"' or '\' that is part of
+/// a key is escaped with a '\', and an empty key is written as `""`, so `{ "": { "b": 1 } }` becomes
+/// `{ "\"\".b": 1 }`. Empty objects and arrays are kept as leaf values so they are not lost, and a
+/// value that's not an object or an array has the path "".
+///
+pub struct FlattenPathsTool {
+}
+
+///
+/// Tool that converts an object mapping paths to values (as produced by flatten-paths) back into a nested value
+///
+/// Arrays are padded with nulls up to the largest index that's set. An index can be at most the
+/// length the array already has plus the number of paths, as any larger index couldn't have
+/// been produced by flatten-paths.
+///
+pub struct UnflattenPathsTool {
+}
+
+///
+/// Reasons a value can't be stored at a path
+///
+enum SetPathError {
+    /// The path is already set, or passes through a value that isn't an object or an array
+    Conflict,
+
+    /// The path contains an array index that is too large
+    IndexTooLarge
+}
+
+///
+/// Escapes a key so that it can be used as part of a path
+///
+fn escape_key(key: &str) -> String {
+    // An empty key would otherwise disappear from the path
+    if key.len() == 0 {
+        return String::from("\"\"");
+    }
+
+    let mut result = String::new();
+
+    for chr in key.chars() {
+        match chr {
+            '.' | '[' | ']' | '"' | '\\'    => { result.push('\\'); result.push(chr); },
+            _                               => result.push(chr)
+        }
+    }
+
+    result
+}
+
+///
+/// Splits a path into its components
+///
+pub fn parse_path(path: &str) -> Result<Vec<PathComponent>, String> {
+    let mut result      = vec![];
+    let mut chars       = path.chars().peekable();
+    let mut key         = String::new();
+    let mut in_key      = path.len() > 0 && !path.starts_with('[');
+
+    while let Some(chr) = chars.next() {
+        match chr {
+            '\\' => {
+                match chars.next() {
+                    Some(escaped)   => key.push(escaped),
+                    None            => return Err(String::from("Path ends with an escape character"))
+                }
+            },
+
+            '.' | '[' => {
+                if in_key {
+                    result.push(PathComponent::Key(key));
+                    key = String::new();
+                }
+
+                if chr == '.' {
+                    in_key = true;
+                } else {
+                    // Read the index
+                    let mut index = String::new();
+                    loop {
+                        match chars.next() {
+                            Some(']')   => break,
+                            Some(digit) => index.push(digit),
+                            None        => return Err(String::from("Missing ']' in path"))
+                        }
+                    }
+
+                    let index = index.parse::<usize>().map_err(|_| format!("'{}' is not a valid array index", index))?;
+                    result.push(PathComponent::Index(index));
+
+                    // An index must be followed by another index, a key or the end of the path
+                    in_key = false;
+                    match chars.peek() {
+                        Some(&'.') | Some(&'[') | None  => (),
+                        _                               => return Err(String::from("Expected '.' or '[' after an array index"))
+                    }
+                }
+            },
+
+            '"' => {
+                // '""' is an empty key: any other '"' should have been escaped
+                if key.len() > 0 || chars.next() != Some('"') {
+                    return Err(String::from("Unexpected '\"' in path"));
+                }
+
+                match chars.peek() {
+                    Some(&'.') | Some(&'[') | None  => (),
+                    _                               => return Err(String::from("Expected '.' or '[' after an empty key"))
+                }
+            },
+
+            ']' => return Err(String::from("Unexpected ']' in path")),
+
+            _   => key.push(chr)
+        }
+    }
+
+    if in_key {
+        result.push(PathComponent::Key(key));
+    }
+
+    Ok(result)
+}
+
+impl FlattenPathsTool {
+    ///
+    /// Creates a new flatten-paths tool
+    ///
+    pub fn new() -> FlattenPathsTool {
+        FlattenPathsTool { }
+    }
+
+    ///
+    /// Adds the leaf values of a value at a particular path to a result
+    ///
+    fn flatten_into(value: &Value, path: String, result: &mut Map<String, Value>) {
+        match value {
+            &Value::Object(ref fields) if fields.len() > 0 => {
+                for (key, field) in fields.iter() {
+                    let field_path = if path.len() == 0 {
+                        escape_key(key)
+                    } else {
+                        format!("{}.{}", path, escape_key(key))
+                    };
+
+                    FlattenPathsTool::flatten_into(field, field_path, result);
+                }
+            },
+
+            &Value::Array(ref items) if items.len() > 0 => {
+                for (index, item) in items.iter().enumerate() {
+                    FlattenPathsTool::flatten_into(item, format!("{}[{}]", path, index), result);
+                }
+            },
+
+            leaf => { result.insert(path, leaf.clone()); }
+        }
+    }
+
+    ///
+    /// Flattens a value into a map of paths to leaf values
+    ///
+    pub fn flatten(value: &Value) -> Map<String, Value> {
+        let mut result = Map::new();
+        FlattenPathsTool::flatten_into(value, String::new(), &mut result);
+        result
+    }
+}
+
+impl UnflattenPathsTool {
+    ///
+    /// Creates a new unflatten-paths tool
+    ///
+    pub fn new() -> UnflattenPathsTool {
+        UnflattenPathsTool { }
+    }
+
+    ///
+    /// Stores a value at a path within a target value, creating objects and arrays as needed
+    ///
+    fn set_path(target: &mut Value, path: &[PathComponent], value: Value, max_padding: usize) -> Result<(), SetPathError> {
+        if path.len() == 0 {
+            // Can only set a value that hasn't already been set
+            return if target.is_null() { *target = value; Ok(()) } else { Err(SetPathError::Conflict) };
+        }
+
+        match &path[0] {
+            &PathComponent::Key(ref key) => {
+                if target.is_null() {
+                    *target = Value::Object(Map::new());
+                }
+
+                match target {
+                    &mut Value::Object(ref mut fields) => {
+                        let field = fields.entry(key.clone()).or_insert(Value::Null);
+                        UnflattenPathsTool::set_path(field, &path[1..], value, max_padding)
+                    },
+                    _ => Err(SetPathError::Conflict)
+                }
+            },
+
+            &PathComponent::Index(index) => {
+                if target.is_null() {
+                    *target = Value::Array(vec![]);
+                }
+
+                match target {
+                    &mut Value::Array(ref mut items) => {
+                        // Padding an array up to an arbitrary index could use any amount of memory
+                        if index >= items.len() + max_padding {
+                            return Err(SetPathError::IndexTooLarge);
+                        }
+
+                        while items.len() <= index {
+                            items.push(Value::Null);
+                        }
+                        UnflattenPathsTool::set_path(&mut items[index], &path[1..], value, max_padding)
+                    },
+                    _ => Err(SetPathError::Conflict)
+                }
+            }
+        }
+    }
+
+    ///
+    /// Converts a map of paths to values into a nested value
+    ///
+    pub fn unflatten(paths: &Map<String, Value>) -> Result<Value, Value> {
+        let mut result = Value::Null;
+
+        for (path, value) in paths.iter() {
+            let components = parse_path(path).map_err(|description| json![{
+                "error":        "Invalid path",
+                "path":         path,
+                "description":  description
+            }])?;
+
+            UnflattenPathsTool::set_path(&mut result, &components, value.clone(), paths.len()).map_err(|error| match error {
+                SetPathError::Conflict      => json![{
+                    "error":        "Path conflicts with another path",
+                    "path":         path
+                }],
+                SetPathError::IndexTooLarge => json![{
+                    "error":        "Array index in path is too large",
+                    "path":         path,
+                    "max_padding":  paths.len()
+                }]
+            })?;
+        }
+
+        Ok(result)
+    }
+}
+
+impl Tool for FlattenPathsTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        Ok(Value::Object(FlattenPathsTool::flatten(&input)))
+    }
+}
+
+impl Tool for UnflattenPathsTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        match input {
+            Value::Object(ref paths)    => UnflattenPathsTool::unflatten(paths),
+            _                           => Err(json![{
+                "error": "Input to unflatten-paths must be an object mapping paths to values"
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn can_flatten_nested_value() {
+        let env     = EmptyEnvironment::new();
+        let tool    = FlattenPathsTool::new();
+
+        let result  = tool.invoke_json(json![{ "a": { "b": [ 1, { "c": 2 } ] }, "d": "x" }], &env);
+
+        assert!(result == Ok(json![{ "a.b[0]": 1, "a.b[1].c": 2, "d": "x" }]));
+    }
+
+    #[test]
+    fn round_trip_preserves_value() {
+        let env         = EmptyEnvironment::new();
+        let original    = json![{
+            "a":        { "b": [ 1, { "c": 2 }, [ 3, 4 ] ] },
+            "empty":    { "array": [], "object": {} },
+            "odd.key":  { "[x]": null, "\"q\"": true },
+            "":         { "b": 1, "": [ 2 ] },
+            "b":        3
+        }];
+
+        let flattened   = FlattenPathsTool::new().invoke_json(original.clone(), &env).unwrap();
+        let restored    = UnflattenPathsTool::new().invoke_json(flattened, &env);
+
+        assert!(restored == Ok(original));
+    }
+
+    #[test]
+    fn empty_keys_are_quoted() {
+        assert!(FlattenPathsTool::flatten(&json![{ "": { "b": 1 } }]) == *json![{ "\"\".b": 1 }].as_object().unwrap());
+        assert!(FlattenPathsTool::flatten(&json![{ "a": { "": 1 } }]) == *json![{ "a.\"\"": 1 }].as_object().unwrap());
+        assert!(FlattenPathsTool::flatten(&json![{ "": { "b": 1 } }]) != FlattenPathsTool::flatten(&json![{ "b": 1 }]));
+
+        assert!(parse_path("\"\"[0]") == Ok(vec![ PathComponent::Key(String::new()), PathComponent::Index(0) ]));
+        assert!(parse_path("a\"\"").is_err());
+        assert!(parse_path("\"\"b").is_err());
+    }
+
+    #[test]
+    fn can_parse_paths() {
+        assert!(parse_path("a.b[2][0].c\\.d") == Ok(vec![
+            PathComponent::Key(String::from("a")),
+            PathComponent::Key(String::from("b")),
+            PathComponent::Index(2),
+            PathComponent::Index(0),
+            PathComponent::Key(String::from("c.d"))
+        ]));
+        assert!(parse_path("a[x]").is_err());
+    }
+
+    #[test]
+    fn conflicting_paths_are_error() {
+        let env     = EmptyEnvironment::new();
+        let result  = UnflattenPathsTool::new().invoke_json(json![{ "a": 1, "a.b": 2 }], &env);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn huge_index_is_error() {
+        let env     = EmptyEnvironment::new();
+        let result  = UnflattenPathsTool::new().invoke_json(json![{ "a[4000000000]": 1 }], &env);
+
+        assert!(result.unwrap_err()["error"] == json!["Array index in path is too large"]);
+    }
+
+    #[test]
+    fn long_arrays_round_trip() {
+        // 'a[10]' is set before 'a[2]', as the paths are in key order
+        let env         = EmptyEnvironment::new();
+        let original    = json![{ "a": [ 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11 ] }];
+
+        let flattened   = FlattenPathsTool::new().invoke_json(original.clone(), &env).unwrap();
+        let restored    = UnflattenPathsTool::new().invoke_json(flattened, &env);
+
+        assert!(restored == Ok(original));
+    }
+}
@@ -0,0 +1,69 @@
+pub mod defaults;
+pub mod columns;
+pub mod fingerprint;
+pub mod deep_map_values;
+pub mod flatten_paths;
+pub mod validate;
+pub mod one_of;
+pub mod pick;
+pub mod next_id;
+pub mod cache;
+pub mod value_size;
+pub mod tool;
+
+pub use self::defaults::*;
+pub use self::columns::*;
+pub use self::fingerprint::*;
+pub use self::deep_map_values::*;
+pub use self::flatten_paths::*;
+pub use self::validate::*;
+pub use self::one_of::*;
+pub use self::pick::*;
+pub use self::next_id::*;
+pub use self::cache::*;
+pub use self::value_size::*;
+
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+///
+/// ToolSet containing the tools for manipulating JSON data
+///
+pub struct DataTools { }
+
+impl DataTools {
+    pub fn new() -> DataTools {
+        DataTools { }
+    }
+}
+
+impl<'a> ToolSet for &'a DataTools {
+    fn create_tools(self, _environment: &Environment) -> Vec<(String, Box<Tool>)> {
+        let cache = Cache::new();
+
+        vec![
+            (String::from(self::tool::DEFAULTS),        Box::new(DefaultsTool::new())),
+            (String::from(self::tool::COLUMNS),         Box::new(ColumnsTool::new())),
+            (String::from(self::tool::ROWS),            Box::new(RowsTool::new())),
+            (String::from(self::tool::FINGERPRINT),     Box::new(FingerprintTool::new())),
+            (String::from(self::tool::DEEP_MAP_VALUES), Box::new(DeepMapValuesTool::new())),
+            (String::from(self::tool::FLATTEN_PATHS),   Box::new(FlattenPathsTool::new())),
+            (String::from(self::tool::UNFLATTEN_PATHS), Box::new(UnflattenPathsTool::new())),
+            (String::from(self::tool::VALIDATE),        Box::new(ValidateTool::new())),
+            (String::from(self::tool::ONE_OF),          Box::new(OneOfTool::new())),
+            (String::from(self::tool::PICK),            Box::new(PickTool::new())),
+            (String::from(self::tool::OMIT),            Box::new(OmitTool::new())),
+            (String::from(self::tool::NEXT_ID),         Box::new(NextIdTool::new())),
+            (String::from(self::tool::CACHE_GET),       Box::new(CacheGetTool::new(cache.clone()))),
+            (String::from(self::tool::CACHE_SET),       Box::new(CacheSetTool::new(cache.clone()))),
+            (String::from(self::tool::CACHE_CLEAR),     Box::new(CacheClearTool::new(cache))),
+            (String::from(self::tool::VALUE_SIZE),      Box::new(ValueSizeTool::new()))
+        ]
+    }
+}
+
+impl ToolSet for DataTools {
+    fn create_tools(self, environment: &Environment) -> Vec<(String, Box<Tool>)> {
+        (&self).create_tools(environment)
+    }
+}
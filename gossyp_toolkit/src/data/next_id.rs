@@ -0,0 +1,114 @@
+//!
+//! The next-id tool generates sequential IDs
+//!
+
+use std::result::Result;
+use std::error::Error;
+use std::sync::*;
+use std::collections::HashMap;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Parameters that can be passed to the next-id tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct NextIdParameters {
+    /// The scope to generate an ID for (scopes have independent counters)
+    pub scope: Option<String>
+}
+
+///
+/// Tool that returns a new ID each time it's called
+///
+/// IDs start at 1 and increase by one each time the tool is called. The input can be null or
+/// `{ "scope": <name> }`: each scope has its own sequence of IDs. The counters belong to the
+/// tool, so each environment the data tools are imported into has its own sequences.
+///
+pub struct NextIdTool {
+    /// The last ID generated for each scope
+    counters: Arc<Mutex<HashMap<String, u64>>>
+}
+
+impl NextIdTool {
+    ///
+    /// Creates a new next-id tool
+    ///
+    pub fn new() -> NextIdTool {
+        NextIdTool { counters: Arc::new(Mutex::new(HashMap::new())) }
+    }
+
+    ///
+    /// Generates the next ID for a scope
+    ///
+    pub fn next_id(&self, scope: &str) -> u64 {
+        let mut counters    = self.counters.lock().unwrap();
+        let counter         = counters.entry(String::from(scope)).or_insert(0);
+
+        *counter += 1;
+        *counter
+    }
+}
+
+impl Tool for NextIdTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        // Null input is the default scope
+        let parameters = match input {
+            Value::Null => Ok(NextIdParameters { scope: None }),
+            input       => from_value::<NextIdParameters>(input)
+        };
+
+        match parameters {
+            Ok(NextIdParameters { scope }) => Ok(json![ self.next_id(&scope.unwrap_or_else(|| String::new())) ]),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to next-id must be null or of the form { \"scope\": <name> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::thread;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn single_scope_increases_monotonically() {
+        let env     = EmptyEnvironment::new();
+        let tool    = NextIdTool::new();
+
+        assert!(tool.invoke_json(Value::Null, &env) == Ok(json![ 1 ]));
+        assert!(tool.invoke_json(Value::Null, &env) == Ok(json![ 2 ]));
+        assert!(tool.invoke_json(json![{ "scope": "orders" }], &env) == Ok(json![ 1 ]));
+        assert!(tool.invoke_json(json![{ "scope": "orders" }], &env) == Ok(json![ 2 ]));
+        assert!(tool.invoke_json(json![{ "scope": "orders" }], &env) == Ok(json![ 3 ]));
+    }
+
+    #[test]
+    fn scopes_increment_independently() {
+        let env     = EmptyEnvironment::new();
+        let tool    = NextIdTool::new();
+
+        assert!(tool.invoke_json(json![{ "scope": "orders" }], &env) == Ok(json![ 1 ]));
+        assert!(tool.invoke_json(json![{ "scope": "orders" }], &env) == Ok(json![ 2 ]));
+        assert!(tool.invoke_json(json![{ "scope": "customers" }], &env) == Ok(json![ 1 ]));
+        assert!(tool.invoke_json(json![{ "scope": "orders" }], &env) == Ok(json![ 3 ]));
+    }
+
+    #[test]
+    fn ids_are_unique_across_threads() {
+        let tool    = Arc::new(NextIdTool::new());
+        let threads = (0..4).map(|_| {
+            let tool = tool.clone();
+            thread::spawn(move || (0..100).map(|_| tool.next_id("shared")).collect::<Vec<_>>())
+        }).collect::<Vec<_>>();
+
+        let mut ids = threads.into_iter().flat_map(|thread| thread.join().unwrap()).collect::<Vec<_>>();
+        ids.sort();
+
+        assert!(ids == (1..401).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,128 @@
+//!
+//! The one-of tool checks that a value matches at least one of several schemas
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+use super::validate::*;
+
+///
+/// Parameters that can be passed to the one-of tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct OneOfParameters {
+    /// The value to check
+    pub value: Value,
+
+    /// The schemas that the value can match
+    pub schemas: Vec<Value>
+}
+
+impl OneOfParameters {
+    pub fn new(value: Value, schemas: Vec<Value>) -> OneOfParameters {
+        OneOfParameters { value: value, schemas: schemas }
+    }
+}
+
+///
+/// Tool that returns a value if it matches one of a set of schemas, or an error if it matches none of them
+///
+/// The schemas are the same as the ones used by the validate tool, and are tried in order. If the
+/// value doesn't match any of them, the error contains the reasons that each schema failed.
+///
+pub struct OneOfTool {
+}
+
+impl OneOfTool {
+    ///
+    /// Creates a new one-of tool
+    ///
+    pub fn new() -> OneOfTool {
+        OneOfTool { }
+    }
+
+    ///
+    /// Checks a value against a set of schemas, returning the index of the first one that matches
+    ///
+    /// If none of the schemas match, the result is the reasons why each one failed
+    ///
+    pub fn find_match(value: &Value, schemas: &Vec<Value>) -> Result<usize, Vec<Vec<ValidationError>>> {
+        let mut failures = vec![];
+
+        for (index, schema) in schemas.iter().enumerate() {
+            let errors = ValidateTool::validate(value, schema);
+
+            if errors.len() == 0 {
+                return Ok(index);
+            }
+
+            failures.push(errors);
+        }
+
+        Err(failures)
+    }
+}
+
+impl Tool for OneOfTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<OneOfParameters>(input);
+
+        match parameters {
+            Ok(OneOfParameters { value, schemas }) => {
+                match OneOfTool::find_match(&value, &schemas) {
+                    Ok(_)           => Ok(value),
+                    Err(failures)   => Err(json![{
+                        "error":    "Value does not match any of the schemas",
+                        "failures": failures
+                    }])
+                }
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to one-of must be of the form { \"value\": <value>, \"schemas\": [ <schema>, ... ] }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    fn schemas() -> Vec<Value> {
+        vec![
+            json![{ "type": "string" }],
+            json![{ "type": "object", "required": [ "name" ] }]
+        ]
+    }
+
+    #[test]
+    fn value_matching_second_schema_is_returned() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<OneOfParameters, Value>::from(Box::new(OneOfTool::new()));
+
+        assert!(OneOfTool::find_match(&json![{ "name": "x" }], &schemas()) == Ok(1));
+        assert!(tool.invoke(OneOfParameters::new(json![{ "name": "x" }], schemas()), &env) == Ok(json![{ "name": "x" }]));
+    }
+
+    #[test]
+    fn value_matching_no_schema_reports_each_failure() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<OneOfParameters, Value>::from(Box::new(OneOfTool::new()));
+
+        let result  = tool.invoke(OneOfParameters::new(json![{ "title": "x" }], schemas()), &env);
+
+        assert!(result == Err(json![{
+            "error":    "Value does not match any of the schemas",
+            "failures": [
+                [ { "path": "", "message": "Expected a value of type 'string'" } ],
+                [ { "path": "", "message": "Missing required field 'name'" } ]
+            ]
+        }]));
+    }
+}
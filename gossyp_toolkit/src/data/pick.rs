@@ -0,0 +1,158 @@
+//!
+//! The pick and omit tools select which fields of an object to keep
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Parameters that can be passed to the pick and omit tools
+///
+#[derive(Serialize, Deserialize)]
+pub struct PickParameters {
+    /// The object to take fields from
+    pub value: Map<String, Value>,
+
+    /// The keys of the fields to pick or omit
+    pub keys: Vec<String>
+}
+
+impl PickParameters {
+    pub fn new(value: Map<String, Value>, keys: Vec<&str>) -> PickParameters {
+        PickParameters { value: value, keys: keys.into_iter().map(|key| String::from(key)).collect() }
+    }
+}
+
+///
+/// Tool that returns an object containing only the specified fields of another object
+///
+/// Keys that aren't in the object are ignored, and the fields in the result are in the same order
+/// as they were in the original object.
+///
+pub struct PickTool {
+}
+
+///
+/// Tool that returns an object without the specified fields of another object
+///
+pub struct OmitTool {
+}
+
+///
+/// Keeps or removes the fields of an object with the specified keys
+///
+fn filter_fields(value: Map<String, Value>, keys: &Vec<String>, keep: bool) -> Map<String, Value> {
+    value.into_iter()
+        .filter(|&(ref key, _)| keys.contains(key) == keep)
+        .collect()
+}
+
+impl PickTool {
+    ///
+    /// Creates a new pick tool
+    ///
+    pub fn new() -> PickTool {
+        PickTool { }
+    }
+
+    ///
+    /// Returns an object containing only the fields with the specified keys
+    ///
+    pub fn pick(value: Map<String, Value>, keys: &Vec<String>) -> Map<String, Value> {
+        filter_fields(value, keys, true)
+    }
+}
+
+impl OmitTool {
+    ///
+    /// Creates a new omit tool
+    ///
+    pub fn new() -> OmitTool {
+        OmitTool { }
+    }
+
+    ///
+    /// Returns an object without the fields with the specified keys
+    ///
+    pub fn omit(value: Map<String, Value>, keys: &Vec<String>) -> Map<String, Value> {
+        filter_fields(value, keys, false)
+    }
+}
+
+impl Tool for PickTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        match from_value::<PickParameters>(input) {
+            Ok(PickParameters { value, keys })  => Ok(Value::Object(PickTool::pick(value, &keys))),
+
+            Err(erm)                            => Err(json![{
+                "error":        "Parameters to pick must be of the form { \"value\": <object>, \"keys\": [ <key>, ... ] }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+impl Tool for OmitTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        match from_value::<PickParameters>(input) {
+            Ok(PickParameters { value, keys })  => Ok(Value::Object(OmitTool::omit(value, &keys))),
+
+            Err(erm)                            => Err(json![{
+                "error":        "Parameters to omit must be of the form { \"value\": <object>, \"keys\": [ <key>, ... ] }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    fn test_object() -> Map<String, Value> {
+        match json![{ "a": 1, "b": 2, "c": 3, "d": 4 }] {
+            Value::Object(fields)   => fields,
+            _                       => unreachable!()
+        }
+    }
+
+    #[test]
+    fn can_pick_subset() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<PickParameters, Value>::from(Box::new(PickTool::new()));
+
+        let result  = tool.invoke(PickParameters::new(test_object(), vec![ "c", "a" ]), &env);
+
+        assert!(result == Ok(json![{ "a": 1, "c": 3 }]));
+    }
+
+    #[test]
+    fn can_omit_subset() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<PickParameters, Value>::from(Box::new(OmitTool::new()));
+
+        let result  = tool.invoke(PickParameters::new(test_object(), vec![ "b", "d" ]), &env);
+
+        assert!(result == Ok(json![{ "a": 1, "c": 3 }]));
+    }
+
+    #[test]
+    fn missing_keys_are_absent_from_pick() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<PickParameters, Value>::from(Box::new(PickTool::new()));
+
+        let result  = tool.invoke(PickParameters::new(test_object(), vec![ "a", "missing" ]), &env);
+
+        assert!(result == Ok(json![{ "a": 1 }]));
+    }
+
+    #[test]
+    fn field_order_is_preserved() {
+        let keys: Vec<String> = PickTool::pick(test_object(), &vec![ String::from("d"), String::from("b") ]).keys().cloned().collect();
+
+        assert!(keys == vec![ String::from("b"), String::from("d") ]);
+    }
+}
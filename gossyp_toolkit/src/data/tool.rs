@@ -0,0 +1,16 @@
+pub const DEFAULTS: &'static str        = "defaults";
+pub const COLUMNS: &'static str         = "columns";
+pub const ROWS: &'static str            = "rows";
+pub const FINGERPRINT: &'static str     = "fingerprint";
+pub const DEEP_MAP_VALUES: &'static str = "deep-map-values";
+pub const FLATTEN_PATHS: &'static str   = "flatten-paths";
+pub const UNFLATTEN_PATHS: &'static str = "unflatten-paths";
+pub const VALIDATE: &'static str        = "validate";
+pub const ONE_OF: &'static str          = "one-of";
+pub const PICK: &'static str            = "pick";
+pub const OMIT: &'static str            = "omit";
+pub const NEXT_ID: &'static str         = "next-id";
+pub const CACHE_GET: &'static str       = "cache-get";
+pub const CACHE_SET: &'static str       = "cache-set";
+pub const CACHE_CLEAR: &'static str     = "cache-clear";
+pub const VALUE_SIZE: &'static str      = "value-size";
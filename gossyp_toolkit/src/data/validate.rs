@@ -0,0 +1,216 @@
+//!
+//! The validate tool checks that a value matches a schema
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Parameters that can be passed to the validate tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct ValidateParameters {
+    /// The value to check
+    pub value: Value,
+
+    /// The schema that the value should match
+    pub schema: Value
+}
+
+impl ValidateParameters {
+    pub fn new(value: Value, schema: Value) -> ValidateParameters {
+        ValidateParameters { value: value, schema: schema }
+    }
+}
+
+///
+/// A reason why a value doesn't match a schema
+///
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ValidationError {
+    /// The path to the value that doesn't match (with the same syntax as flatten-paths)
+    pub path: String,
+
+    /// Why the value doesn't match
+    pub message: String
+}
+
+///
+/// Result of the validate tool
+///
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct ValidateResult {
+    /// True if the value matches the schema
+    pub valid: bool,
+
+    /// The reasons the value doesn't match the schema
+    pub errors: Vec<ValidationError>
+}
+
+///
+/// Tool that checks whether or not a value matches a schema
+///
+/// Schemas are a subset of JSON schema. These keywords are supported:
+///
+/// * `type` - a type name or an array of type names. The type names are `null`, `boolean`,
+///   `number`, `integer`, `string`, `array` and `object`
+/// * `enum` - an array of the values that are allowed
+/// * `properties` - an object mapping field names to the schemas the fields must match
+/// * `required` - an array of the fields that an object must have
+/// * `items` - the schema that every item in an array must match
+///
+/// Other keywords are ignored, so `{}` matches every value.
+///
+pub struct ValidateTool {
+}
+
+impl ValidateTool {
+    ///
+    /// Creates a new validate tool
+    ///
+    pub fn new() -> ValidateTool {
+        ValidateTool { }
+    }
+
+    ///
+    /// True if a value is of the named type
+    ///
+    fn is_type(value: &Value, type_name: &str) -> bool {
+        match (type_name, value) {
+            ("null", &Value::Null)                  => true,
+            ("boolean", &Value::Bool(_))            => true,
+            ("number", &Value::Number(_))           => true,
+            ("integer", &Value::Number(ref n))      => n.is_i64() || n.is_u64() || n.as_f64().map(|n| n.fract() == 0.0).unwrap_or(false),
+            ("string", &Value::String(_))           => true,
+            ("array", &Value::Array(_))             => true,
+            ("object", &Value::Object(_))           => true,
+            _                                       => false
+        }
+    }
+
+    ///
+    /// Returns the path of an item within a value
+    ///
+    fn field_path(path: &str, field: &str) -> String {
+        if path.len() == 0 { String::from(field) } else { format!("{}.{}", path, field) }
+    }
+
+    ///
+    /// Checks a value at a particular path against a schema, adding any problems to a list of errors
+    ///
+    fn validate_at(value: &Value, schema: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+        let error = |message: String| ValidationError { path: String::from(path), message: message };
+
+        // Type
+        match schema.get("type") {
+            Some(&Value::String(ref type_name)) => {
+                if !ValidateTool::is_type(value, type_name) {
+                    errors.push(error(format!("Expected a value of type '{}'", type_name)));
+                    return;
+                }
+            },
+
+            Some(&Value::Array(ref type_names)) => {
+                if !type_names.iter().any(|type_name| type_name.as_str().map(|type_name| ValidateTool::is_type(value, type_name)).unwrap_or(false)) {
+                    errors.push(error(format!("Expected a value of one of the types {}", Value::Array(type_names.clone()))));
+                    return;
+                }
+            },
+
+            _ => ()
+        }
+
+        // Enumeration
+        if let Some(&Value::Array(ref allowed)) = schema.get("enum") {
+            if !allowed.contains(value) {
+                errors.push(error(format!("Expected one of the values {}", Value::Array(allowed.clone()))));
+            }
+        }
+
+        // Objects
+        if let &Value::Object(ref fields) = value {
+            if let Some(&Value::Array(ref required)) = schema.get("required") {
+                for field in required.iter().filter_map(|field| field.as_str()) {
+                    if !fields.contains_key(field) {
+                        errors.push(error(format!("Missing required field '{}'", field)));
+                    }
+                }
+            }
+
+            if let Some(&Value::Object(ref properties)) = schema.get("properties") {
+                for (field, field_schema) in properties.iter() {
+                    if let Some(field_value) = fields.get(field) {
+                        ValidateTool::validate_at(field_value, field_schema, &ValidateTool::field_path(path, field), errors);
+                    }
+                }
+            }
+        }
+
+        // Arrays
+        if let &Value::Array(ref items) = value {
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    ValidateTool::validate_at(item, item_schema, &format!("{}[{}]", path, index), errors);
+                }
+            }
+        }
+    }
+
+    ///
+    /// Checks a value against a schema, returning the reasons that it doesn't match
+    ///
+    pub fn validate(value: &Value, schema: &Value) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        ValidateTool::validate_at(value, schema, "", &mut errors);
+        errors
+    }
+}
+
+impl Tool for ValidateTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<ValidateParameters>(input);
+
+        match parameters {
+            Ok(ValidateParameters { value, schema }) => {
+                let errors = ValidateTool::validate(&value, &schema);
+                Ok(to_value(ValidateResult { valid: errors.len() == 0, errors: errors }).unwrap())
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to validate must be of the form { \"value\": <value>, \"schema\": <schema> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn matching_value_is_valid() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<ValidateParameters, ValidateResult>::from(Box::new(ValidateTool::new()));
+
+        let result  = tool.invoke(ValidateParameters::new(
+            json![{ "name": "x", "sizes": [ 1, 2 ] }],
+            json![{ "type": "object", "required": [ "name" ], "properties": { "name": { "type": "string" }, "sizes": { "type": "array", "items": { "type": "integer" } } } }]), &env);
+
+        assert!(result == Ok(ValidateResult { valid: true, errors: vec![] }));
+    }
+
+    #[test]
+    fn errors_have_paths() {
+        let errors = ValidateTool::validate(
+            &json![{ "sizes": [ 1, "two" ] }],
+            &json![{ "type": "object", "required": [ "name" ], "properties": { "sizes": { "type": "array", "items": { "type": "integer" } } } }]);
+
+        assert!(errors.len() == 2);
+        assert!(errors[0].path == "");
+        assert!(errors[1].path == "sizes[1]");
+    }
+}
@@ -0,0 +1,134 @@
+//!
+//! The value-size tool measures how large a JSON value is
+//!
+
+use std::result::Result;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// The size of a JSON value
+///
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct ValueSize {
+    /// The length in bytes of the value's compact JSON representation
+    pub bytes: u64,
+
+    /// The total number of nodes (scalars, objects and arrays) in the value
+    pub nodes: u64,
+
+    /// The number of scalars (nulls, booleans, numbers and strings) in the value
+    pub scalars: u64,
+
+    /// The number of objects in the value
+    pub objects: u64,
+
+    /// The number of arrays in the value
+    pub arrays: u64
+}
+
+///
+/// Tool that returns the size of its input
+///
+/// The result is `{ "bytes": <n>, "nodes": <n>, "scalars": <n>, "objects": <n>, "arrays": <n> }`.
+/// `bytes` is the length of the value serialized as compact JSON, and the other fields count the
+/// nodes in the value (object keys are not counted as nodes). This makes it possible for a script
+/// to reject oversized data before passing it on.
+///
+pub struct ValueSizeTool {
+}
+
+impl ValueSize {
+    ///
+    /// Creates a size for an empty value
+    ///
+    pub fn new() -> ValueSize {
+        ValueSize { bytes: 0, nodes: 0, scalars: 0, objects: 0, arrays: 0 }
+    }
+}
+
+impl ValueSizeTool {
+    ///
+    /// Creates a new value-size tool
+    ///
+    pub fn new() -> ValueSizeTool {
+        ValueSizeTool { }
+    }
+
+    ///
+    /// Adds the size of a value to a running total
+    ///
+    fn add_size(value: &Value, size: &mut ValueSize) {
+        size.nodes += 1;
+
+        match value {
+            &Value::Array(ref values) => {
+                // '[' and ']', plus a ',' between each value
+                size.arrays += 1;
+                size.bytes  += 2 + (values.len().max(1) - 1) as u64;
+
+                values.iter().for_each(|value| ValueSizeTool::add_size(value, size));
+            },
+
+            &Value::Object(ref fields) => {
+                // '{' and '}', a ',' between each field and a ':' after each key
+                size.objects    += 1;
+                size.bytes      += 2 + (fields.len().max(1) - 1) as u64 + fields.len() as u64;
+
+                for (key, value) in fields.iter() {
+                    size.bytes += to_string(key).unwrap().len() as u64;
+                    ValueSizeTool::add_size(value, size);
+                }
+            },
+
+            other => {
+                size.scalars    += 1;
+                size.bytes      += to_string(other).unwrap().len() as u64;
+            }
+        }
+    }
+
+    ///
+    /// Measures the size of a value
+    ///
+    pub fn value_size(value: &Value) -> ValueSize {
+        let mut size = ValueSize::new();
+        ValueSizeTool::add_size(value, &mut size);
+
+        size
+    }
+}
+
+impl Tool for ValueSizeTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        Ok(to_value(ValueSizeTool::value_size(&input)).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn measures_nested_structure() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<Value, ValueSize>::from(Box::new(ValueSizeTool::new()));
+        let value   = json![{
+            "name":     "gossyp",
+            "tags":     [ "a", "b\"c", null ],
+            "nested":   { "empty": {}, "list": [], "flag": true, "count": 1.5 }
+        }];
+
+        let size    = tool.invoke(value.clone(), &env).unwrap();
+
+        assert!(size.bytes == to_string(&value).unwrap().len() as u64);
+        assert!(size == ValueSize { bytes: size.bytes, nodes: 11, scalars: 6, objects: 3, arrays: 2 });
+    }
+
+    #[test]
+    fn measures_scalars() {
+        assert!(ValueSizeTool::value_size(&json![ "\u{e9}" ]) == ValueSize { bytes: 4, nodes: 1, scalars: 1, objects: 0, arrays: 0 });
+        assert!(ValueSizeTool::value_size(&Value::Null) == ValueSize { bytes: 4, nodes: 1, scalars: 1, objects: 0, arrays: 0 });
+    }
+}
@@ -0,0 +1,119 @@
+//!
+//! The call-named tool calls a tool that takes positional arguments using named arguments
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+///
+/// Parameters that can be passed to the call-named tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct CallNamedParameters {
+    /// The name of the tool to call
+    pub tool: String,
+
+    /// The names of the arguments, in the order the tool expects them
+    pub arg_order: Vec<String>,
+
+    /// The arguments to pass to the tool
+    pub args: Map<String, Value>
+}
+
+impl CallNamedParameters {
+    pub fn new(tool: &str, arg_order: Vec<&str>, args: Value) -> CallNamedParameters {
+        CallNamedParameters {
+            tool:       String::from(tool),
+            arg_order:  arg_order.into_iter().map(|name| String::from(name)).collect(),
+            args:       match args { Value::Object(args) => args, _ => Map::new() }
+        }
+    }
+}
+
+///
+/// Tool that reorders a set of named arguments into an array and passes them to another tool
+///
+/// Arguments that are named in `arg_order` but not supplied are passed as null. Supplying an
+/// argument that isn't in `arg_order` is an error, as it would otherwise be silently ignored.
+///
+pub struct CallNamedTool {
+}
+
+impl CallNamedTool {
+    ///
+    /// Creates a new call-named tool
+    ///
+    pub fn new() -> CallNamedTool {
+        CallNamedTool { }
+    }
+
+    ///
+    /// Converts a set of named arguments into a positional array
+    ///
+    pub fn positional_args(arg_order: &Vec<String>, mut args: Map<String, Value>) -> Result<Value, Value> {
+        let positional = arg_order.iter()
+            .map(|name| args.remove(name).unwrap_or(Value::Null))
+            .collect::<Vec<_>>();
+
+        // Anything left over wasn't in the order list
+        if let Some(name) = args.keys().nth(0) {
+            return Err(json![{
+                "error":        "Argument is not in arg_order",
+                "argument":     name
+            }]);
+        }
+
+        Ok(Value::Array(positional))
+    }
+}
+
+impl Tool for CallNamedTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<CallNamedParameters>(input);
+
+        match parameters {
+            Ok(CallNamedParameters { tool, arg_order, args }) => {
+                let args        = CallNamedTool::positional_args(&arg_order, args)?;
+                let tool_name   = tool;
+                let tool        = get_tool_or_error(environment, &tool_name)?;
+
+                tool.invoke_json(args, environment)
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to call-named must be of the form { \"tool\": <tool_name>, \"arg_order\": [ <name>, ... ], \"args\": { <name>: <value>, ... } }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reorders_named_arguments() {
+        let env = DynamicEnvironment::new();
+        env.define("subtract", Box::new(make_pure_tool(|(a, b): (i32, i32)| a-b)));
+
+        let tool = TypedTool::<CallNamedParameters, Value>::from(Box::new(CallNamedTool::new()));
+
+        assert!(tool.invoke(CallNamedParameters::new("subtract", vec![ "a", "b" ], json![{ "b": 2, "a": 10 }]), &env) == Ok(json![ 8 ]));
+        assert!(tool.invoke(CallNamedParameters::new("subtract", vec![ "b", "a" ], json![{ "b": 2, "a": 10 }]), &env) == Ok(json![ -8 ]));
+    }
+
+    #[test]
+    fn argument_missing_from_order_is_error() {
+        let env = DynamicEnvironment::new();
+        env.define("subtract", Box::new(make_pure_tool(|(a, b): (i32, i32)| a-b)));
+
+        let tool    = CallNamedTool::new();
+        let result  = tool.invoke_json(to_value(CallNamedParameters::new("subtract", vec![ "a", "b" ], json![{ "a": 10, "b": 2, "c": 3 }])).unwrap(), &env);
+
+        assert!(result == Err(json![{ "error": "Argument is not in arg_order", "argument": "c" }]));
+    }
+}
@@ -0,0 +1,123 @@
+//!
+//! The capture tool invokes a tool and returns its result as data instead of failing
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+///
+/// Parameters that can be passed to the capture tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct CaptureParameters {
+    /// The name of the tool to invoke
+    pub tool: String,
+
+    /// The input to pass to the tool
+    pub input: Value
+}
+
+impl CaptureParameters {
+    pub fn new(tool: &str, input: Value) -> CaptureParameters {
+        CaptureParameters { tool: String::from(tool), input: input }
+    }
+}
+
+///
+/// Tool that invokes another tool and describes whether or not it succeeded
+///
+/// The result is `{ "ok": true, "value": <output> }` if the tool succeeds and
+/// `{ "ok": false, "error": <error> }` if it fails, so a script can inspect an error without
+/// it stopping the script. A tool that can't be found is captured in the same way as one that
+/// fails.
+///
+pub struct CaptureTool {
+}
+
+impl CaptureTool {
+    ///
+    /// Creates a new capture tool
+    ///
+    pub fn new() -> CaptureTool {
+        CaptureTool { }
+    }
+
+    ///
+    /// Converts the result of a tool into a value
+    ///
+    pub fn reify(result: Result<Value, Value>) -> Value {
+        match result {
+            Ok(value)   => json![{ "ok": true, "value": value }],
+            Err(error)  => json![{ "ok": false, "error": error }]
+        }
+    }
+
+    ///
+    /// Invokes a tool from the environment and captures its result
+    ///
+    pub fn capture(tool_name: &str, input: Value, environment: &Environment) -> Value {
+        let result = get_tool_or_error(environment, tool_name)
+            .and_then(|tool| tool.invoke_json(input, environment));
+
+        CaptureTool::reify(result)
+    }
+}
+
+impl Tool for CaptureTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<CaptureParameters>(input);
+
+        match parameters {
+            Ok(CaptureParameters { tool, input }) => Ok(CaptureTool::capture(&tool, input, environment)),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to capture must be of the form { \"tool\": <tool_name>, \"input\": <value> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_environment() -> DynamicEnvironment {
+        let env = DynamicEnvironment::new();
+
+        env.define("double", Box::new(make_pure_tool(|x: i32| x*2)));
+        env.define("failing", Box::new(make_tool(|_: i32| -> Result<i32, &'static str> { Err("Failed") })));
+
+        env
+    }
+
+    #[test]
+    fn captures_success() {
+        let env     = test_environment();
+        let tool    = TypedTool::<CaptureParameters, Value>::from(Box::new(CaptureTool::new()));
+
+        assert!(tool.invoke(CaptureParameters::new("double", json![ 4 ]), &env) == Ok(json![{ "ok": true, "value": 8 }]));
+    }
+
+    #[test]
+    fn captures_error() {
+        let env     = test_environment();
+        let tool    = TypedTool::<CaptureParameters, Value>::from(Box::new(CaptureTool::new()));
+
+        assert!(tool.invoke(CaptureParameters::new("failing", json![ 4 ]), &env) == Ok(json![{ "ok": false, "error": "Failed" }]));
+    }
+
+    #[test]
+    fn captures_missing_tool() {
+        let env     = test_environment();
+        let tool    = CaptureTool::new();
+
+        let result  = tool.invoke_json(json![{ "tool": "missing", "input": 1 }], &env).unwrap();
+
+        assert!(result["ok"] == json![ false ]);
+        assert!(result["error"]["error"] == json![ "Tool not found" ]);
+    }
+}
@@ -0,0 +1,295 @@
+//!
+//! The compose-tool tool defines a new tool that pipes its input through a series of existing tools
+//!
+
+use std::result::Result;
+use std::error::Error;
+use std::cell::Cell;
+use std::sync::*;
+use std::collections::HashMap;
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+///
+/// Parameters that can be passed to the compose-tool tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct ComposeToolParameters {
+    /// The name of the tool to define
+    pub name: String,
+
+    /// The names of the tools that the input is passed through, in order
+    pub stages: Vec<String>
+}
+
+/// The number of composed tools that can be running inside each other on one thread before invocation fails
+pub const MAX_COMPOSITION_DEPTH: usize = 100;
+
+thread_local! {
+    /// The number of composed tools that are currently running on this thread
+    static COMPOSITION_DEPTH: Cell<usize> = Cell::new(0);
+}
+
+impl ComposeToolParameters {
+    pub fn new(name: &str, stages: Vec<&str>) -> ComposeToolParameters {
+        ComposeToolParameters { name: String::from(name), stages: stages.into_iter().map(|stage| String::from(stage)).collect() }
+    }
+}
+
+///
+/// Tool that defines a new tool which passes its input through a series of other tools
+///
+/// The stages are retrieved from the environment when the new tool is defined, so redefining
+/// them later doesn't change what the composed tool does.
+///
+/// A composition can't refer to itself, either directly or through the stages of other tools
+/// defined by this tool, as this is almost always a mistake. Tools can still end up calling each
+/// other by name when they run (for instance, from a script), so composed tools also stop with an
+/// error if they are nested more than `MAX_COMPOSITION_DEPTH` deep.
+///
+pub struct ComposeToolTool {
+    /// The stages of each tool that has been defined by this tool
+    compositions: Arc<Mutex<HashMap<String, Vec<String>>>>
+}
+
+///
+/// A tool created by compose-tool
+///
+pub struct ComposedTool {
+    /// The tools that the input is passed through
+    pipe: PipeTool
+}
+
+///
+/// Increases the composition depth for this thread, restoring it when dropped
+///
+/// The depth is restored even if one of the stages panics, so a failed invocation can't
+/// leave the thread unable to run composed tools.
+///
+struct CompositionDepthGuard {
+    /// The depth to restore when this guard is dropped
+    previous_depth: usize
+}
+
+impl CompositionDepthGuard {
+    fn enter(previous_depth: usize) -> CompositionDepthGuard {
+        COMPOSITION_DEPTH.with(|current| current.set(previous_depth+1));
+        CompositionDepthGuard { previous_depth }
+    }
+}
+
+impl Drop for CompositionDepthGuard {
+    fn drop(&mut self) {
+        COMPOSITION_DEPTH.with(|current| current.set(self.previous_depth));
+    }
+}
+
+impl ComposeToolTool {
+    ///
+    /// Creates a new compose-tool tool
+    ///
+    pub fn new() -> ComposeToolTool {
+        ComposeToolTool { compositions: Arc::new(Mutex::new(HashMap::new())) }
+    }
+
+    ///
+    /// Finds a path through the compositions from a stage back to the tool being defined
+    ///
+    /// Returns the names on the path, starting and ending with the tool being defined, or None
+    /// if defining the tool with these stages wouldn't create a cycle.
+    ///
+    pub fn find_cycle(name: &str, stages: &Vec<String>, compositions: &HashMap<String, Vec<String>>) -> Option<Vec<String>> {
+        // Depth-first search from the stages of the new tool
+        let mut path    = vec![ String::from(name) ];
+        let mut pending = vec![ stages.iter() ];
+        let mut visited = vec![];
+
+        while let Some(next) = pending.last_mut().map(|stages| stages.next()) {
+            match next {
+                Some(stage) if stage == name => {
+                    path.push(stage.clone());
+                    return Some(path);
+                },
+
+                Some(stage) => {
+                    if !visited.contains(stage) {
+                        visited.push(stage.clone());
+
+                        if let Some(stage_stages) = compositions.get(stage) {
+                            path.push(stage.clone());
+                            pending.push(stage_stages.iter());
+                        }
+                    }
+                },
+
+                None => {
+                    // Finished with these stages
+                    pending.pop();
+                    path.pop();
+                }
+            }
+        }
+
+        None
+    }
+
+    ///
+    /// Creates a tool that pipes its input through the named tools from an environment
+    ///
+    pub fn compose(stages: &Vec<String>, environment: &Environment) -> Result<ComposedTool, Value> {
+        let mut tools = vec![];
+
+        for stage in stages.iter() {
+            tools.push(get_tool_or_error(environment, stage)?);
+        }
+
+        Ok(ComposedTool { pipe: pipe(tools) })
+    }
+}
+
+impl Tool for ComposedTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let depth = COMPOSITION_DEPTH.with(|depth| depth.get());
+
+        if depth >= MAX_COMPOSITION_DEPTH {
+            return Err(json![{
+                "error":        "Composed tools are nested too deeply",
+                "max_depth":    MAX_COMPOSITION_DEPTH
+            }]);
+        }
+
+        let _depth = CompositionDepthGuard::enter(depth);
+        self.pipe.invoke_json(input, environment)
+    }
+}
+
+impl Tool for ComposeToolTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<ComposeToolParameters>(input);
+
+        match parameters {
+            Ok(ComposeToolParameters { name, stages }) => {
+                let mut compositions = self.compositions.lock().unwrap();
+
+                if let Some(cycle) = ComposeToolTool::find_cycle(&name, &stages, &compositions) {
+                    return Err(json![{
+                        "error":    "Composition contains a cycle",
+                        "cycle":    cycle
+                    }]);
+                }
+
+                let tool = ComposeToolTool::compose(&stages, environment)?;
+                define_new_tool(environment, &name, Box::new(tool))?;
+                compositions.insert(name, stages);
+
+                Ok(Value::Null)
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to compose-tool must be of the form { \"name\": <tool_name>, \"stages\": [ <tool_name>, ... ] }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::tool_name::*;
+    use std::panic;
+
+    fn test_environment() -> DynamicEnvironment {
+        let env = DynamicEnvironment::new();
+
+        env.define("add-one", Box::new(make_pure_tool(|x: i32| x+1)));
+        env.define("double", Box::new(make_pure_tool(|x: i32| x*2)));
+
+        env
+    }
+
+    #[test]
+    fn composed_tool_runs_stages_in_order() {
+        let env     = test_environment();
+        let tool    = TypedTool::<ComposeToolParameters, Value>::from(Box::new(ComposeToolTool::new()));
+
+        assert!(tool.invoke(ComposeToolParameters::new("add-then-double", vec![ "add-one", "double", "add-one" ]), &env).is_ok());
+
+        let composed = env.get_typed_tool::<i32, i32>("add-then-double").unwrap();
+        assert!(composed.invoke(3, &env) == Ok(9));
+
+        let tools = env.get_typed_tool::<(), ListToolsResult>(LIST_TOOLS).unwrap().invoke((), &env).unwrap();
+        assert!(tools.names.contains(&String::from("add-then-double")));
+    }
+
+    #[test]
+    fn missing_stage_is_error() {
+        let env     = test_environment();
+        let tool    = TypedTool::<ComposeToolParameters, Value>::from(Box::new(ComposeToolTool::new()));
+
+        assert!(tool.invoke(ComposeToolParameters::new("broken", vec![ "add-one", "missing" ]), &env).is_err());
+        assert!(env.get_json_tool("broken").is_err());
+    }
+
+    #[test]
+    fn two_tool_cycle_is_rejected() {
+        let env     = test_environment();
+        let tool    = ComposeToolTool::new();
+
+        assert!(tool.invoke_json(to_value(ComposeToolParameters::new("a", vec![ "add-one" ])).unwrap(), &env).is_ok());
+        assert!(tool.invoke_json(to_value(ComposeToolParameters::new("b", vec![ "double", "a" ])).unwrap(), &env).is_ok());
+
+        let result = tool.invoke_json(to_value(ComposeToolParameters::new("a", vec![ "b" ])).unwrap(), &env);
+
+        assert!(result == Err(json![{
+            "error":    "Composition contains a cycle",
+            "cycle":    [ "a", "b", "a" ]
+        }]));
+
+        // 'a' keeps its original definition
+        assert!(env.get_typed_tool::<i32, i32>("a").unwrap().invoke(1, &env) == Ok(2));
+    }
+
+    #[test]
+    fn self_reference_is_rejected() {
+        let env     = test_environment();
+        let tool    = ComposeToolTool::new();
+
+        assert!(tool.invoke_json(to_value(ComposeToolParameters::new("a", vec![ "add-one", "a" ])).unwrap(), &env).is_err());
+        assert!(env.get_json_tool("a").is_err());
+    }
+
+    #[test]
+    fn runtime_recursion_is_limited() {
+        let env     = test_environment();
+        let tool    = ComposeToolTool::new();
+
+        // A tool that looks up the composed tool by name when it runs, which compose-tool can't detect
+        env.define("call-loop", Box::new(make_dynamic_tool(|input: Value, environment: &Environment| {
+            environment.get_json_tool("loop").map_err(|_| json![ "Missing" ])?.invoke_json(input, environment)
+        })));
+
+        assert!(tool.invoke_json(to_value(ComposeToolParameters::new("loop", vec![ "add-one", "call-loop" ])).unwrap(), &env).is_ok());
+
+        let result = env.get_json_tool("loop").unwrap().invoke_json(json![ 1 ], &env);
+
+        assert!(result.unwrap_err()["error"] == json![ "Composed tools are nested too deeply" ]);
+        assert!(COMPOSITION_DEPTH.with(|depth| depth.get()) == 0);
+    }
+
+    #[test]
+    fn depth_is_restored_when_a_stage_panics() {
+        let env     = test_environment();
+        let tool    = ComposeToolTool::new();
+
+        env.define("panic", Box::new(make_pure_tool(|_: i32| -> i32 { panic!("Stage failed") })));
+        assert!(tool.invoke_json(to_value(ComposeToolParameters::new("panics", vec![ "add-one", "panic" ])).unwrap(), &env).is_ok());
+
+        let composed    = env.get_json_tool("panics").unwrap();
+        let result      = panic::catch_unwind(panic::AssertUnwindSafe(|| composed.invoke_json(json![ 1 ], &env)));
+
+        assert!(result.is_err());
+        assert!(COMPOSITION_DEPTH.with(|depth| depth.get()) == 0);
+    }
+}
@@ -0,0 +1,135 @@
+//!
+//! The gather tool invokes a set of named tools and collects their results into an object
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+///
+/// Parameters that can be passed to the gather tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct GatherParameters {
+    /// Maps the names of the tools to invoke to the input for each tool
+    pub tools: Map<String, Value>
+}
+
+impl GatherParameters {
+    pub fn new(tools: Value) -> GatherParameters {
+        GatherParameters { tools: match tools { Value::Object(tools) => tools, _ => Map::new() } }
+    }
+}
+
+///
+/// The results of the gather tool
+///
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct GatherResult {
+    /// The output of each tool that succeeded, keyed by tool name
+    pub results: Map<String, Value>,
+
+    /// The error from each tool that failed or couldn't be found, keyed by tool name
+    pub errors: Map<String, Value>
+}
+
+///
+/// Tool that invokes several tools and gathers their outputs into a single object
+///
+/// Every tool is invoked, even if some of them fail: the successful outputs end up in `results`
+/// and the failures in `errors`, so each tool name appears in exactly one of the two. The tools
+/// are invoked one at a time, in the order of their names.
+///
+pub struct GatherTool {
+}
+
+impl GatherTool {
+    ///
+    /// Creates a new gather tool
+    ///
+    pub fn new() -> GatherTool {
+        GatherTool { }
+    }
+
+    ///
+    /// Invokes a single tool from the environment
+    ///
+    fn invoke_tool(tool_name: &str, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let tool = get_tool_or_error(environment, tool_name)?;
+
+        tool.invoke_json(input, environment)
+    }
+
+    ///
+    /// Invokes each tool with its input and collects the results
+    ///
+    pub fn gather(tools: Map<String, Value>, environment: &Environment) -> GatherResult {
+        let mut results = Map::new();
+        let mut errors  = Map::new();
+
+        for (tool_name, input) in tools {
+            match GatherTool::invoke_tool(&tool_name, input, environment) {
+                Ok(output)  => { results.insert(tool_name, output); },
+                Err(error)  => { errors.insert(tool_name, error); }
+            }
+        }
+
+        GatherResult { results: results, errors: errors }
+    }
+}
+
+impl Tool for GatherTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<GatherParameters>(input);
+
+        match parameters {
+            Ok(GatherParameters { tools }) => Ok(json![ GatherTool::gather(tools, environment) ]),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to gather must be of the form { \"tools\": { <tool_name>: <input>, ... } }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_environment() -> DynamicEnvironment {
+        let env = DynamicEnvironment::new();
+
+        env.define("double", Box::new(make_pure_tool(|x: i32| x*2)));
+        env.define("negate", Box::new(make_pure_tool(|x: i32| -x)));
+        env.define("failing", Box::new(make_tool(|_: i32| -> Result<i32, &'static str> { Err("Failed") })));
+
+        env
+    }
+
+    #[test]
+    fn gathers_results_from_each_tool() {
+        let env     = test_environment();
+        let tool    = TypedTool::<GatherParameters, GatherResult>::from(Box::new(GatherTool::new()));
+
+        let result  = tool.invoke(GatherParameters::new(json![{ "double": 2, "negate": 3 }]), &env).unwrap();
+
+        assert!(Value::Object(result.results) == json![{ "double": 4, "negate": -3 }]);
+        assert!(result.errors.len() == 0);
+    }
+
+    #[test]
+    fn collects_errors_without_stopping() {
+        let env     = test_environment();
+        let tool    = GatherTool::new();
+
+        let result  = tool.invoke_json(json![{ "tools": { "double": 5, "failing": 1, "missing": 2, "negate": "not a number" } }], &env).unwrap();
+
+        assert!(result["results"] == json![{ "double": 10 }]);
+        assert!(result["errors"]["failing"] == json![ "Failed" ]);
+        assert!(result["errors"]["missing"]["error"] == json![ "Tool not found" ]);
+        assert!(result["errors"]["negate"].is_object());
+    }
+}
@@ -0,0 +1,53 @@
+pub mod time;
+pub mod with_fallback;
+pub mod when;
+pub mod retry;
+pub mod compose_tool;
+pub mod call_named;
+pub mod gather;
+pub mod capture;
+pub mod tool;
+
+pub use self::time::*;
+pub use self::with_fallback::*;
+pub use self::when::*;
+pub use self::retry::*;
+pub use self::compose_tool::*;
+pub use self::call_named::*;
+pub use self::gather::*;
+pub use self::capture::*;
+
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+///
+/// ToolSet containing the tools that control how other tools are invoked
+///
+pub struct FlowTools { }
+
+impl FlowTools {
+    pub fn new() -> FlowTools {
+        FlowTools { }
+    }
+}
+
+impl<'a> ToolSet for &'a FlowTools {
+    fn create_tools(self, _environment: &Environment) -> Vec<(String, Box<Tool>)> {
+        vec![
+            (String::from(self::tool::TIME),            Box::new(TimeTool::new())),
+            (String::from(self::tool::WITH_FALLBACK),   Box::new(WithFallbackTool::new())),
+            (String::from(self::tool::WHEN),            Box::new(WhenTool::new())),
+            (String::from(self::tool::RETRY),           Box::new(RetryTool::new())),
+            (String::from(self::tool::COMPOSE_TOOL),    Box::new(ComposeToolTool::new())),
+            (String::from(self::tool::CALL_NAMED),      Box::new(CallNamedTool::new())),
+            (String::from(self::tool::GATHER),          Box::new(GatherTool::new())),
+            (String::from(self::tool::CAPTURE),         Box::new(CaptureTool::new()))
+        ]
+    }
+}
+
+impl ToolSet for FlowTools {
+    fn create_tools(self, environment: &Environment) -> Vec<(String, Box<Tool>)> {
+        (&self).create_tools(environment)
+    }
+}
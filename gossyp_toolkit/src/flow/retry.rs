@@ -0,0 +1,232 @@
+//!
+//! The retry tool invokes a tool repeatedly until it succeeds, waiting longer between each attempt
+//!
+//! `with_retry` wraps a tool so that it's always retried in this way.
+//!
+
+use std::thread;
+use std::result::Result;
+use std::error::Error;
+use std::time::*;
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+///
+/// Parameters that can be passed to the retry tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct RetryParameters {
+    /// The name of the tool to invoke
+    pub tool: String,
+
+    /// The input to pass to the tool
+    pub input: Option<Value>,
+
+    /// The maximum number of times to invoke the tool
+    pub attempts: u32,
+
+    /// The time to wait after the first failure, in milliseconds (this doubles after each subsequent failure)
+    pub backoff_ms: Option<u64>
+}
+
+impl RetryParameters {
+    pub fn new(tool: &str, input: Value, attempts: u32, backoff_ms: u64) -> RetryParameters {
+        RetryParameters { tool: String::from(tool), input: Some(input), attempts: attempts, backoff_ms: Some(backoff_ms) }
+    }
+}
+
+///
+/// Tool that invokes another tool until it succeeds or runs out of attempts
+///
+/// After attempt `n` (counting from 0) fails, the tool waits for `backoff_ms * 2^n` milliseconds
+/// before trying again. The result is the result of the first successful attempt, or the error
+/// from the last attempt if none of them succeed.
+///
+pub struct RetryTool {
+    /// Function used to wait between attempts
+    sleep: Box<Fn(Duration)+Send+Sync>
+}
+
+impl RetryTool {
+    ///
+    /// Creates a new retry tool
+    ///
+    pub fn new() -> RetryTool {
+        RetryTool::with_sleep(|duration| thread::sleep(duration))
+    }
+
+    ///
+    /// Creates a new retry tool that uses a particular function to wait between attempts
+    ///
+    pub fn with_sleep<TFn: 'static+Send+Sync+Fn(Duration)>(sleep: TFn) -> RetryTool {
+        RetryTool { sleep: Box::new(sleep) }
+    }
+
+    ///
+    /// Returns the time to wait after a particular attempt fails
+    ///
+    pub fn backoff(backoff_ms: u64, attempt: u32) -> Duration {
+        Duration::from_millis(backoff_ms.saturating_mul(1u64.checked_shl(attempt).unwrap_or(u64::max_value())))
+    }
+
+    ///
+    /// Invokes a tool until it succeeds or has been tried a certain number of times
+    ///
+    pub fn retry(&self, tool: &Box<Tool>, input: Value, attempts: u32, backoff_ms: u64, environment: &Environment) -> Result<Value, Value> {
+        let mut attempt = 0;
+
+        loop {
+            let result = tool.invoke_json(input.clone(), environment);
+
+            if result.is_ok() || attempt+1 >= attempts {
+                return result;
+            }
+
+            (self.sleep)(RetryTool::backoff(backoff_ms, attempt));
+            attempt += 1;
+        }
+    }
+}
+
+///
+/// Tool that retries another tool until it succeeds or runs out of attempts
+///
+pub struct RetryingTool {
+    /// The tool to retry
+    tool: Box<Tool>,
+
+    /// The maximum number of times to invoke the tool
+    attempts: u32,
+
+    /// The time to wait after the first failure, in milliseconds
+    backoff_ms: u64,
+
+    /// Used to perform the retries
+    retry: RetryTool
+}
+
+///
+/// Creates a tool that invokes another tool until it succeeds, in the same way as the retry tool
+///
+/// The tool is always invoked at least once.
+///
+pub fn with_retry(tool: Box<Tool>, attempts: u32, backoff_ms: u64) -> RetryingTool {
+    RetryingTool { tool, attempts, backoff_ms, retry: RetryTool::new() }
+}
+
+impl Tool for RetryingTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        self.retry.retry(&self.tool, input, self.attempts, self.backoff_ms, environment)
+    }
+
+    ///
+    /// The description of a retrying tool has the number of attempts and the description of the tool it wraps
+    ///
+    fn describe(&self) -> Option<Value> {
+        Some(json![{
+            "description":  format!("Retries the tool up to {} times", self.attempts),
+            "attempts":     self.attempts,
+            "backoff_ms":   self.backoff_ms,
+            "wraps":        self.tool.describe()
+        }])
+    }
+}
+
+impl Tool for RetryTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<RetryParameters>(input);
+
+        match parameters {
+            Ok(RetryParameters { attempts: 0, .. }) => Err(json![{
+                "error": "The number of attempts passed to retry must be at least 1"
+            }]),
+
+            Ok(RetryParameters { tool, input, attempts, backoff_ms }) => {
+                let tool = get_tool_or_error(environment, &tool)?;
+
+                self.retry(&tool, input.unwrap_or(Value::Null), attempts, backoff_ms.unwrap_or(0), environment)
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to retry must be of the form { \"tool\": <tool_name>, \"input\": <value>, \"attempts\": <number>, \"backoff_ms\": <number> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::*;
+
+    ///
+    /// Creates an environment with a tool that fails a certain number of times before succeeding
+    ///
+    fn environment_with_flaky_tool(failures: u32) -> DynamicEnvironment {
+        let env         = DynamicEnvironment::new();
+        let call_count  = Mutex::new(0);
+
+        env.define("flaky", Box::new(make_tool(move |x: i32| {
+            let mut call_count = call_count.lock().unwrap();
+            *call_count += 1;
+
+            if *call_count <= failures { Err(json![{ "error": "Failed", "attempt": *call_count }]) } else { Ok(x) }
+        })));
+
+        env
+    }
+
+    #[test]
+    fn succeeds_after_failures() {
+        let env         = environment_with_flaky_tool(2);
+        let delays      = Arc::new(Mutex::new(vec![]));
+        let record      = delays.clone();
+        let tool        = RetryTool::with_sleep(move |duration| record.lock().unwrap().push(duration));
+
+        let result      = tool.invoke_json(to_value(RetryParameters::new("flaky", json![ 42 ], 5, 10)).unwrap(), &env);
+
+        assert!(result == Ok(json![ 42 ]));
+        assert!(*delays.lock().unwrap() == vec![ Duration::from_millis(10), Duration::from_millis(20) ]);
+    }
+
+    #[test]
+    fn returns_last_error() {
+        let env         = environment_with_flaky_tool(5);
+        let tool        = RetryTool::with_sleep(|_| { });
+
+        let result      = tool.invoke_json(to_value(RetryParameters::new("flaky", json![ 42 ], 3, 10)).unwrap(), &env);
+
+        assert!(result == Err(json![{ "error": "Failed", "attempt": 3 }]));
+    }
+
+    #[test]
+    fn wrapped_tool_succeeds_after_failures() {
+        let env         = environment_with_flaky_tool(2);
+        let tool        = with_retry(env.get_json_tool("flaky").unwrap(), 5, 0);
+
+        assert!(tool.invoke_json(json![ 42 ], &env) == Ok(json![ 42 ]));
+    }
+
+    #[test]
+    fn description_includes_timeout_and_wrapped_tool() {
+        let env         = environment_with_flaky_tool(0);
+        let inner       = make_pure_tool(|x: i32| x);
+        let tool        = with_retry(Box::new(with_timeout(Box::new(inner), Duration::from_millis(50), Arc::new(env))), 3, 10);
+
+        let description = tool.describe().unwrap();
+
+        assert!(description["description"] == json![ "Retries the tool up to 3 times" ]);
+        assert!(description["wraps"]["description"] == json![ "Gives up if the tool takes longer than 50ms" ]);
+        assert!(description["wraps"]["wraps"] == Value::Null);
+    }
+
+    #[test]
+    fn zero_attempts_is_error() {
+        let env         = environment_with_flaky_tool(0);
+        let tool        = RetryTool::with_sleep(|_| { });
+
+        assert!(tool.invoke_json(to_value(RetryParameters::new("flaky", json![ 42 ], 0, 10)).unwrap(), &env).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//!
+//! The time tool measures how long another tool takes to run
+//!
+
+use std::result::Result;
+use std::error::Error;
+use std::time::*;
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+///
+/// Parameters that can be passed to the time tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct TimeParameters {
+    /// The name of the tool to invoke
+    pub tool: String,
+
+    /// The input to pass to the tool
+    pub input: Option<Value>
+}
+
+impl TimeParameters {
+    pub fn new(tool: &str, input: Value) -> TimeParameters {
+        TimeParameters { tool: String::from(tool), input: Some(input) }
+    }
+}
+
+///
+/// Result of the time tool
+///
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct TimeResult {
+    /// The value returned by the tool
+    pub result: Value,
+
+    /// How long the tool took to run, in milliseconds
+    pub millis: f64
+}
+
+///
+/// Tool that invokes another tool and reports how long it took
+///
+/// If the tool fails, the error is of the form `{ "error": <tool error>, "millis": <time> }`
+///
+pub struct TimeTool {
+}
+
+impl TimeTool {
+    ///
+    /// Creates a new time tool
+    ///
+    pub fn new() -> TimeTool {
+        TimeTool { }
+    }
+
+    ///
+    /// Converts a duration to milliseconds
+    ///
+    fn millis(duration: Duration) -> f64 {
+        (duration.as_secs() as f64)*1000.0 + (duration.subsec_nanos() as f64)/1_000_000.0
+    }
+
+    ///
+    /// Invokes a tool, returning its result and how long it took to run
+    ///
+    pub fn time(tool: &Box<Tool>, input: Value, environment: &Environment) -> (Result<Value, Value>, f64) {
+        let start   = Instant::now();
+        let result  = tool.invoke_json(input, environment);
+
+        (result, TimeTool::millis(start.elapsed()))
+    }
+}
+
+impl Tool for TimeTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<TimeParameters>(input);
+
+        match parameters {
+            Ok(TimeParameters { tool, input }) => {
+                // Tool comes from the current environment
+                let tool = get_tool_or_error(environment, &tool)?;
+
+                match TimeTool::time(&tool, input.unwrap_or(Value::Null), environment) {
+                    (Ok(result), millis)    => Ok(to_value(TimeResult { result: result, millis: millis }).unwrap()),
+                    (Err(error), millis)    => Err(json![{ "error": error, "millis": millis }])
+                }
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to time must be of the form { \"tool\": <tool_name>, \"input\": <value> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn can_time_tool() {
+        let env     = DynamicEnvironment::new();
+        env.define("add_one", Box::new(make_pure_tool(|x: i32| x+1)));
+
+        let tool    = TypedTool::<TimeParameters, TimeResult>::from(Box::new(TimeTool::new()));
+        let result  = tool.invoke(TimeParameters::new("add_one", json![1]), &env).unwrap();
+
+        assert!(result.result == json![2]);
+        assert!(result.millis >= 0.0);
+        assert!(result.millis < 60_000.0);
+    }
+
+    #[test]
+    fn errors_include_timing() {
+        let env     = DynamicEnvironment::new();
+        env.define("fail", Box::new(make_tool(|_: ()| -> Result<(), &'static str> { Err("Failed") })));
+
+        let tool    = TimeTool::new();
+        let result  = tool.invoke_json(json![{ "tool": "fail" }], &env);
+
+        assert!(result.is_err());
+
+        let error   = result.unwrap_err();
+        assert!(error["error"] == json!["Failed"]);
+        assert!(error["millis"].as_f64().unwrap() >= 0.0);
+    }
+
+    #[test]
+    fn missing_tool_is_error() {
+        let env     = DynamicEnvironment::new();
+        let tool    = TimeTool::new();
+
+        assert!(tool.invoke_json(json![{ "tool": "missing" }], &env).is_err());
+    }
+}
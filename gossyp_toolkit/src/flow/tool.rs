@@ -0,0 +1,8 @@
+pub const TIME: &'static str            = "time";
+pub const WITH_FALLBACK: &'static str   = "with-fallback";
+pub const WHEN: &'static str            = "when";
+pub const RETRY: &'static str           = "retry";
+pub const COMPOSE_TOOL: &'static str    = "compose-tool";
+pub const CALL_NAMED: &'static str      = "call-named";
+pub const GATHER: &'static str          = "gather";
+pub const CAPTURE: &'static str         = "capture";
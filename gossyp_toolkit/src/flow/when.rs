@@ -0,0 +1,120 @@
+//!
+//! The when tool invokes a tool only if a condition tool passes
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+///
+/// Parameters that can be passed to the when tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct WhenParameters {
+    /// The name of the tool that decides whether or not to invoke the action
+    pub condition: String,
+
+    /// The name of the tool to invoke if the condition passes
+    pub tool: String,
+
+    /// The input to pass to the condition and the tool
+    pub input: Option<Value>
+}
+
+impl WhenParameters {
+    pub fn new(condition: &str, tool: &str, input: Value) -> WhenParameters {
+        WhenParameters { condition: String::from(condition), tool: String::from(tool), input: Some(input) }
+    }
+}
+
+///
+/// Tool that invokes another tool if a condition passes, and otherwise returns its input unchanged
+///
+/// The condition passes if it returns a truthy value: anything other than null, false, 0, an
+/// empty string or an empty array.
+///
+pub struct WhenTool {
+}
+
+impl WhenTool {
+    ///
+    /// Creates a new when tool
+    ///
+    pub fn new() -> WhenTool {
+        WhenTool { }
+    }
+}
+
+impl Tool for WhenTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<WhenParameters>(input);
+
+        match parameters {
+            Ok(WhenParameters { condition, tool, input }) => {
+                let condition   = get_tool_or_error(environment, &condition)?;
+                let tool        = get_tool_or_error(environment, &tool)?;
+                let input       = input.unwrap_or(Value::Null);
+
+                if is_truthy(&condition.invoke_json(input.clone(), environment)?) {
+                    tool.invoke_json(input, environment)
+                } else {
+                    Ok(input)
+                }
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to when must be of the form { \"condition\": <tool_name>, \"tool\": <tool_name>, \"input\": <value> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_environment() -> DynamicEnvironment {
+        let env = DynamicEnvironment::new();
+
+        env.define("is-even", Box::new(make_pure_tool(|x: i32| x%2 == 0)));
+        env.define("halve", Box::new(make_pure_tool(|x: i32| x/2)));
+        env.define("nothing", Box::new(make_pure_tool(|_: Value| json![[]])));
+
+        env
+    }
+
+    #[test]
+    fn invokes_tool_when_condition_passes() {
+        let env     = test_environment();
+        let tool    = TypedTool::<WhenParameters, Value>::from(Box::new(WhenTool::new()));
+
+        assert!(tool.invoke(WhenParameters::new("is-even", "halve", json![ 10 ]), &env) == Ok(json![ 5 ]));
+    }
+
+    #[test]
+    fn passes_input_through_when_condition_fails() {
+        let env     = test_environment();
+        let tool    = TypedTool::<WhenParameters, Value>::from(Box::new(WhenTool::new()));
+
+        assert!(tool.invoke(WhenParameters::new("is-even", "halve", json![ 7 ]), &env) == Ok(json![ 7 ]));
+    }
+
+    #[test]
+    fn missing_tool_is_error() {
+        let env     = test_environment();
+        let tool    = TypedTool::<WhenParameters, Value>::from(Box::new(WhenTool::new()));
+
+        assert!(tool.invoke(WhenParameters::new("is-even", "does-not-exist", json![ 7 ]), &env).is_err());
+    }
+
+    #[test]
+    fn empty_array_fails_condition() {
+        let env     = test_environment();
+        let tool    = TypedTool::<WhenParameters, Value>::from(Box::new(WhenTool::new()));
+
+        assert!(tool.invoke(WhenParameters::new("nothing", "halve", json![ 10 ]), &env) == Ok(json![ 10 ]));
+    }
+}
@@ -0,0 +1,121 @@
+//!
+//! The with-fallback tool invokes one tool, or another if the first does not exist
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+///
+/// Parameters that can be passed to the with-fallback tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct WithFallbackParameters {
+    /// The name of the tool to try first
+    pub primary: String,
+
+    /// The name of the tool to use if the primary tool can't be found
+    pub fallback: String,
+
+    /// The input to pass to the tool
+    pub input: Option<Value>
+}
+
+impl WithFallbackParameters {
+    pub fn new(primary: &str, fallback: &str, input: Value) -> WithFallbackParameters {
+        WithFallbackParameters { primary: String::from(primary), fallback: String::from(fallback), input: Some(input) }
+    }
+}
+
+///
+/// Tool that invokes a tool from the environment, using a fallback tool if it can't be found
+///
+/// The fallback is only used if the primary tool is not in the environment: if the primary tool
+/// exists but returns an error, that error is the result.
+///
+pub struct WithFallbackTool {
+}
+
+impl WithFallbackTool {
+    ///
+    /// Creates a new with-fallback tool
+    ///
+    pub fn new() -> WithFallbackTool {
+        WithFallbackTool { }
+    }
+
+    ///
+    /// Retrieves the primary tool, or the fallback if the primary tool can't be found
+    ///
+    pub fn resolve(primary: &str, fallback: &str, environment: &Environment) -> Result<Box<Tool>, Value> {
+        // Only a tool that can't be found is replaced by the fallback: other retrieval failures are reported
+        match environment.get_json_tool(primary) {
+            Err(erm) => match erm.reason() {
+                RetrieveFailReason::NotFound    => get_tool_or_error(environment, fallback),
+                _                               => Err(tool_not_found_error(primary, &erm))
+            },
+
+            Ok(primary_tool) => Ok(primary_tool)
+        }
+    }
+}
+
+impl Tool for WithFallbackTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<WithFallbackParameters>(input);
+
+        match parameters {
+            Ok(WithFallbackParameters { primary, fallback, input }) => {
+                let tool = WithFallbackTool::resolve(&primary, &fallback, environment)?;
+                tool.invoke_json(input.unwrap_or(Value::Null), environment)
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to with-fallback must be of the form { \"primary\": <tool_name>, \"fallback\": <tool_name>, \"input\": <value> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_environment() -> DynamicEnvironment {
+        let env = DynamicEnvironment::new();
+
+        env.define("fallback", Box::new(make_pure_tool(|x: i32| x+100)));
+        env.define("failing", Box::new(make_tool(|_: i32| -> Result<i32, &'static str> { Err("Failed") })));
+
+        env
+    }
+
+    #[test]
+    fn missing_primary_uses_fallback() {
+        let env     = test_environment();
+        let tool    = TypedTool::<WithFallbackParameters, i32>::from(Box::new(WithFallbackTool::new()));
+
+        assert!(tool.invoke(WithFallbackParameters::new("missing", "fallback", json![1]), &env) == Ok(101));
+    }
+
+    #[test]
+    fn present_primary_is_used() {
+        let env     = test_environment();
+        env.define("primary", Box::new(make_pure_tool(|x: i32| x+1)));
+
+        let tool    = TypedTool::<WithFallbackParameters, i32>::from(Box::new(WithFallbackTool::new()));
+
+        assert!(tool.invoke(WithFallbackParameters::new("primary", "fallback", json![1]), &env) == Ok(2));
+    }
+
+    #[test]
+    fn erroring_primary_does_not_use_fallback() {
+        let env     = test_environment();
+        let tool    = TypedTool::<WithFallbackParameters, i32>::from(Box::new(WithFallbackTool::new()));
+
+        assert!(tool.invoke(WithFallbackParameters::new("failing", "fallback", json![1]), &env) == Err(json!["Failed"]));
+    }
+}
@@ -0,0 +1,232 @@
+//!
+//! The capabilities tool describes the tools available in an environment, grouped by category
+//!
+
+use std::result::Result;
+use std::error::Error;
+use std::io::{empty, sink};
+use std::collections::{HashMap, BTreeMap};
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+use gossyp_base::basic::tool_name::*;
+
+use super::super::algorithm::*;
+use super::super::data::*;
+use super::super::flow::*;
+use super::super::io::*;
+use super::super::text::*;
+use super::IntrospectTools;
+
+/// Category for tools that aren't in any known category
+pub const USER_DEFINED_CATEGORY: &'static str = "user-defined";
+
+///
+/// Parameters that can be passed to the capabilities tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct CapabilitiesParameters {
+    /// Extra categories (mapping category names to the tools in them), used before the built-in ones
+    pub categories: Option<HashMap<String, Vec<String>>>
+}
+
+///
+/// Tool that reports the tools in the environment grouped into categories
+///
+/// The tools from the toolsets in this crate are categorised by the module they are from (eg,
+/// `io` or `algorithm`) and the tools that manage the environment itself are in the `environment`
+/// category. Toolsets from other crates can be given a category when the tool is created (see
+/// `with_categories`), or the tools can be categorised by passing in `{ "categories": { ... } }`.
+/// Anything else is reported as `user-defined`.
+///
+pub struct CapabilitiesTool {
+    /// Categories for tools from outside this crate, which are used before the built-in ones
+    categories: Vec<(String, Vec<String>)>
+}
+
+impl CapabilitiesTool {
+    ///
+    /// Creates a new capabilities tool
+    ///
+    pub fn new() -> CapabilitiesTool {
+        CapabilitiesTool { categories: vec![] }
+    }
+
+    ///
+    /// Creates a new capabilities tool that knows about some extra categories
+    ///
+    pub fn with_categories(categories: Vec<(String, Vec<String>)>) -> CapabilitiesTool {
+        CapabilitiesTool { categories: categories }
+    }
+
+    ///
+    /// Returns the names of the tools created by a toolset
+    ///
+    pub fn toolset_names<TToolSet: ToolSet>(toolset: TToolSet) -> Vec<String> {
+        toolset.create_tools(&EmptyEnvironment::new())
+            .into_iter()
+            .map(|(name, _tool)| name)
+            .collect()
+    }
+
+    ///
+    /// Returns the categories for the tools that are known to this crate
+    ///
+    pub fn known_categories() -> Vec<(String, Vec<String>)> {
+        let mut io_tools = CapabilitiesTool::toolset_names(IoTools::new_with_streams(empty(), sink()));
+        io_tools.extend(CapabilitiesTool::toolset_names(TranscriptTools::new()));
+
+        vec![
+            (String::from("environment"),   vec![ LIST_TOOLS, DEFINE_TOOL, UNDEFINE_TOOL, TOOL_ORIGIN ].into_iter().map(|name| String::from(name)).collect()),
+            (String::from("algorithm"),     CapabilitiesTool::toolset_names(AlgorithmTools::new())),
+            (String::from("data"),          CapabilitiesTool::toolset_names(DataTools::new())),
+            (String::from("flow"),          CapabilitiesTool::toolset_names(FlowTools::new())),
+            (String::from("io"),            io_tools),
+            (String::from("text"),          CapabilitiesTool::toolset_names(TextTools::new())),
+            (String::from("introspect"),    CapabilitiesTool::toolset_names(IntrospectTools::new()))
+        ]
+    }
+
+    ///
+    /// Groups a set of tool names into categories
+    ///
+    /// The categories supplied when the tool was created take precedence over the built-in ones,
+    /// and the extra categories take precedence over both.
+    ///
+    pub fn categorise(&self, names: &Vec<String>, extra_categories: &HashMap<String, Vec<String>>) -> BTreeMap<String, Vec<String>> {
+        // Build a map from tool names to categories
+        let known_categories = CapabilitiesTool::known_categories();
+        let mut category_for_tool: HashMap<&str, &str> = HashMap::new();
+
+        let categories = known_categories.iter()
+            .chain(self.categories.iter())
+            .map(|&(ref category, ref tools)| (category, tools))
+            .chain(extra_categories.iter());
+
+        for (category, tools) in categories {
+            for tool in tools.iter() {
+                category_for_tool.insert(tool, category);
+            }
+        }
+
+        // Group the tools
+        let mut result: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for name in names.iter() {
+            let category = category_for_tool.get(&**name).map(|category| *category).unwrap_or(USER_DEFINED_CATEGORY);
+            result.entry(String::from(category)).or_insert_with(|| vec![]).push(name.clone());
+        }
+
+        for tools in result.values_mut() {
+            tools.sort();
+        }
+
+        result
+    }
+}
+
+impl Tool for CapabilitiesTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        // Null input means there are no extra categories
+        let parameters = match input {
+            Value::Null => Ok(CapabilitiesParameters { categories: None }),
+            input       => from_value::<CapabilitiesParameters>(input)
+        };
+
+        match parameters {
+            Ok(CapabilitiesParameters { categories }) => {
+                // Find out what tools are in the environment
+                let list_tools  = environment.get_json_tool(LIST_TOOLS).map_err(|retrieve_error| json![{
+                    "error":        "Could not retrieve list-tools",
+                    "description":  retrieve_error.message()
+                }])?;
+                let tools       = from_value::<ListToolsResult>(list_tools.invoke_json(Value::Null, environment)?).map_err(|erm| json![{
+                    "error":        "Unexpected result from list-tools",
+                    "description":  erm.description()
+                }])?;
+
+                Ok(json![ self.categorise(&tools.names, &categories.unwrap_or_else(|| HashMap::new())) ])
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to capabilities must be of the form { \"categories\": { <category>: [ <tool_name>, ... ] } }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_environment() -> DynamicEnvironment {
+        let env = DynamicEnvironment::new();
+
+        env.import(IoTools::new_with_streams(empty(), sink()));
+        env.import(AlgorithmTools::new());
+        env.define("my-tool", Box::new(make_pure_tool(|x: i32| x)));
+
+        env
+    }
+
+    #[test]
+    fn groups_tools_by_category() {
+        let env     = test_environment();
+        let tool    = CapabilitiesTool::new();
+
+        assert!(tool.invoke_json(Value::Null, &env) == Ok(json![{
+            "algorithm":    [ "between", "chunk", "clamp", "compare-values", "count-by", "difference", "filter", "intersect", "repeat", "sort", "topo-sort", "transpose", "union" ],
+            "environment":  [ "define-tool", "list-tools", "undefine-tool" ],
+            "io":           [ "capture-output", "golden-test", "load-config", "print", "println", "read-line", "wait-for-file-change", "watch-file", "write-bytes" ],
+            "user-defined": [ "my-tool" ]
+        }]));
+    }
+
+    struct MyTools { }
+
+    impl ToolSet for MyTools {
+        fn create_tools(self, _environment: &Environment) -> Vec<(String, Box<Tool>)> {
+            vec![ (String::from("my-tool"), Box::new(make_pure_tool(|x: i32| x))) ]
+        }
+    }
+
+    #[test]
+    fn can_categorise_other_toolsets() {
+        let env     = test_environment();
+        let tool    = CapabilitiesTool::with_categories(vec![ (String::from("mine"), CapabilitiesTool::toolset_names(MyTools { })) ]);
+
+        let result  = tool.invoke_json(Value::Null, &env).unwrap();
+
+        assert!(result["mine"] == json![[ "my-tool" ]]);
+        assert!(result.get(USER_DEFINED_CATEGORY).is_none());
+    }
+
+    #[test]
+    fn every_toolkit_tool_has_a_category() {
+        let env = DynamicEnvironment::new();
+
+        env.import(IoTools::new_with_streams(empty(), sink()));
+        env.import(TranscriptTools::new());
+        env.import(AlgorithmTools::new());
+        env.import(DataTools::new());
+        env.import(FlowTools::new());
+        env.import(TextTools::new());
+        env.import(IntrospectTools::new());
+
+        let result = CapabilitiesTool::new().invoke_json(Value::Null, &env).unwrap();
+
+        assert!(result.get(USER_DEFINED_CATEGORY).is_none());
+    }
+
+    #[test]
+    fn can_supply_extra_categories() {
+        let env     = test_environment();
+        let tool    = CapabilitiesTool::new();
+
+        let result  = tool.invoke_json(json![{ "categories": { "mine": [ "my-tool" ] } }], &env).unwrap();
+
+        assert!(result["mine"] == json![[ "my-tool" ]]);
+        assert!(result.get(USER_DEFINED_CATEGORY).is_none());
+    }
+}
@@ -0,0 +1,106 @@
+//!
+//! The describe-tool tool reports the description of a tool in the environment
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+///
+/// Tool that takes the name of a tool and returns its description
+///
+/// The description is whatever the tool returns from `describe`. Tools that wrap other tools
+/// (such as those created by `with_retry` or `with_timeout`) include the description of the tool
+/// they wrap in their `wraps` field, so the result describes the whole chain.
+///
+pub struct DescribeTool {
+}
+
+impl DescribeTool {
+    ///
+    /// Creates a new describe-tool tool
+    ///
+    pub fn new() -> DescribeTool {
+        DescribeTool { }
+    }
+
+    ///
+    /// Retrieves the description of a tool in an environment
+    ///
+    pub fn describe(name: &str, environment: &Environment) -> Result<Value, Value> {
+        let tool = get_tool_or_error(environment, name)?;
+
+        tool.describe().ok_or_else(|| json![{
+            "error":        "Tool has no description",
+            "tool_name":    name
+        }])
+    }
+}
+
+impl Tool for DescribeTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        match from_value::<String>(input) {
+            Ok(name) => DescribeTool::describe(&name, environment),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to describe-tool must be the name of a tool",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::*;
+    use std::time::*;
+    use flow::*;
+
+    ///
+    /// A tool that has a description
+    ///
+    struct DescribedTool;
+
+    impl Tool for DescribedTool {
+        fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+            Ok(input)
+        }
+
+        fn describe(&self) -> Option<Value> {
+            Some(json![{ "description": "Returns its input" }])
+        }
+    }
+
+    #[test]
+    fn describes_tool() {
+        let env = DynamicEnvironment::new();
+        env.define("described", Box::new(DescribedTool));
+
+        assert!(DescribeTool::new().invoke_json(json![ "described" ], &env) == Ok(json![{ "description": "Returns its input" }]));
+    }
+
+    #[test]
+    fn description_of_wrapped_tool_mentions_every_layer() {
+        let env     = DynamicEnvironment::new();
+        let inner   = with_timeout(Box::new(DescribedTool), Duration::from_millis(50), Arc::new(EmptyEnvironment::new()));
+        env.define("retried", Box::new(with_retry(Box::new(inner), 3, 10)));
+
+        let description = DescribeTool::new().invoke_json(json![ "retried" ], &env).unwrap();
+
+        assert!(description["description"] == json![ "Retries the tool up to 3 times" ]);
+        assert!(description["wraps"]["description"] == json![ "Gives up if the tool takes longer than 50ms" ]);
+        assert!(description["wraps"]["wraps"]["description"] == json![ "Returns its input" ]);
+    }
+
+    #[test]
+    fn tool_without_description_is_error() {
+        let env = DynamicEnvironment::new();
+        env.define("add-one", Box::new(make_pure_tool(|x: i32| x+1)));
+
+        assert!(DescribeTool::new().invoke_json(json![ "add-one" ], &env) == Err(json![{ "error": "Tool has no description", "tool_name": "add-one" }]));
+        assert!(DescribeTool::new().invoke_json(json![ "missing" ], &env).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+//!
+//! The manifest tool describes every tool in an environment
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::tool_name::*;
+use gossyp_base::basic::*;
+
+use super::tool::*;
+
+///
+/// Tool that generates a document describing all of the tools in the environment
+///
+/// The result has the form `{ "tools": [ { "name": <tool_name>, ... } ] }`. If the environment
+/// has a `describe-tool` tool (such as `DescribeTool`), it's called with the name of each tool and the fields of the
+/// object it returns (for example, a description or schemas for the input and output) are added
+/// to the entry for that tool. Tools that can't be described only have a name.
+///
+pub struct ManifestTool {
+}
+
+impl ManifestTool {
+    ///
+    /// Creates a new manifest tool
+    ///
+    pub fn new() -> ManifestTool {
+        ManifestTool { }
+    }
+
+    ///
+    /// Generates the manifest entry for a single tool
+    ///
+    fn describe(name: &str, describe_tool: &Option<Box<Tool>>, environment: &Environment) -> Value {
+        let mut entry = Map::new();
+
+        // Tools that can't be described just have a name
+        if let &Some(ref describe_tool) = describe_tool {
+            if let Ok(Value::Object(metadata)) = describe_tool.invoke_json(json![ name ], environment) {
+                entry = metadata;
+            }
+        }
+
+        entry.insert(String::from("name"), json![ name ]);
+
+        Value::Object(entry)
+    }
+
+    ///
+    /// Generates a manifest for the tools in an environment
+    ///
+    pub fn manifest(environment: &Environment) -> Result<Value, Value> {
+        // Fetch the names of the tools
+        let list_tools      = environment.get_json_tool(LIST_TOOLS).map_err(|retrieve_error| json![{
+            "error":        "Could not retrieve list-tools",
+            "description":  retrieve_error.message()
+        }])?;
+        let mut names       = from_value::<ListToolsResult>(list_tools.invoke_json(Value::Null, environment)?).map_err(|erm| json![{
+            "error":        "Unexpected result from list-tools",
+            "description":  erm.description()
+        }])?.names;
+        names.sort();
+
+        // Describe each one
+        let describe_tool   = environment.get_json_tool(DESCRIBE_TOOL).ok();
+        let tools           = names.iter()
+            .map(|name| ManifestTool::describe(name, &describe_tool, environment))
+            .collect::<Vec<_>>();
+
+        Ok(json![{ "tools": tools }])
+    }
+}
+
+impl Tool for ManifestTool {
+    fn invoke_json(&self, _input: Value, environment: &Environment) -> Result<Value, Value> {
+        ManifestTool::manifest(environment)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn manifest_describes_tools() {
+        let env = DynamicEnvironment::new();
+
+        env.define("add-one", Box::new(make_pure_tool(|x: i32| x+1)));
+        env.define("undescribed", Box::new(make_pure_tool(|x: i32| x)));
+        env.define(DESCRIBE_TOOL, Box::new(make_tool(|name: String| {
+            match name.as_str() {
+                "add-one"   => Ok(json![{ "description": "Adds one to a number", "input": "number", "output": "number" }]),
+                _           => Err(json![{ "error": "No metadata" }])
+            }
+        })));
+
+        let manifest = ManifestTool::new().invoke_json(Value::Null, &env).unwrap();
+        let tools    = manifest["tools"].as_array().unwrap();
+
+        assert!(tools.contains(&json![{ "name": "add-one", "description": "Adds one to a number", "input": "number", "output": "number" }]));
+        assert!(tools.contains(&json![{ "name": "undescribed" }]));
+        assert!(tools.contains(&json![{ "name": "list-tools" }]));
+    }
+
+    #[test]
+    fn tools_have_names_without_describe_tool() {
+        let env = DynamicEnvironment::new();
+
+        env.define("add-one", Box::new(make_pure_tool(|x: i32| x+1)));
+
+        let manifest = ManifestTool::new().invoke_json(Value::Null, &env).unwrap();
+
+        assert!(manifest["tools"].as_array().unwrap().contains(&json![{ "name": "add-one" }]));
+    }
+}
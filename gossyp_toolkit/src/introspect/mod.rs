@@ -0,0 +1,52 @@
+pub mod capabilities;
+pub mod manifest;
+pub mod which;
+pub mod describe;
+pub mod tool;
+
+pub use self::capabilities::*;
+pub use self::manifest::*;
+pub use self::which::*;
+pub use self::describe::*;
+
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+///
+/// ToolSet containing tools for finding out about the environment
+///
+pub struct IntrospectTools {
+    /// Categories for tools from outside this crate, passed on to the capabilities tool
+    categories: Vec<(String, Vec<String>)>
+}
+
+impl IntrospectTools {
+    pub fn new() -> IntrospectTools {
+        IntrospectTools { categories: vec![] }
+    }
+
+    ///
+    /// Adds a category for the tools in a toolset from outside this crate
+    ///
+    pub fn with_category<TToolSet: ToolSet>(mut self, category: &str, toolset: TToolSet) -> IntrospectTools {
+        self.categories.push((String::from(category), CapabilitiesTool::toolset_names(toolset)));
+        self
+    }
+}
+
+impl<'a> ToolSet for &'a IntrospectTools {
+    fn create_tools(self, _environment: &Environment) -> Vec<(String, Box<Tool>)> {
+        vec![
+            (String::from(self::tool::CAPABILITIES),    Box::new(CapabilitiesTool::with_categories(self.categories.clone()))),
+            (String::from(self::tool::MANIFEST),        Box::new(ManifestTool::new())),
+            (String::from(self::tool::WHICH),           Box::new(WhichTool::new())),
+            (String::from(self::tool::DESCRIBE_TOOL),   Box::new(DescribeTool::new()))
+        ]
+    }
+}
+
+impl ToolSet for IntrospectTools {
+    fn create_tools(self, environment: &Environment) -> Vec<(String, Box<Tool>)> {
+        (&self).create_tools(environment)
+    }
+}
@@ -0,0 +1,4 @@
+pub const CAPABILITIES: &'static str    = "capabilities";
+pub const MANIFEST: &'static str        = "manifest";
+pub const WHICH: &'static str           = "which";
+pub const DESCRIBE_TOOL: &'static str   = "describe-tool";
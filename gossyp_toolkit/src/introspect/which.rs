@@ -0,0 +1,146 @@
+//!
+//! The which tool reports where a tool in the environment comes from
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+use gossyp_base::basic::tool_name::*;
+
+use super::capabilities::*;
+
+///
+/// Result of the which tool
+///
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct WhichResult {
+    /// The name of the tool
+    pub name: String,
+
+    /// The index of the environment that provided the tool, if the environment is a combined environment
+    pub environment: Option<usize>,
+
+    /// The category that the tool is in, if it's one of the tools from this crate
+    pub category: Option<String>
+}
+
+///
+/// Tool that takes the name of a tool and reports which environment provided it
+///
+/// In a combined environment, this uses the `tool-origin` tool to find the index of the
+/// environment that resolved the name, which is useful for finding out which environment is
+/// overriding a tool. In other environments, the index is `null`.
+///
+pub struct WhichTool {
+}
+
+impl WhichTool {
+    ///
+    /// Creates a new which tool
+    ///
+    pub fn new() -> WhichTool {
+        WhichTool { }
+    }
+
+    ///
+    /// Finds the category of a tool, if it's known
+    ///
+    pub fn category(name: &str) -> Option<String> {
+        CapabilitiesTool::known_categories().into_iter()
+            .filter(|&(_, ref tools)| tools.iter().any(|tool| tool == name))
+            .map(|(category, _)| category)
+            .nth(0)
+    }
+
+    ///
+    /// Finds where a tool comes from in an environment
+    ///
+    pub fn which(name: &str, environment: &Environment) -> Result<WhichResult, Value> {
+        let environment_index = match environment.get_json_tool(TOOL_ORIGIN) {
+            Ok(tool_origin) => {
+                // Combined environments can say which environment provided the tool
+                let origin = tool_origin.invoke_json(json![ name ], environment)
+                    .map_err(|_| tool_not_found_error(name, &RetrieveToolError::not_found()))?;
+
+                origin["environment"].as_u64().map(|index| index as usize)
+            },
+
+            Err(_) => {
+                // Other environments can only say whether or not the tool exists
+                get_tool_or_error(environment, name)?;
+
+                None
+            }
+        };
+
+        Ok(WhichResult {
+            name:           String::from(name),
+            environment:    environment_index,
+            category:       WhichTool::category(name)
+        })
+    }
+}
+
+impl Tool for WhichTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        match from_value::<String>(input) {
+            Ok(name) => Ok(to_value(WhichTool::which(&name, environment)?).unwrap()),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to which must be the name of a tool",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reports_second_environment_when_first_lacks_tool() {
+        let first   = DynamicEnvironment::new();
+        let second  = DynamicEnvironment::new();
+
+        first.define("first-tool", Box::new(make_pure_tool(|x: i32| x+1)));
+        second.define("second-tool", Box::new(make_pure_tool(|x: i32| x+2)));
+
+        let combined    = CombinedEnvironment::from_environments(vec![ &first, &second ]);
+        let tool        = WhichTool::new();
+
+        assert!(tool.invoke_json(json!["second-tool"], &combined) == Ok(json![{ "name": "second-tool", "environment": 1, "category": null }]));
+        assert!(tool.invoke_json(json!["first-tool"], &combined) == Ok(json![{ "name": "first-tool", "environment": 0, "category": null }]));
+    }
+
+    #[test]
+    fn reports_environment_that_overrides_tool_without_listing_it() {
+        let first   = StaticEnvironment::from_tool("tool", Box::new(make_pure_tool(|x: i32| x+1)));
+        let second  = DynamicEnvironment::new();
+
+        second.define("tool", Box::new(make_pure_tool(|x: i32| x+2)));
+
+        let combined = CombinedEnvironment::from_environments(vec![ &first, &second ]);
+
+        assert!(WhichTool::which("tool", &combined) == Ok(WhichResult { name: String::from("tool"), environment: Some(0), category: None }));
+    }
+
+    #[test]
+    fn reports_category_of_known_tools() {
+        let env = DynamicEnvironment::new();
+
+        assert!(WhichTool::which(LIST_TOOLS, &env) == Ok(WhichResult { name: String::from(LIST_TOOLS), environment: None, category: Some(String::from("environment")) }));
+    }
+
+    #[test]
+    fn missing_tool_is_an_error() {
+        let first       = DynamicEnvironment::new();
+        let second      = DynamicEnvironment::new();
+        let combined    = CombinedEnvironment::from_environments(vec![ &first, &second ]);
+
+        assert!(WhichTool::new().invoke_json(json!["missing-tool"], &combined).is_err());
+        assert!(WhichTool::new().invoke_json(json!["missing-tool"], &first).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//!
+//! The capture-output tool runs another tool and returns what it wrote along with its result
+//!
+//! The print and write-bytes tools usually write to a fixed stream, but if the environment they
+//! are invoked in contains an `output-stream` tool, they will send their output there instead (as
+//! an array of bytes). This tool works by invoking a tool in an environment with an `output-stream`
+//! tool that collects whatever is written to it.
+//!
+
+use std::result::Result;
+use std::error::Error;
+use std::sync::*;
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+use super::tool::*;
+
+///
+/// Writes some bytes to the output stream defined by an environment
+///
+/// Returns None if the environment does not define an output stream, in which case the
+/// bytes should be written to the tool's usual stream.
+///
+pub fn write_to_output_stream(bytes: &[u8], environment: &Environment) -> Option<Result<Value, Value>> {
+    environment.get_json_tool(OUTPUT_STREAM).ok()
+        .map(|output_stream| output_stream.invoke_json(json![ bytes ], environment))
+}
+
+///
+/// Parameters that can be passed to the capture-output tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct CaptureOutputParameters {
+    /// The name of the tool to invoke
+    pub tool: String,
+
+    /// The input to pass to the tool
+    pub input: Option<Value>
+}
+
+impl CaptureOutputParameters {
+    pub fn new(tool: &str, input: Value) -> CaptureOutputParameters {
+        CaptureOutputParameters { tool: String::from(tool), input: Some(input) }
+    }
+}
+
+///
+/// Result of the capture-output tool
+///
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct CaptureOutputResult {
+    /// The value returned by the tool
+    pub result: Value,
+
+    /// The output written by the tool (invalid UTF-8 is replaced)
+    pub output: String
+}
+
+///
+/// Output stream tool that stores everything written to it in a buffer
+///
+struct OutputBufferTool {
+    buffer: Arc<Mutex<Vec<u8>>>
+}
+
+impl Tool for OutputBufferTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        match from_value::<Vec<u8>>(input) {
+            Ok(bytes)   => { self.buffer.lock().unwrap().extend(bytes); Ok(Value::Null) },
+            Err(erm)    => Err(json![{
+                "error":        "Output must be an array of bytes",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+///
+/// Tool that invokes another tool and captures anything it writes using the print or write-bytes tools
+///
+/// If the tool fails, the error is of the form `{ "error": <tool error>, "output": <output> }`
+///
+pub struct CaptureOutputTool {
+}
+
+impl CaptureOutputTool {
+    ///
+    /// Creates a new capture-output tool
+    ///
+    pub fn new() -> CaptureOutputTool {
+        CaptureOutputTool { }
+    }
+
+    ///
+    /// Invokes a tool, returning its result and the output it wrote
+    ///
+    pub fn capture(tool: &Box<Tool>, input: Value, environment: &Environment) -> (Result<Value, Value>, String) {
+        // The output stream in this environment overrides any that's defined in the main environment
+        let buffer          = Arc::new(Mutex::new(vec![]));
+        let output_env      = StaticEnvironment::from_tool(OUTPUT_STREAM, Box::new(OutputBufferTool { buffer: buffer.clone() }));
+        let capture_env     = CombinedEnvironment::from_environments(vec![ &output_env, environment ]);
+
+        let result          = tool.invoke_json(input, &capture_env);
+        let output          = String::from_utf8_lossy(&buffer.lock().unwrap()).into_owned();
+
+        (result, output)
+    }
+}
+
+impl Tool for CaptureOutputTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<CaptureOutputParameters>(input);
+
+        match parameters {
+            Ok(CaptureOutputParameters { tool, input }) => {
+                // Tool comes from the current environment
+                let tool = get_tool_or_error(environment, &tool)?;
+
+                match CaptureOutputTool::capture(&tool, input.unwrap_or(Value::Null), environment) {
+                    (Ok(result), output)    => Ok(to_value(CaptureOutputResult { result: result, output: output }).unwrap()),
+                    (Err(error), output)    => Err(json![{ "error": error, "output": output }])
+                }
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to capture-output must be of the form { \"tool\": <tool_name>, \"input\": <value> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::IoTools;
+    use std::io;
+    use std::io::Write;
+
+    struct TestOutput(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for TestOutput {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.lock().unwrap().write(buf) }
+        fn flush(&mut self) -> io::Result<()> { Ok(()) }
+    }
+
+    fn greeting_tool(greeting: &'static str) -> Box<Tool> {
+        Box::new(make_dynamic_tool(move |name: String, environment: &Environment| -> Result<usize, Value> {
+            let print = environment.get_json_tool(PRINT).map_err(|erm| json![ erm.message() ])?;
+            print.invoke_json(json![ format!("{}, {}", greeting, name) ], environment)?;
+
+            Ok(name.len())
+        }))
+    }
+
+    #[test]
+    fn captures_output_from_each_tool_separately() {
+        let main_output = Arc::new(Mutex::new(vec![]));
+        let env         = DynamicEnvironment::new();
+        env.import(IoTools::new_with_streams(io::empty(), TestOutput(main_output.clone())));
+        env.define("hello", greeting_tool("Hello"));
+        env.define("goodbye", greeting_tool("Goodbye"));
+
+        let capture     = TypedTool::<CaptureOutputParameters, CaptureOutputResult>::from(Box::new(CaptureOutputTool::new()));
+
+        let hello       = capture.invoke(CaptureOutputParameters::new("hello", json!["world"]), &env);
+        let goodbye     = capture.invoke(CaptureOutputParameters::new("goodbye", json!["moon"]), &env);
+
+        assert!(hello == Ok(CaptureOutputResult { result: json![5], output: String::from("Hello, world") }));
+        assert!(goodbye == Ok(CaptureOutputResult { result: json![4], output: String::from("Goodbye, moon") }));
+        assert!(main_output.lock().unwrap().len() == 0);
+    }
+
+    #[test]
+    fn captures_bytes_written_directly() {
+        let main_output = Arc::new(Mutex::new(vec![]));
+        let env         = DynamicEnvironment::new();
+        env.import(IoTools::new_with_streams(io::empty(), TestOutput(main_output.clone())));
+
+        let capture     = CaptureOutputTool::new();
+        let result      = capture.invoke_json(json![{ "tool": WRITE_BYTES, "input": [ 104, 105 ] }], &env);
+
+        assert!(result == Ok(json![{ "result": null, "output": "hi" }]));
+        assert!(main_output.lock().unwrap().len() == 0);
+    }
+}
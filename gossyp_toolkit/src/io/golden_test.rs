@@ -0,0 +1,191 @@
+//!
+//! The golden-test tool checks the output of a tool against the expected output stored in a file
+//!
+
+use std::fs;
+use std::result::Result;
+use std::error::Error;
+use std::collections::BTreeSet;
+use serde_json::*;
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+use super::super::data::*;
+
+///
+/// Parameters that can be passed to the golden-test tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct GoldenTestParameters {
+    /// The name of the tool to test
+    pub tool: String,
+
+    /// The input to pass to the tool
+    pub input: Value,
+
+    /// The path of a JSON file containing the expected output of the tool
+    pub expected_file: String
+}
+
+impl GoldenTestParameters {
+    pub fn new(tool: &str, input: Value, expected_file: &str) -> GoldenTestParameters {
+        GoldenTestParameters { tool: String::from(tool), input: input, expected_file: String::from(expected_file) }
+    }
+}
+
+///
+/// Tool that invokes a tool and checks that its output is the same as the value in a 'golden' file
+///
+/// The result is the output of the tool if it's equal to the expected value. Otherwise the result
+/// is an error with a list of differences, each of which has a `path` in the format used by
+/// flatten-paths along with the `expected` and `actual` values at that path (a missing value is
+/// left out).
+///
+pub struct GoldenTestTool {
+}
+
+impl GoldenTestTool {
+    ///
+    /// Creates a new golden-test tool
+    ///
+    pub fn new() -> GoldenTestTool {
+        GoldenTestTool { }
+    }
+
+    ///
+    /// Reads the expected value from a golden file
+    ///
+    pub fn read_expected(expected_file: &str) -> Result<Value, Value> {
+        let contents = fs::read_to_string(expected_file).map_err(|erm| json![{
+            "error":        "Could not read golden file",
+            "file":         expected_file,
+            "description":  erm.description()
+        }])?;
+
+        from_str::<Value>(&contents).map_err(|erm| json![{
+            "error":        "Golden file is not valid JSON",
+            "file":         expected_file,
+            "description":  erm.description()
+        }])
+    }
+
+    ///
+    /// Finds the differences between an expected and an actual value (an empty list if they're equal)
+    ///
+    pub fn differences(expected: &Value, actual: &Value) -> Vec<Value> {
+        let mut expected_paths  = FlattenPathsTool::flatten(expected);
+        let mut actual_paths    = FlattenPathsTool::flatten(actual);
+        let all_paths           = expected_paths.keys().chain(actual_paths.keys()).cloned().collect::<BTreeSet<_>>();
+
+        all_paths.into_iter()
+            .filter_map(|path| {
+                let expected_value  = expected_paths.remove(&path);
+                let actual_value    = actual_paths.remove(&path);
+
+                if expected_value == actual_value {
+                    None
+                } else {
+                    let mut difference = Map::new();
+                    difference.insert(String::from("path"), Value::String(path));
+                    if let Some(expected_value) = expected_value { difference.insert(String::from("expected"), expected_value); }
+                    if let Some(actual_value) = actual_value { difference.insert(String::from("actual"), actual_value); }
+
+                    Some(Value::Object(difference))
+                }
+            })
+            .collect()
+    }
+
+    ///
+    /// Invokes a tool and compares its output with the contents of a golden file
+    ///
+    pub fn test(tool_name: &str, input: Value, expected_file: &str, environment: &Environment) -> Result<Value, Value> {
+        let tool        = get_tool_or_error(environment, tool_name)?;
+
+        let expected    = GoldenTestTool::read_expected(expected_file)?;
+        let actual      = tool.invoke_json(input, environment)?;
+
+        if expected == actual {
+            Ok(actual)
+        } else {
+            // The differences only describe the mismatch: if the paths can't show it, report the whole value
+            let mut differences = GoldenTestTool::differences(&expected, &actual);
+            if differences.len() == 0 {
+                differences.push(json![{ "path": "", "expected": expected, "actual": actual }]);
+            }
+
+            Err(json![{
+                "error":        "Output does not match golden file",
+                "tool_name":    tool_name,
+                "file":         expected_file,
+                "differences":  differences
+            }])
+        }
+    }
+}
+
+impl Tool for GoldenTestTool {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<GoldenTestParameters>(input);
+
+        match parameters {
+            Ok(GoldenTestParameters { tool, input, expected_file }) => {
+                GoldenTestTool::test(&tool, input, &expected_file, environment)
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to golden-test must be of the form { \"tool\": <tool_name>, \"input\": <value>, \"expected_file\": <path> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::temp_file::*;
+
+    fn test_environment() -> DynamicEnvironment {
+        let env = DynamicEnvironment::new();
+        env.define("describe", Box::new(make_pure_tool(|x: i32| json![{ "value": x, "doubled": [ x*2 ] }])));
+
+        env
+    }
+
+    #[test]
+    fn passes_when_output_matches_golden_file() {
+        let env     = test_environment();
+        let file    = TempFile::with_contents("match.json", "{ \"value\": 2, \"doubled\": [ 4 ] }");
+        let tool    = TypedTool::<GoldenTestParameters, Value>::from(Box::new(GoldenTestTool::new()));
+
+        assert!(tool.invoke(GoldenTestParameters::new("describe", json![ 2 ], file.path()), &env) == Ok(json![{ "value": 2, "doubled": [ 4 ] }]));
+    }
+
+    #[test]
+    fn reports_differences_when_output_does_not_match() {
+        let env     = test_environment();
+        let file    = TempFile::with_contents("mismatch.json", "{ \"value\": 3, \"doubled\": [ 7 ], \"tripled\": 9 }");
+        let tool    = TypedTool::<GoldenTestParameters, Value>::from(Box::new(GoldenTestTool::new()));
+
+        let result  = tool.invoke(GoldenTestParameters::new("describe", json![ 3 ], file.path()), &env);
+
+        assert!(result.unwrap_err()["differences"] == json![[
+            { "path": "doubled[0]", "expected": 7, "actual": 6 },
+            { "path": "tripled", "expected": 9 }
+        ]]);
+    }
+
+    #[test]
+    fn fails_when_values_differ_but_paths_do_not() {
+        let env     = DynamicEnvironment::new();
+        let file    = TempFile::with_contents("empty.json", "{}");
+        env.define("empty-array", Box::new(make_pure_tool(|_: Value| json![[]])));
+
+        let result  = GoldenTestTool::test("empty-array", Value::Null, file.path(), &env);
+
+        assert!(result.unwrap_err()["differences"] == json![[
+            { "path": "", "expected": {}, "actual": [] }
+        ]]);
+    }
+}
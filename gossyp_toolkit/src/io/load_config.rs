@@ -0,0 +1,161 @@
+//!
+//! The load-config tool reads JSON configuration from an environment variable or a file
+//!
+
+use std::env;
+use std::fs;
+use std::io;
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Parameters that can be passed to the load-config tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct LoadConfigParameters {
+    /// The name of an environment variable that can contain the configuration
+    pub env: Option<String>,
+
+    /// The path of a file that can contain the configuration
+    pub file: Option<String>,
+
+    /// The configuration to use if neither the environment variable nor the file are present
+    pub default: Option<Value>
+}
+
+impl LoadConfigParameters {
+    pub fn new(env: Option<&str>, file: Option<&str>, default: Value) -> LoadConfigParameters {
+        LoadConfigParameters { env: env.map(|env| String::from(env)), file: file.map(|file| String::from(file)), default: Some(default) }
+    }
+}
+
+///
+/// Tool that reads JSON configuration from the first place it's available
+///
+/// The places are checked in this order:
+///
+/// * the environment variable named by `env`, if it's set
+/// * the file named by `file`, if it exists
+/// * the value of `default` (or null if there's no default)
+///
+/// If the environment variable or the file are present but don't contain valid JSON the result is
+/// an error rather than the next configuration in the list, so mistakes don't go unnoticed.
+///
+pub struct LoadConfigTool {
+}
+
+impl LoadConfigTool {
+    ///
+    /// Creates a new load-config tool
+    ///
+    pub fn new() -> LoadConfigTool {
+        LoadConfigTool { }
+    }
+
+    ///
+    /// Parses configuration read from a particular source
+    ///
+    fn parse(config: &str, source: Value) -> Result<Value, Value> {
+        from_str::<Value>(config).map_err(|erm| json![{
+            "error":        "Configuration is not valid JSON",
+            "source":       source,
+            "description":  erm.description()
+        }])
+    }
+
+    ///
+    /// Loads configuration from an environment variable, a file or a default value
+    ///
+    pub fn load(env_var: Option<&str>, file: Option<&str>, default: Value) -> Result<Value, Value> {
+        LoadConfigTool::load_with_env_lookup(env_var, file, default, |name| env::var(name).ok())
+    }
+
+    ///
+    /// Loads configuration, using a function to look up the value of environment variables
+    ///
+    pub fn load_with_env_lookup<TLookup: Fn(&str) -> Option<String>>(env_var: Option<&str>, file: Option<&str>, default: Value, lookup_env: TLookup) -> Result<Value, Value> {
+        // Environment variable
+        if let Some(env_var) = env_var {
+            if let Some(config) = lookup_env(env_var) {
+                return LoadConfigTool::parse(&config, json![{ "env": env_var }]);
+            }
+        }
+
+        // File
+        if let Some(file) = file {
+            match fs::read_to_string(file) {
+                Ok(config)                                              => return LoadConfigTool::parse(&config, json![{ "file": file }]),
+                Err(ref erm) if erm.kind() == io::ErrorKind::NotFound   => (),
+                Err(erm)                                                => return Err(json![{
+                    "error":        "Could not read configuration file",
+                    "file":         file,
+                    "description":  erm.description()
+                }])
+            }
+        }
+
+        // Default
+        Ok(default)
+    }
+}
+
+impl Tool for LoadConfigTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<LoadConfigParameters>(input);
+
+        match parameters {
+            Ok(LoadConfigParameters { env, file, default }) => {
+                LoadConfigTool::load(env.as_ref().map(|env| env.as_str()), file.as_ref().map(|file| file.as_str()), default.unwrap_or(Value::Null))
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to load-config must be of the form { \"env\": <variable_name>, \"file\": <path>, \"default\": <value> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::temp_file::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn environment_variable_is_used_first() {
+        let file    = TempFile::with_contents("env.json", "{ \"from\": \"file\" }");
+        let lookup  = |name: &str| if name == "CONFIG" { Some(String::from("{ \"from\": \"env\" }")) } else { None };
+
+        let result  = LoadConfigTool::load_with_env_lookup(Some("CONFIG"), Some(file.path()), json![{ "from": "default" }], lookup);
+
+        assert!(result == Ok(json![{ "from": "env" }]));
+    }
+
+    #[test]
+    fn file_is_used_if_environment_variable_is_not_set() {
+        let file = TempFile::with_contents("file.json", "{ \"from\": \"file\" }");
+
+        let result = LoadConfigTool::new().invoke_json(to_value(LoadConfigParameters::new(Some("GOSSYP_LOAD_CONFIG_TEST_UNSET"), Some(file.path()), json![{ "from": "default" }])).unwrap(), &EmptyEnvironment::new());
+
+        assert!(result == Ok(json![{ "from": "file" }]));
+    }
+
+    #[test]
+    fn default_is_used_if_nothing_else_is_present() {
+        let file = TempFile::new("does-not-exist.json");
+
+        let result = LoadConfigTool::load(Some("GOSSYP_LOAD_CONFIG_TEST_UNSET"), Some(file.path()), json![{ "from": "default" }]);
+
+        assert!(result == Ok(json![{ "from": "default" }]));
+    }
+
+    #[test]
+    fn invalid_json_is_error() {
+        let file = TempFile::with_contents("invalid.json", "{ not json");
+
+        assert!(LoadConfigTool::load(None, Some(file.path()), json![{ "from": "default" }]).is_err());
+    }
+}
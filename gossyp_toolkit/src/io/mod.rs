@@ -3,10 +3,25 @@ pub mod write_bytes;
 pub mod read_line;
 pub mod tool;
 pub mod shared_stream;
+pub mod record;
+pub mod capture_output;
+pub mod load_config;
+pub mod watch_file;
+pub mod golden_test;
+pub mod transcript;
+
+#[cfg(test)]
+mod temp_file;
 
 pub use self::print::*;
 pub use self::write_bytes::*;
 pub use self::read_line::*;
+pub use self::record::*;
+pub use self::capture_output::*;
+pub use self::load_config::*;
+pub use self::watch_file::*;
+pub use self::golden_test::*;
+pub use self::transcript::*;
 
 use std::io::*;
 use gossyp_base::*;
@@ -21,6 +36,13 @@ pub struct IoTools<ReadStream: Read+Send, WriteStream: Write+Send> {
     write_tools: WriteTools<WriteStream>
 }
 
+///
+/// Tools for recording a transcript of a REPL session
+///
+pub struct TranscriptTools {
+    transcript: Transcript
+}
+
 ///
 /// Tools for reading from a stream
 ///
@@ -56,6 +78,15 @@ impl IoTools<Stdin, Stdout> {
     }
 }
 
+impl TranscriptTools {
+    ///
+    /// Creates a set of transcript tools that share a single transcript
+    ///
+    pub fn new() -> TranscriptTools {
+        TranscriptTools { transcript: Transcript::new() }
+    }
+}
+
 impl<ReadStream: Read+Send> ReadTools<ReadStream> {
     ///
     /// Creates a set of tools for reading from a particular stream
@@ -76,8 +107,12 @@ impl<WriteStream: Write+Send> WriteTools<WriteStream> {
 
 impl<ReadStream: 'static+Read+Send, WriteStream: 'static+Write+Send> ToolSet for IoTools<ReadStream, WriteStream> {
     fn create_tools(self, environment: &Environment) -> Vec<(String, Box<Tool>)> {
-        let mut result = self.read_tools.create_tools(environment);
-        result.extend(self.write_tools.create_tools(environment));
+        expand_tool_aliases(self.create_tools_with_aliases(environment))
+    }
+
+    fn create_tools_with_aliases(self, environment: &Environment) -> Vec<(Vec<String>, Box<Tool>)> {
+        let mut result = self.read_tools.create_tools_with_aliases(environment);
+        result.extend(self.write_tools.create_tools_with_aliases(environment));
 
         result
     }
@@ -88,18 +123,73 @@ impl<ReadStream: 'static+Read+Send> ToolSet for ReadTools<ReadStream> {
         let read_stream = SharedRead::new(self.read_stream);
 
         vec![
-            (String::from(self::tool::READ_LINE), Box::new(ReadLineTool::new_with_stream(read_stream)))
+            (String::from(self::tool::READ_LINE),               Box::new(ReadLineTool::new_with_stream(read_stream))),
+            (String::from(self::tool::LOAD_CONFIG),             Box::new(LoadConfigTool::new())),
+            (String::from(self::tool::WATCH_FILE),              Box::new(WatchFileTool::new())),
+            (String::from(self::tool::WAIT_FOR_FILE_CHANGE),    Box::new(WaitForFileChangeTool::new())),
+            (String::from(self::tool::GOLDEN_TEST),             Box::new(GoldenTestTool::new()))
         ]
     }
 }
 
-impl<WriteStream: 'static+Write+Send> ToolSet for WriteTools<WriteStream> {
+impl<'a> ToolSet for &'a TranscriptTools {
     fn create_tools(self, _environment: &Environment) -> Vec<(String, Box<Tool>)> {
+        vec![
+            (String::from(self::tool::START_TRANSCRIPT),    Box::new(StartTranscriptTool::new(self.transcript.clone()))),
+            (String::from(self::tool::STOP_TRANSCRIPT),     Box::new(StopTranscriptTool::new(self.transcript.clone()))),
+            (String::from(self::tool::RECORD_TRANSCRIPT),   Box::new(RecordTranscriptTool::new(self.transcript.clone()))),
+            (String::from(self::tool::READ_TRANSCRIPT),     Box::new(ReadTranscriptTool::new()))
+        ]
+    }
+}
+
+impl ToolSet for TranscriptTools {
+    fn create_tools(self, environment: &Environment) -> Vec<(String, Box<Tool>)> {
+        (&self).create_tools(environment)
+    }
+}
+
+impl<WriteStream: 'static+Write+Send> ToolSet for WriteTools<WriteStream> {
+    fn create_tools(self, environment: &Environment) -> Vec<(String, Box<Tool>)> {
+        expand_tool_aliases(self.create_tools_with_aliases(environment))
+    }
+
+    fn create_tools_with_aliases(self, _environment: &Environment) -> Vec<(Vec<String>, Box<Tool>)> {
         let write_stream = SharedWrite::new(self.write_stream);
 
         vec![
-            (String::from(self::tool::PRINT),       Box::new(PrintTool::<SharedWrite<WriteStream>>::new_with_stream(write_stream.clone()))),
-            (String::from(self::tool::WRITE_BYTES), Box::new(WriteBytesTool::new_with_stream(write_stream.clone())))
+            (vec![ String::from(self::tool::PRINT), String::from(self::tool::PRINTLN) ],    Box::new(PrintTool::<SharedWrite<WriteStream>>::new_with_stream(write_stream.clone()))),
+            (vec![ String::from(self::tool::WRITE_BYTES) ],                                 Box::new(WriteBytesTool::new_with_stream(write_stream.clone()))),
+            (vec![ String::from(self::tool::CAPTURE_OUTPUT) ],                              Box::new(CaptureOutputTool::new()))
         ]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::tool::*;
+    use std::sync::*;
+
+    struct TestOutput(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for TestOutput {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> { self.0.lock().unwrap().write(buf) }
+        fn flush(&mut self) -> Result<()> { Ok(()) }
+    }
+
+    #[test]
+    fn print_and_println_are_the_same_tool() {
+        let output  = Arc::new(Mutex::new(vec![]));
+        let env     = DynamicEnvironment::new();
+        env.import(IoTools::new_with_streams(empty(), TestOutput(output.clone())));
+
+        let print   = env.get_typed_tool::<&str, ()>(PRINT).unwrap();
+        let println = env.get_typed_tool::<&str, ()>(PRINTLN).unwrap();
+
+        assert!(print.invoke("Hello, ", &env) == Ok(()));
+        assert!(println.invoke("world", &env) == Ok(()));
+
+        assert!(output.lock().unwrap().as_slice() == b"Hello, world");
+    }
+}
@@ -9,6 +9,8 @@ use std::sync::*;
 use serde_json::*;
 use gossyp_base::*;
 
+use super::capture_output::*;
+
 ///
 /// Tool that prints out text for its parameter to a stream
 ///
@@ -36,7 +38,7 @@ impl<Stream: Write+Send> PrintTool<Stream> {
 }
 
 impl<Stream: Write+Send> Tool for PrintTool<Stream> {
-    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
         // Decide what to print
         let print_string = match input {
             Value::String(ref s) => {
@@ -50,6 +52,11 @@ impl<Stream: Write+Send> Tool for PrintTool<Stream> {
             }
         };
 
+        // The environment can redirect the output
+        if let Some(result) = write_to_output_stream(print_string.as_bytes(), environment) {
+            return result.map(|_| Value::Null);
+        }
+
         // Acquire the stream for printing
         let mut target = self.stream.lock().unwrap();
 
@@ -0,0 +1,102 @@
+//!
+//! Wraps a tool so that its invocations are recorded to a file
+//!
+//! Each invocation is appended to the file as a single line of JSON. The line is an object with
+//! an `input` field containing the parameters passed to the tool and either an `output` field
+//! containing the result or an `error` field if the tool failed:
+//!
+//! ```text
+//! {"input":1,"output":2}
+//! {"input":"x","error":{"error":"Bad input"}}
+//! ```
+//!
+
+use std::result::Result;
+use std::io;
+use std::io::*;
+use std::fs::*;
+use std::path::*;
+use std::sync::*;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Tool that records the input and output of another tool to a stream
+///
+pub struct RecordingTool<Stream: Write+Send> {
+    /// The tool being recorded
+    tool: Box<Tool>,
+
+    /// Where the invocations are written
+    stream: Mutex<Stream>
+}
+
+impl<Stream: Write+Send> RecordingTool<Stream> {
+    ///
+    /// Creates a tool that records invocations of another tool to a stream
+    ///
+    pub fn new_with_stream(tool: Box<Tool>, stream: Stream) -> RecordingTool<Stream> {
+        RecordingTool { tool: tool, stream: Mutex::new(stream) }
+    }
+}
+
+///
+/// Creates a tool that appends its inputs and outputs to the file at the specified path
+///
+pub fn record_tool<P: AsRef<Path>>(tool: Box<Tool>, path: P) -> io::Result<RecordingTool<File>> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    Ok(RecordingTool::new_with_stream(tool, file))
+}
+
+impl<Stream: Write+Send> Tool for RecordingTool<Stream> {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
+        let result = self.tool.invoke_json(input.clone(), environment);
+
+        let record = match result {
+            Ok(ref output)  => json![{ "input": input, "output": output }],
+            Err(ref error)  => json![{ "input": input, "error": error }]
+        };
+
+        // Write the whole line while holding the lock so that lines from different threads don't interleave
+        {
+            let mut line    = to_string(&record).unwrap_or(String::from("{}"));
+            line.push('\n');
+
+            // Failing to record an invocation doesn't change its result
+            let mut stream  = self.stream.lock().unwrap();
+            stream.write_all(line.as_bytes()).and_then(|_| stream.flush()).ok();
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::temp_file::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn invocations_are_recorded_as_json_lines() {
+        let file    = TempFile::new("record-tool.jsonl");
+        let path    = file.path();
+
+        let env     = EmptyEnvironment::new();
+        let tool    = record_tool(Box::new(make_tool(|x: i32| if x >= 0 { Ok(x+1) } else { Err("Negative") })), &path).unwrap();
+
+        assert!(tool.invoke_json(json![1], &env) == Ok(json![2]));
+        assert!(tool.invoke_json(json![-1], &env) == Err(json!["Negative"]));
+
+        let mut recorded = String::new();
+        File::open(&path).unwrap().read_to_string(&mut recorded).unwrap();
+
+        let lines: Vec<Value> = recorded.lines().map(|line| from_str(line).unwrap()).collect();
+
+        assert!(lines == vec![
+            json![{ "input": 1, "output": 2 }],
+            json![{ "input": -1, "error": "Negative" }]
+        ]);
+    }
+}
@@ -0,0 +1,57 @@
+//!
+//! Temporary files used by the tests for the I/O tools
+//!
+
+use std::env;
+use std::fs;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counts the temporary files created by this process, so that no two have the same path
+static NEXT_TEMP_FILE: AtomicUsize = AtomicUsize::new(0);
+
+///
+/// A path in the temporary directory, which is deleted when this is dropped
+///
+pub struct TempFile {
+    path: String
+}
+
+impl TempFile {
+    ///
+    /// Creates a new temporary path without creating the file
+    ///
+    /// The process ID keeps test runs that happen at the same time apart, and the counter keeps
+    /// tests in the same run apart.
+    ///
+    pub fn new(name: &str) -> TempFile {
+        let index   = NEXT_TEMP_FILE.fetch_add(1, Ordering::SeqCst);
+        let path    = env::temp_dir().join(format!("gossyp-test-{}-{}-{}", process::id(), index, name));
+
+        TempFile { path: String::from(path.to_str().unwrap()) }
+    }
+
+    ///
+    /// Creates a new temporary file with the specified contents
+    ///
+    pub fn with_contents(name: &str, contents: &str) -> TempFile {
+        let file = TempFile::new(name);
+        fs::write(&file.path, contents).unwrap();
+
+        file
+    }
+
+    ///
+    /// The path of this file
+    ///
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        // The file might never have been created
+        fs::remove_file(&self.path).ok();
+    }
+}
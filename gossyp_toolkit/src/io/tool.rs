@@ -1,5 +1,16 @@
+pub const PRINT: &'static str                   = "print";
+pub const PRINTLN: &'static str                 = "println";
+pub const WRITE_BYTES: &'static str             = "write-bytes";
+pub const CAPTURE_OUTPUT: &'static str          = "capture-output";
+pub const OUTPUT_STREAM: &'static str           = "output-stream";
 
-pub const PRINT: &'static str       = "print";
-pub const WRITE_BYTES: &'static str = "write-bytes";
+pub const READ_LINE: &'static str               = "read-line";
+pub const LOAD_CONFIG: &'static str             = "load-config";
+pub const WATCH_FILE: &'static str              = "watch-file";
+pub const WAIT_FOR_FILE_CHANGE: &'static str    = "wait-for-file-change";
+pub const GOLDEN_TEST: &'static str             = "golden-test";
 
-pub const READ_LINE: &'static str   = "read-line";
+pub const START_TRANSCRIPT: &'static str        = "start-transcript";
+pub const STOP_TRANSCRIPT: &'static str         = "stop-transcript";
+pub const RECORD_TRANSCRIPT: &'static str       = "record-transcript";
+pub const READ_TRANSCRIPT: &'static str         = "read-transcript";
@@ -0,0 +1,289 @@
+//!
+//! The transcript tools record the lines entered into a REPL along with their results, so that a session can be replayed later
+//!
+
+use std::fs;
+use std::io::Write;
+use std::sync::*;
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+/// The prefix for lines in a transcript that record a result rather than an input
+pub const TRANSCRIPT_OUTPUT_PREFIX: &'static str = "// ";
+
+///
+/// Parameters that can be passed to the record-transcript tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct RecordTranscriptParameters {
+    /// The line that was entered
+    pub input: String,
+
+    /// The result of evaluating the line, if it succeeded
+    pub result: Option<Value>,
+
+    /// The error produced by evaluating the line, if it failed
+    pub error: Option<Value>
+}
+
+impl RecordTranscriptParameters {
+    pub fn new(input: &str, result: Result<Value, Value>) -> RecordTranscriptParameters {
+        match result {
+            Ok(result)  => RecordTranscriptParameters { input: String::from(input), result: Some(result), error: None },
+            Err(error)  => RecordTranscriptParameters { input: String::from(input), result: None, error: Some(error) }
+        }
+    }
+}
+
+///
+/// The transcript file that is currently being written, shared between the transcript tools
+///
+#[derive(Clone)]
+pub struct Transcript {
+    file: Arc<Mutex<Option<fs::File>>>
+}
+
+///
+/// Tool that starts recording a transcript to the file at a path (replacing any transcript that's already being recorded)
+///
+pub struct StartTranscriptTool {
+    transcript: Transcript
+}
+
+///
+/// Tool that stops recording the current transcript
+///
+pub struct StopTranscriptTool {
+    transcript: Transcript
+}
+
+///
+/// Tool that adds an input line and its result to the current transcript (or does nothing if no transcript is being recorded)
+///
+pub struct RecordTranscriptTool {
+    transcript: Transcript
+}
+
+///
+/// Tool that reads the input lines from a transcript file, in the order they were entered
+///
+/// Each input is written on its own line, followed by a line starting with `// ` that records its
+/// result. The results are comments in the script language, so they're skipped when the inputs are
+/// read back.
+///
+pub struct ReadTranscriptTool {
+}
+
+impl Transcript {
+    ///
+    /// Creates a new transcript that isn't recording anything
+    ///
+    pub fn new() -> Transcript {
+        Transcript { file: Arc::new(Mutex::new(None)) }
+    }
+
+    ///
+    /// Starts writing the transcript to a file
+    ///
+    pub fn start(&self, path: &str) -> Result<(), Value> {
+        let file = fs::File::create(path).map_err(|erm| json![{
+            "error":        "Could not create transcript file",
+            "file":         path,
+            "description":  erm.description()
+        }])?;
+
+        *self.file.lock().unwrap() = Some(file);
+        Ok(())
+    }
+
+    ///
+    /// Stops writing the transcript
+    ///
+    pub fn stop(&self) {
+        *self.file.lock().unwrap() = None;
+    }
+
+    ///
+    /// Returns true if the transcript is being written to a file
+    ///
+    pub fn is_recording(&self) -> bool {
+        self.file.lock().unwrap().is_some()
+    }
+
+    ///
+    /// Writes an input line and its result to the transcript (blank lines aren't recorded)
+    ///
+    pub fn record(&self, input: &str, result: &Result<Value, Value>) -> Result<(), Value> {
+        if input.trim().is_empty() {
+            return Ok(());
+        }
+
+        let mut file = self.file.lock().unwrap();
+        if let Some(ref mut file) = *file {
+            let output = match result {
+                &Ok(Value::Null)    => None,
+                &Ok(ref value)      => Some(format!("{}=> {}", TRANSCRIPT_OUTPUT_PREFIX, value)),
+                &Err(ref error)     => Some(format!("{}*** Error: {}", TRANSCRIPT_OUTPUT_PREFIX, error))
+            };
+
+            // Write the whole entry at once so the file is never left with an input and no result
+            let mut entry = format!("{}\n", input.trim_right());
+            if let Some(output) = output {
+                entry.push_str(&output);
+                entry.push('\n');
+            }
+
+            file.write_all(entry.as_bytes()).map_err(|erm| json![{
+                "error":        "Could not write to transcript file",
+                "description":  erm.description()
+            }])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl StartTranscriptTool {
+    ///
+    /// Creates a new start-transcript tool
+    ///
+    pub fn new(transcript: Transcript) -> StartTranscriptTool {
+        StartTranscriptTool { transcript: transcript }
+    }
+}
+
+impl StopTranscriptTool {
+    ///
+    /// Creates a new stop-transcript tool
+    ///
+    pub fn new(transcript: Transcript) -> StopTranscriptTool {
+        StopTranscriptTool { transcript: transcript }
+    }
+}
+
+impl RecordTranscriptTool {
+    ///
+    /// Creates a new record-transcript tool
+    ///
+    pub fn new(transcript: Transcript) -> RecordTranscriptTool {
+        RecordTranscriptTool { transcript: transcript }
+    }
+}
+
+impl ReadTranscriptTool {
+    ///
+    /// Creates a new read-transcript tool
+    ///
+    pub fn new() -> ReadTranscriptTool {
+        ReadTranscriptTool { }
+    }
+
+    ///
+    /// Reads the input lines from a transcript, skipping the results and any blank lines
+    ///
+    pub fn read(path: &str) -> Result<Vec<String>, Value> {
+        let contents = fs::read_to_string(path).map_err(|erm| json![{
+            "error":        "Could not read transcript file",
+            "file":         path,
+            "description":  erm.description()
+        }])?;
+
+        Ok(contents.lines()
+            .filter(|line| !line.trim().is_empty() && !line.starts_with(TRANSCRIPT_OUTPUT_PREFIX))
+            .map(|line| String::from(line))
+            .collect())
+    }
+}
+
+impl Tool for StartTranscriptTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let path = from_value::<String>(input).map_err(|erm| json![{
+            "error":        "start-transcript must be called with the path of the file to write",
+            "description":  erm.description()
+        }])?;
+
+        self.transcript.start(&path)?;
+        Ok(Value::Null)
+    }
+}
+
+impl Tool for StopTranscriptTool {
+    fn invoke_json(&self, _input: Value, _environment: &Environment) -> Result<Value, Value> {
+        self.transcript.stop();
+        Ok(Value::Null)
+    }
+}
+
+impl Tool for RecordTranscriptTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<RecordTranscriptParameters>(input);
+
+        match parameters {
+            Ok(RecordTranscriptParameters { input, result, error }) => {
+                let result = match error {
+                    Some(error) => Err(error),
+                    None        => Ok(result.unwrap_or(Value::Null))
+                };
+
+                self.transcript.record(&input, &result)?;
+                Ok(Value::Null)
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to record-transcript must be of the form { \"input\": <line>, \"result\": <value>, \"error\": <error> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+impl Tool for ReadTranscriptTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let path = from_value::<String>(input).map_err(|erm| json![{
+            "error":        "read-transcript must be called with the path of the file to read",
+            "description":  erm.description()
+        }])?;
+
+        Ok(json![ ReadTranscriptTool::read(&path)? ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::temp_file::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn recorded_commands_can_be_replayed() {
+        let env         = EmptyEnvironment::new();
+        let file        = TempFile::new("replay.txt");
+        let path        = file.path();
+        let transcript  = Transcript::new();
+
+        let start       = StartTranscriptTool::new(transcript.clone());
+        let stop        = StopTranscriptTool::new(transcript.clone());
+        let record      = TypedTool::<RecordTranscriptParameters, ()>::from(Box::new(RecordTranscriptTool::new(transcript.clone())));
+
+        assert!(start.invoke_json(json![ path ], &env) == Ok(Value::Null));
+        assert!(record.invoke(RecordTranscriptParameters::new("var x = 1", Ok(json![ [ 1 ] ])), &env) == Ok(()));
+        assert!(record.invoke(RecordTranscriptParameters::new("print x", Err(json![{ "error": "Tool not found" }])), &env) == Ok(()));
+        assert!(stop.invoke_json(Value::Null, &env) == Ok(Value::Null));
+
+        // Nothing is recorded once the transcript has stopped
+        assert!(record.invoke(RecordTranscriptParameters::new("print y", Ok(Value::Null)), &env) == Ok(()));
+        assert!(!transcript.is_recording());
+
+        assert!(fs::read_to_string(&path).unwrap() == "var x = 1\n// => [1]\nprint x\n// *** Error: {\"error\":\"Tool not found\"}\n");
+        assert!(ReadTranscriptTool::new().invoke_json(json![ path ], &env) == Ok(json![[ "var x = 1", "print x" ]]));
+    }
+
+    #[test]
+    fn missing_transcript_is_error() {
+        let env = EmptyEnvironment::new();
+
+        assert!(ReadTranscriptTool::new().invoke_json(json![ TempFile::new("does-not-exist.txt").path() ], &env).is_err());
+    }
+}
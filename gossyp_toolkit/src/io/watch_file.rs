@@ -0,0 +1,304 @@
+//!
+//! The watch-file tools read a JSON file and wait for it to change
+//!
+
+use std::fs;
+use std::io;
+use std::thread;
+use std::result::Result;
+use std::error::Error;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+use serde_json::*;
+use gossyp_base::*;
+
+/// The interval between checks for changes to a file if none is specified
+pub const DEFAULT_WATCH_INTERVAL_MS: u64 = 100;
+
+///
+/// Parameters that can be passed to the watch-file tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct WatchFileParameters {
+    /// The path of the file to read
+    pub path: String
+}
+
+///
+/// Parameters that can be passed to the wait-for-file-change tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct WaitForFileChangeParameters {
+    /// The path of the file to watch
+    pub path: String,
+
+    /// The modification time that was last seen (as returned by watch-file)
+    pub modified: u64,
+
+    /// The number of milliseconds between checks for changes
+    pub interval_ms: Option<u64>,
+
+    /// The number of milliseconds to wait before giving up, or none to wait forever
+    pub timeout_ms: Option<u64>
+}
+
+///
+/// The contents of a watched file
+///
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct WatchedFile {
+    /// The path of the file
+    pub path: String,
+
+    /// The parsed contents of the file
+    pub contents: Value,
+
+    /// When the file was last modified, in milliseconds since the unix epoch
+    pub modified: u64
+}
+
+///
+/// Tool that reads a JSON file along with its modification time
+///
+pub struct WatchFileTool {
+}
+
+///
+/// Tool that waits for a JSON file to be modified, then returns its new contents
+///
+/// Changes are detected by checking the modification time of the file every `interval_ms`
+/// milliseconds, so changes that don't alter the modification time (or several changes within
+/// the same millisecond) aren't seen.
+///
+pub struct WaitForFileChangeTool {
+}
+
+impl WatchFileTool {
+    ///
+    /// Creates a new watch-file tool
+    ///
+    pub fn new() -> WatchFileTool {
+        WatchFileTool { }
+    }
+
+    ///
+    /// Generates the error for a file that couldn't be read
+    ///
+    fn read_error(path: &str, erm: io::Error) -> Value {
+        let error = if erm.kind() == io::ErrorKind::NotFound { "File not found" } else { "Could not read file" };
+
+        json![{
+            "error":        error,
+            "path":         path,
+            "description":  erm.description()
+        }]
+    }
+
+    ///
+    /// Retrieves the modification time of a file, in milliseconds since the unix epoch
+    ///
+    pub fn modified(path: &str) -> Result<u64, Value> {
+        let modified = fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .map_err(|erm| WatchFileTool::read_error(path, erm))?;
+        let since_epoch = modified.duration_since(UNIX_EPOCH).unwrap_or(Duration::from_millis(0));
+
+        Ok(since_epoch.as_secs()*1000 + (since_epoch.subsec_nanos() as u64)/1_000_000)
+    }
+
+    ///
+    /// Reads a file and parses it as JSON
+    ///
+    /// The result is `Ok` if the file could be read, containing the result of parsing it.
+    ///
+    fn read_contents(path: &str) -> Result<Result<Value, Value>, Value> {
+        let contents = fs::read_to_string(path).map_err(|erm| WatchFileTool::read_error(path, erm))?;
+
+        Ok(from_str::<Value>(&contents).map_err(|erm| json![{
+            "error":        "File is not valid JSON",
+            "path":         path,
+            "description":  erm.description()
+        }]))
+    }
+
+    ///
+    /// Reads and parses a file
+    ///
+    pub fn read(path: &str) -> Result<WatchedFile, Value> {
+        let modified    = WatchFileTool::modified(path)?;
+        let contents    = WatchFileTool::read_contents(path)??;
+
+        Ok(WatchedFile { path: String::from(path), contents: contents, modified: modified })
+    }
+}
+
+impl WaitForFileChangeTool {
+    ///
+    /// Creates a new wait-for-file-change tool
+    ///
+    pub fn new() -> WaitForFileChangeTool {
+        WaitForFileChangeTool { }
+    }
+
+    ///
+    /// Waits until the modification time of a file is different from the specified time, then reads it
+    ///
+    /// A file that has changed but isn't valid JSON might still be being written, so it's read
+    /// again after each interval until it is valid. If it's still not valid when the timeout
+    /// passes, the result is the error from parsing it.
+    ///
+    pub fn wait(path: &str, modified: u64, interval: Duration, timeout: Option<Duration>) -> Result<WatchedFile, Value> {
+        let started         = Instant::now();
+        let mut last_error  = None;
+
+        loop {
+            let current_modified = WatchFileTool::modified(path)?;
+
+            if current_modified != modified {
+                match WatchFileTool::read_contents(path)? {
+                    Ok(contents)    => return Ok(WatchedFile { path: String::from(path), contents: contents, modified: current_modified }),
+                    Err(erm)        => last_error = Some(erm)
+                }
+            }
+
+            if let Some(timeout) = timeout {
+                if started.elapsed() >= timeout {
+                    return Err(last_error.unwrap_or_else(|| json![{
+                        "error":    "Timed out waiting for file to change",
+                        "path":     path
+                    }]));
+                }
+            }
+
+            thread::sleep(interval);
+        }
+    }
+}
+
+impl Tool for WatchFileTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        match from_value::<WatchFileParameters>(input) {
+            Ok(WatchFileParameters { path }) => Ok(to_value(WatchFileTool::read(&path)?).unwrap()),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to watch-file must be of the form { \"path\": <path> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+impl Tool for WaitForFileChangeTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        match from_value::<WaitForFileChangeParameters>(input) {
+            Ok(WaitForFileChangeParameters { path, modified, interval_ms, timeout_ms }) => {
+                let interval    = Duration::from_millis(interval_ms.unwrap_or(DEFAULT_WATCH_INTERVAL_MS));
+                let timeout     = timeout_ms.map(|timeout_ms| Duration::from_millis(timeout_ms));
+
+                Ok(to_value(WaitForFileChangeTool::wait(&path, modified, interval, timeout)?).unwrap())
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to wait-for-file-change must be of the form { \"path\": <path>, \"modified\": <time>, \"interval_ms\": <number>, \"timeout_ms\": <number> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::temp_file::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn reads_contents_and_modification_time() {
+        let file    = TempFile::with_contents("read.json", "{ \"value\": 1 }");
+        let path    = file.path();
+        let result  = WatchFileTool::new().invoke_json(json![{ "path": path }], &EmptyEnvironment::new()).unwrap();
+
+        assert!(result["contents"] == json![{ "value": 1 }]);
+        assert!(result["modified"].as_u64() == Some(WatchFileTool::modified(&path).unwrap()));
+    }
+
+    #[test]
+    fn missing_file_is_error() {
+        let file    = TempFile::new("does-not-exist.json");
+        let path    = file.path();
+        let result  = WatchFileTool::new().invoke_json(json![{ "path": path }], &EmptyEnvironment::new());
+
+        assert!(result.unwrap_err()["error"] == json!["File not found"]);
+    }
+
+    #[test]
+    fn detects_change_after_file_is_rewritten() {
+        let file    = TempFile::with_contents("change.json", "{ \"value\": 1 }");
+        let path    = file.path();
+        let initial = WatchFileTool::read(path).unwrap();
+
+        let writer_path = String::from(path);
+        let last_seen   = initial.modified;
+        let writer      = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            fs::write(&writer_path, "{ \"value\": 2 }").unwrap();
+
+            // Filesystems with a coarse timestamp resolution might not change the modification time, so move it on explicitly
+            let modified = UNIX_EPOCH + Duration::from_millis(last_seen + 2000);
+            fs::OpenOptions::new().write(true).open(&writer_path).unwrap().set_modified(modified).unwrap();
+        });
+
+        let changed = WaitForFileChangeTool::new().invoke_json(json![{ "path": path, "modified": initial.modified, "interval_ms": 10, "timeout_ms": 5000 }], &EmptyEnvironment::new());
+        writer.join().unwrap();
+
+        assert!(changed.unwrap()["contents"] == json![{ "value": 2 }]);
+    }
+
+    #[test]
+    fn waits_for_partly_written_file_to_become_valid() {
+        let file    = TempFile::with_contents("partial.json", "{ \"value\": 1 }");
+        let path    = file.path();
+        let initial = WatchFileTool::read(path).unwrap();
+
+        // Write half of the new contents, as a writer that truncates the file first might be seen doing
+        fs::write(path, "{ \"val").unwrap();
+        fs::OpenOptions::new().write(true).open(path).unwrap().set_modified(UNIX_EPOCH + Duration::from_millis(initial.modified + 2000)).unwrap();
+
+        let writer_path = String::from(path);
+        let last_seen   = initial.modified;
+        let writer      = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            fs::write(&writer_path, "{ \"value\": 2 }").unwrap();
+            fs::OpenOptions::new().write(true).open(&writer_path).unwrap().set_modified(UNIX_EPOCH + Duration::from_millis(last_seen + 4000)).unwrap();
+        });
+
+        let changed = WaitForFileChangeTool::wait(path, initial.modified, Duration::from_millis(10), Some(Duration::from_millis(5000)));
+        writer.join().unwrap();
+
+        assert!(changed.unwrap().contents == json![{ "value": 2 }]);
+    }
+
+    #[test]
+    fn invalid_file_is_error_at_timeout() {
+        let file    = TempFile::with_contents("invalid.json", "{ \"value\": 1 }");
+        let path    = file.path();
+        let initial = WatchFileTool::read(path).unwrap();
+
+        fs::write(path, "{ not json").unwrap();
+        fs::OpenOptions::new().write(true).open(path).unwrap().set_modified(UNIX_EPOCH + Duration::from_millis(initial.modified + 2000)).unwrap();
+
+        let result  = WaitForFileChangeTool::wait(path, initial.modified, Duration::from_millis(10), Some(Duration::from_millis(50)));
+
+        assert!(result.unwrap_err()["error"] == json!["File is not valid JSON"]);
+    }
+
+    #[test]
+    fn times_out_if_file_does_not_change() {
+        let file    = TempFile::with_contents("unchanged.json", "{ \"value\": 1 }");
+        let path    = file.path();
+        let initial = WatchFileTool::read(path).unwrap();
+
+        let result  = WaitForFileChangeTool::wait(path, initial.modified, Duration::from_millis(10), Some(Duration::from_millis(50)));
+
+        assert!(result.unwrap_err()["error"] == json!["Timed out waiting for file to change"]);
+    }
+}
@@ -9,6 +9,8 @@ use std::sync::*;
 use serde_json::*;
 use gossyp_base::*;
 
+use super::capture_output::*;
+
 ///
 /// Tool that writes out bytes to a stream
 /// 
@@ -23,10 +25,15 @@ impl<Stream: Write+Send> WriteBytesTool<Stream> {
 }
 
 impl<Stream: Write+Send> Tool for WriteBytesTool<Stream> {
-    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+    fn invoke_json(&self, input: Value, environment: &Environment) -> Result<Value, Value> {
         let bytes = from_value::<Vec<u8>>(input);
 
         bytes.map(|bytes| {
+            // The environment can redirect the output
+            if let Some(result) = write_to_output_stream(&bytes, environment) {
+                return result.map(|_| Value::Null);
+            }
+
             let mut stream      = self.stream.lock().unwrap();
             let write_result    = stream.write(&bytes);
 
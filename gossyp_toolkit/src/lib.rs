@@ -3,4 +3,8 @@ extern crate gossyp_base;
 #[macro_use] extern crate serde_derive;
 
 pub mod algorithm;
+pub mod data;
+pub mod flow;
+pub mod introspect;
 pub mod io;
+pub mod text;
@@ -0,0 +1,101 @@
+//!
+//! The common-prefix tool finds the longest prefix shared by a set of strings
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Tool that returns the longest string that every string in an array starts with
+///
+/// This is the point that a list of completions can be filled in to without choosing between
+/// them. The prefix of an empty array is the empty string, and the prefix of a single string is
+/// the whole string.
+///
+pub struct CommonPrefixTool {
+}
+
+impl CommonPrefixTool {
+    ///
+    /// Creates a new common-prefix tool
+    ///
+    pub fn new() -> CommonPrefixTool {
+        CommonPrefixTool { }
+    }
+
+    ///
+    /// Finds the longest prefix shared by a set of strings
+    ///
+    pub fn common_prefix(strings: &Vec<String>) -> String {
+        let mut remaining   = strings.iter();
+        let mut prefix      = match remaining.next() {
+            Some(first) => first.as_str(),
+            None        => return String::new()
+        };
+
+        for string in remaining {
+            // Shorten the prefix to the part that matches this string (comparing characters so the prefix is always valid UTF-8)
+            let matching_len = prefix.char_indices()
+                .zip(string.chars())
+                .take_while(|&((_, prefix_char), string_char)| prefix_char == string_char)
+                .last()
+                .map(|((index, chr), _)| index + chr.len_utf8())
+                .unwrap_or(0);
+
+            prefix = &prefix[0..matching_len];
+        }
+
+        String::from(prefix)
+    }
+}
+
+impl Tool for CommonPrefixTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let strings = from_value::<Vec<String>>(input);
+
+        match strings {
+            Ok(strings) => Ok(Value::String(CommonPrefixTool::common_prefix(&strings))),
+
+            Err(erm) => Err(json![{
+                "error":        "common-prefix must be called with an array of strings",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn finds_shared_prefix() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<Vec<&str>, String>::from(Box::new(CommonPrefixTool::new()));
+
+        assert!(tool.invoke(vec![ "read-line", "read-transcript", "record-transcript" ], &env) == Ok(String::from("re")));
+        assert!(tool.invoke(vec![ "read-line", "read-transcript" ], &env) == Ok(String::from("read-")));
+        assert!(tool.invoke(vec![ "héllo", "hélp" ], &env) == Ok(String::from("hél")));
+    }
+
+    #[test]
+    fn no_shared_prefix_is_empty() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<Vec<&str>, String>::from(Box::new(CommonPrefixTool::new()));
+
+        assert!(tool.invoke(vec![ "print", "read-line" ], &env) == Ok(String::from("")));
+    }
+
+    #[test]
+    fn empty_and_single_element_arrays() {
+        let env     = EmptyEnvironment::new();
+        let tool    = CommonPrefixTool::new();
+
+        assert!(tool.invoke_json(json![ [] ], &env) == Ok(json![ "" ]));
+        assert!(tool.invoke_json(json![ [ "print" ] ], &env) == Ok(json![ "print" ]));
+        assert!(tool.invoke_json(json![ "print" ], &env).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+//!
+//! The expand-env tool replaces references to environment variables in a string with their values
+//!
+
+use std::env;
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Parameters that can be passed to the expand-env tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct ExpandEnvParameters {
+    /// The string containing the references to expand
+    pub string: String,
+
+    /// If true, referring to a variable that isn't set is an error (by default it expands to an empty string)
+    pub error_on_missing: Option<bool>
+}
+
+impl ExpandEnvParameters {
+    pub fn new(string: &str, error_on_missing: bool) -> ExpandEnvParameters {
+        ExpandEnvParameters { string: String::from(string), error_on_missing: Some(error_on_missing) }
+    }
+}
+
+///
+/// Tool that replaces `${VAR}` in a string with the value of the environment variable `VAR`
+///
+/// `$${` is an escape that produces a literal `${`, so `$${VAR}` becomes `${VAR}`. A `$` that
+/// isn't followed by `{` is left as it is. Variables that aren't set (or whose values aren't valid
+/// unicode) expand to an empty string unless `error_on_missing` is set, in which case they're an
+/// error. A `${` without a closing `}` is always an error.
+///
+pub struct ExpandEnvTool {
+}
+
+impl ExpandEnvTool {
+    ///
+    /// Creates a new expand-env tool
+    ///
+    pub fn new() -> ExpandEnvTool {
+        ExpandEnvTool { }
+    }
+
+    ///
+    /// Expands the environment variable references in a string
+    ///
+    pub fn expand(string: &str, error_on_missing: bool) -> Result<String, Value> {
+        let mut result      = String::new();
+        let mut remaining   = string;
+
+        while let Some(pos) = remaining.find('$') {
+            result.push_str(&remaining[0..pos]);
+            remaining = &remaining[pos..];
+
+            if remaining.starts_with("$${") {
+                // Escaped '${'
+                result.push_str("${");
+                remaining = &remaining[3..];
+
+            } else if remaining.starts_with("${") {
+                // Variable reference
+                let end = remaining.find('}').ok_or_else(|| json![{
+                    "error":    "Variable reference is missing a closing '}'",
+                    "position": string.len() - remaining.len()
+                }])?;
+
+                let name = &remaining[2..end];
+
+                match env::var(name) {
+                    Ok(value)                   => result.push_str(&value),
+                    Err(_) if error_on_missing  => return Err(json![{
+                        "error":    "Environment variable is not set",
+                        "variable": name
+                    }]),
+                    Err(_)                      => ()
+                }
+
+                remaining = &remaining[end+1..];
+
+            } else {
+                // Just a '$'
+                result.push('$');
+                remaining = &remaining[1..];
+            }
+        }
+
+        result.push_str(remaining);
+        Ok(result)
+    }
+}
+
+impl Tool for ExpandEnvTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<ExpandEnvParameters>(input);
+
+        match parameters {
+            Ok(parameters) => {
+                Ok(Value::String(ExpandEnvTool::expand(&parameters.string, parameters.error_on_missing.unwrap_or(false))?))
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to expand-env must be of the form { \"string\": <string>, \"error_on_missing\": <bool> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn expands_set_variable() {
+        env::set_var("GOSSYP_EXPAND_ENV_TEST_SET", "world");
+
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<ExpandEnvParameters, String>::from(Box::new(ExpandEnvTool::new()));
+
+        assert!(tool.invoke(ExpandEnvParameters::new("Hello, ${GOSSYP_EXPAND_ENV_TEST_SET}! $5", false), &env) == Ok(String::from("Hello, world! $5")));
+    }
+
+    #[test]
+    fn unset_variable_is_empty_or_error() {
+        env::remove_var("GOSSYP_EXPAND_ENV_TEST_UNSET");
+
+        assert!(ExpandEnvTool::expand("[${GOSSYP_EXPAND_ENV_TEST_UNSET}]", false) == Ok(String::from("[]")));
+        assert!(ExpandEnvTool::expand("[${GOSSYP_EXPAND_ENV_TEST_UNSET}]", true) == Err(json![{
+            "error":    "Environment variable is not set",
+            "variable": "GOSSYP_EXPAND_ENV_TEST_UNSET"
+        }]));
+    }
+
+    #[test]
+    fn double_dollar_is_literal() {
+        env::set_var("GOSSYP_EXPAND_ENV_TEST_ESCAPE", "value");
+
+        assert!(ExpandEnvTool::expand("$${GOSSYP_EXPAND_ENV_TEST_ESCAPE} is ${GOSSYP_EXPAND_ENV_TEST_ESCAPE}", true) == Ok(String::from("${GOSSYP_EXPAND_ENV_TEST_ESCAPE} is value")));
+    }
+
+    #[test]
+    fn unterminated_reference_is_error() {
+        assert!(ExpandEnvTool::expand("abc ${FOO", false).unwrap_err()["position"] == json![ 4 ]);
+    }
+}
@@ -0,0 +1,53 @@
+pub mod split_once;
+pub mod normalize;
+pub mod tree;
+pub mod parse_args;
+pub mod expand_env;
+pub mod to_fixed;
+pub mod normalize_text;
+pub mod common_prefix;
+pub mod tool;
+
+pub use self::split_once::*;
+pub use self::normalize::*;
+pub use self::tree::*;
+pub use self::parse_args::*;
+pub use self::expand_env::*;
+pub use self::to_fixed::*;
+pub use self::normalize_text::*;
+pub use self::common_prefix::*;
+
+use gossyp_base::*;
+use gossyp_base::basic::*;
+
+///
+/// ToolSet containing the text processing tools
+///
+pub struct TextTools { }
+
+impl TextTools {
+    pub fn new() -> TextTools {
+        TextTools { }
+    }
+}
+
+impl<'a> ToolSet for &'a TextTools {
+    fn create_tools(self, _environment: &Environment) -> Vec<(String, Box<Tool>)> {
+        vec![
+            (String::from(self::tool::SPLIT_ONCE),      Box::new(SplitOnceTool::new())),
+            (String::from(self::tool::NORMALIZE),       Box::new(NormalizeTool::new())),
+            (String::from(self::tool::TREE),            Box::new(TreeTool::new())),
+            (String::from(self::tool::PARSE_ARGS),      Box::new(ParseArgsTool::new())),
+            (String::from(self::tool::EXPAND_ENV),      Box::new(ExpandEnvTool::new())),
+            (String::from(self::tool::TO_FIXED),        Box::new(ToFixedTool::new())),
+            (String::from(self::tool::NORMALIZE_TEXT),  Box::new(NormalizeTextTool::new())),
+            (String::from(self::tool::COMMON_PREFIX),   Box::new(CommonPrefixTool::new()))
+        ]
+    }
+}
+
+impl ToolSet for TextTools {
+    fn create_tools(self, environment: &Environment) -> Vec<(String, Box<Tool>)> {
+        (&self).create_tools(environment)
+    }
+}
@@ -0,0 +1,150 @@
+//!
+//! The normalize tool tidies up the whitespace and casing in a string
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Parameters that can be passed to the normalize tool
+///
+/// Every transformation is off unless it's requested.
+///
+#[derive(Serialize, Deserialize)]
+pub struct NormalizeParameters {
+    /// The string to normalize
+    pub string: String,
+
+    /// Converts the string to lower case
+    pub lowercase: Option<bool>,
+
+    /// Replaces every run of whitespace with a single space
+    pub collapse_whitespace: Option<bool>,
+
+    /// Removes whitespace from the start and end of the string
+    pub trim: Option<bool>
+}
+
+impl NormalizeParameters {
+    pub fn new(string: &str, lowercase: bool, collapse_whitespace: bool, trim: bool) -> NormalizeParameters {
+        NormalizeParameters { 
+            string:                 String::from(string), 
+            lowercase:              Some(lowercase), 
+            collapse_whitespace:    Some(collapse_whitespace), 
+            trim:                   Some(trim) 
+        }
+    }
+}
+
+///
+/// Tool that normalizes the whitespace and casing of a string
+///
+/// The transformations are applied in this order: whitespace is collapsed, then the string is
+/// trimmed, then it's converted to lower case.
+///
+pub struct NormalizeTool {
+}
+
+impl NormalizeTool {
+    ///
+    /// Creates a new normalize tool
+    ///
+    pub fn new() -> NormalizeTool {
+        NormalizeTool { }
+    }
+
+    ///
+    /// Replaces every run of whitespace in a string with a single space
+    ///
+    pub fn collapse_whitespace(string: &str) -> String {
+        let mut result          = String::new();
+        let mut in_whitespace   = false;
+
+        for chr in string.chars() {
+            if chr.is_whitespace() {
+                if !in_whitespace {
+                    result.push(' ');
+                }
+                in_whitespace = true;
+            } else {
+                result.push(chr);
+                in_whitespace = false;
+            }
+        }
+
+        result
+    }
+
+    ///
+    /// Applies the transformations requested by a set of parameters
+    ///
+    pub fn normalize(parameters: &NormalizeParameters) -> String {
+        let mut result = parameters.string.clone();
+
+        if parameters.collapse_whitespace.unwrap_or(false) {
+            result = NormalizeTool::collapse_whitespace(&result);
+        }
+
+        if parameters.trim.unwrap_or(false) {
+            result = String::from(result.trim());
+        }
+
+        if parameters.lowercase.unwrap_or(false) {
+            result = result.to_lowercase();
+        }
+
+        result
+    }
+}
+
+impl Tool for NormalizeTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<NormalizeParameters>(input);
+
+        match parameters {
+            Ok(parameters) => Ok(Value::String(NormalizeTool::normalize(&parameters))),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to normalize must be of the form { \"string\": <string>, \"lowercase\": <bool>, \"collapse_whitespace\": <bool>, \"trim\": <bool> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    const SAMPLE: &'static str = "  Hello \t  WORLD\n\nAgain  ";
+
+    #[test]
+    fn nothing_changes_by_default() {
+        let env     = EmptyEnvironment::new();
+        let tool    = NormalizeTool::new();
+
+        assert!(tool.invoke_json(json![{ "string": SAMPLE }], &env) == Ok(json![SAMPLE]));
+    }
+
+    #[test]
+    fn can_apply_each_transformation() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<NormalizeParameters, String>::from(Box::new(NormalizeTool::new()));
+
+        assert!(tool.invoke(NormalizeParameters::new(SAMPLE, true, false, false), &env) == Ok(String::from("  hello \t  world\n\nagain  ")));
+        assert!(tool.invoke(NormalizeParameters::new(SAMPLE, false, true, false), &env) == Ok(String::from(" Hello WORLD Again ")));
+        assert!(tool.invoke(NormalizeParameters::new(SAMPLE, false, false, true), &env) == Ok(String::from("Hello \t  WORLD\n\nAgain")));
+    }
+
+    #[test]
+    fn can_combine_transformations() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<NormalizeParameters, String>::from(Box::new(NormalizeTool::new()));
+
+        assert!(tool.invoke(NormalizeParameters::new(SAMPLE, false, true, true), &env) == Ok(String::from("Hello WORLD Again")));
+        assert!(tool.invoke(NormalizeParameters::new(SAMPLE, true, true, true), &env) == Ok(String::from("hello world again")));
+    }
+}
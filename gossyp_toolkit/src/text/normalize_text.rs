@@ -0,0 +1,141 @@
+//!
+//! The normalize-text tool removes the platform-specific details from text that has been read from a file
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+/// The byte order mark that some editors put at the start of UTF-8 files
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+///
+/// Parameters that can be passed to the normalize-text tool
+///
+/// Unlike the normalize tool, every conversion is on unless it's turned off.
+///
+#[derive(Serialize, Deserialize)]
+pub struct NormalizeTextParameters {
+    /// The text to normalize
+    pub text: String,
+
+    /// Removes a byte order mark from the start of the text
+    pub strip_bom: Option<bool>,
+
+    /// Converts '\r\n' and '\r' line endings to '\n'
+    pub normalize_line_endings: Option<bool>
+}
+
+impl NormalizeTextParameters {
+    pub fn new(text: &str, strip_bom: bool, normalize_line_endings: bool) -> NormalizeTextParameters {
+        NormalizeTextParameters {
+            text:                   String::from(text),
+            strip_bom:              Some(strip_bom),
+            normalize_line_endings: Some(normalize_line_endings)
+        }
+    }
+}
+
+///
+/// Tool that strips the byte order mark and converts the line endings of some text
+///
+/// Text read from files written on different platforms can start with a byte order mark or use
+/// Windows ('\r\n') or classic Mac ('\r') line endings, none of which the script lexer treats as
+/// a newline. After normalizing, the text has no byte order mark and every line ends with '\n'.
+///
+pub struct NormalizeTextTool {
+}
+
+impl NormalizeTextTool {
+    ///
+    /// Creates a new normalize-text tool
+    ///
+    pub fn new() -> NormalizeTextTool {
+        NormalizeTextTool { }
+    }
+
+    ///
+    /// Removes the byte order mark from the start of some text, if there is one
+    ///
+    pub fn strip_bom(text: &str) -> &str {
+        if text.starts_with(BYTE_ORDER_MARK) {
+            &text[BYTE_ORDER_MARK.len_utf8()..]
+        } else {
+            text
+        }
+    }
+
+    ///
+    /// Converts the line endings in some text to '\n'
+    ///
+    pub fn normalize_line_endings(text: &str) -> String {
+        text.replace("\r\n", "\n").replace('\r', "\n")
+    }
+
+    ///
+    /// Applies the conversions requested by a set of parameters
+    ///
+    pub fn normalize(parameters: &NormalizeTextParameters) -> String {
+        let mut result = parameters.text.as_str();
+
+        if parameters.strip_bom.unwrap_or(true) {
+            result = NormalizeTextTool::strip_bom(result);
+        }
+
+        if parameters.normalize_line_endings.unwrap_or(true) {
+            NormalizeTextTool::normalize_line_endings(result)
+        } else {
+            String::from(result)
+        }
+    }
+}
+
+impl Tool for NormalizeTextTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<NormalizeTextParameters>(input);
+
+        match parameters {
+            Ok(parameters) => Ok(Value::String(NormalizeTextTool::normalize(&parameters))),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to normalize-text must be of the form { \"text\": <string>, \"strip_bom\": <bool>, \"normalize_line_endings\": <bool> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    const SAMPLE: &'static str = "\u{feff}let x = 1\r\nprint x\rprint \"\u{feff}\"\n";
+
+    #[test]
+    fn everything_is_normalized_by_default() {
+        let env     = EmptyEnvironment::new();
+        let tool    = NormalizeTextTool::new();
+
+        assert!(tool.invoke_json(json![{ "text": SAMPLE }], &env) == Ok(json!["let x = 1\nprint x\nprint \"\u{feff}\"\n"]));
+    }
+
+    #[test]
+    fn can_strip_bom() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<NormalizeTextParameters, String>::from(Box::new(NormalizeTextTool::new()));
+
+        assert!(tool.invoke(NormalizeTextParameters::new(SAMPLE, true, false), &env) == Ok(String::from("let x = 1\r\nprint x\rprint \"\u{feff}\"\n")));
+        assert!(tool.invoke(NormalizeTextParameters::new("no bom", true, false), &env) == Ok(String::from("no bom")));
+    }
+
+    #[test]
+    fn can_convert_crlf_line_endings() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<NormalizeTextParameters, String>::from(Box::new(NormalizeTextTool::new()));
+
+        assert!(tool.invoke(NormalizeTextParameters::new(SAMPLE, false, true), &env) == Ok(String::from("\u{feff}let x = 1\nprint x\nprint \"\u{feff}\"\n")));
+        assert!(tool.invoke(NormalizeTextParameters::new("a\r\n\r\nb", false, true), &env) == Ok(String::from("a\n\nb")));
+    }
+}
@@ -0,0 +1,178 @@
+//!
+//! The parse-args tool converts a command-line style string into an object
+//!
+
+use std::result::Result;
+use serde_json::*;
+use gossyp_base::*;
+
+/// The field that arguments that don't belong to a flag are stored in
+pub const POSITIONAL_ARGUMENTS: &'static str = "_";
+
+///
+/// Tool that parses a string of command-line arguments into an object
+///
+/// The string is split into words at whitespace. A word can include whitespace by surrounding
+/// it with single or double quotes, and a '\' can be used to include a quote or another '\'.
+/// Words are then interpreted like this:
+///
+/// * `--name value` sets the field `name` to the string `"value"`
+/// * `--name=value` does the same, even if the value starts with `--`
+/// * `--name` followed by another flag or the end of the string sets `name` to `true`
+/// * a flag that appears more than once produces an array of its values
+/// * words that aren't the value of a flag are added to an array in the `_` field
+///
+/// For example, `--name foo --count 3 --verbose` becomes
+/// `{ "name": "foo", "count": "3", "verbose": true }`.
+///
+pub struct ParseArgsTool {
+}
+
+impl ParseArgsTool {
+    ///
+    /// Creates a new parse-args tool
+    ///
+    pub fn new() -> ParseArgsTool {
+        ParseArgsTool { }
+    }
+
+    ///
+    /// Splits an argument string into words
+    ///
+    pub fn split_words(args: &str) -> Result<Vec<String>, Value> {
+        let mut words       = vec![];
+        let mut word        = None;
+        let mut quote       = None;
+        let mut chars       = args.chars().enumerate();
+
+        while let Some((pos, chr)) = chars.next() {
+            match (chr, quote) {
+                ('\\', _) => {
+                    match chars.next() {
+                        Some((_, escaped))  => word.get_or_insert_with(|| String::new()).push(escaped),
+                        None                => return Err(json![{ "error": "Argument string ends with an escape character", "position": pos }])
+                    }
+                },
+
+                ('"', None) | ('\'', None)  => { quote = Some(chr); word.get_or_insert_with(|| String::new()); },
+                (chr, Some(quote_chr)) if chr == quote_chr => { quote = None; },
+
+                (chr, None) if chr.is_whitespace() => {
+                    if let Some(finished) = word.take() {
+                        words.push(finished);
+                    }
+                },
+
+                (chr, _) => word.get_or_insert_with(|| String::new()).push(chr)
+            }
+        }
+
+        if quote.is_some() {
+            return Err(json![{ "error": "Argument string has an unterminated quote" }]);
+        }
+
+        if let Some(finished) = word {
+            words.push(finished);
+        }
+
+        Ok(words)
+    }
+
+    ///
+    /// Adds a value to a field, converting the field to an array if it's already set
+    ///
+    fn add_value(result: &mut Map<String, Value>, name: &str, value: Value) {
+        let new_value = match result.remove(name) {
+            None                        => value,
+            Some(Value::Array(mut all)) => { all.push(value); Value::Array(all) },
+            Some(existing)              => Value::Array(vec![ existing, value ])
+        };
+
+        result.insert(String::from(name), new_value);
+    }
+
+    ///
+    /// Parses an argument string into an object
+    ///
+    pub fn parse(args: &str) -> Result<Map<String, Value>, Value> {
+        let words           = ParseArgsTool::split_words(args)?;
+        let mut result      = Map::new();
+        let mut positional  = vec![];
+        let mut index       = 0;
+
+        while index < words.len() {
+            let word = &words[index];
+            index += 1;
+
+            if word.starts_with("--") && word.len() > 2 {
+                let flag = &word[2..];
+
+                if let Some(equals) = flag.find('=') {
+                    // --name=value
+                    ParseArgsTool::add_value(&mut result, &flag[0..equals], Value::String(String::from(&flag[equals+1..])));
+                } else if index < words.len() && !words[index].starts_with("--") {
+                    // --name value
+                    ParseArgsTool::add_value(&mut result, flag, Value::String(words[index].clone()));
+                    index += 1;
+                } else {
+                    // --name
+                    ParseArgsTool::add_value(&mut result, flag, Value::Bool(true));
+                }
+            } else {
+                positional.push(Value::String(word.clone()));
+            }
+        }
+
+        if positional.len() > 0 {
+            result.insert(String::from(POSITIONAL_ARGUMENTS), Value::Array(positional));
+        }
+
+        Ok(result)
+    }
+}
+
+impl Tool for ParseArgsTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        match input {
+            Value::String(ref args) => ParseArgsTool::parse(args).map(|result| Value::Object(result)),
+            _                       => Err(json![{
+                "error": "Input to parse-args must be a string"
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn can_parse_flags() {
+        let env     = EmptyEnvironment::new();
+        let tool    = ParseArgsTool::new();
+
+        assert!(tool.invoke_json(json![ "--name foo --count 3 --verbose" ], &env) == Ok(json![{ "name": "foo", "count": "3", "verbose": true }]));
+    }
+
+    #[test]
+    fn repeated_flags_are_arrays() {
+        let env     = EmptyEnvironment::new();
+        let tool    = ParseArgsTool::new();
+
+        assert!(tool.invoke_json(json![ "--tag a --tag=b --tag c" ], &env) == Ok(json![{ "tag": [ "a", "b", "c" ] }]));
+    }
+
+    #[test]
+    fn can_quote_values() {
+        let env     = EmptyEnvironment::new();
+        let tool    = ParseArgsTool::new();
+
+        assert!(tool.invoke_json(json![ "file --title \"two words\" --empty '' --quote \\\"" ], &env) == Ok(json![{ "title": "two words", "empty": "", "quote": "\"", "_": [ "file" ] }]));
+    }
+
+    #[test]
+    fn unterminated_quote_is_error() {
+        assert!(ParseArgsTool::parse("--name \"foo").is_err());
+    }
+}
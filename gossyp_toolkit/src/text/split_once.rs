@@ -0,0 +1,130 @@
+//!
+//! The split-once tool divides a string into the part before and after the first occurrence of a separator
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+///
+/// Parameters that can be passed to the split-once tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct SplitOnceParameters {
+    /// The string to split
+    pub string: String,
+
+    /// The separator to split at
+    pub separator: String
+}
+
+impl SplitOnceParameters {
+    pub fn new(string: &str, separator: &str) -> SplitOnceParameters {
+        SplitOnceParameters { string: String::from(string), separator: String::from(separator) }
+    }
+}
+
+///
+/// Result of the split-once tool
+///
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct SplitOnceResult {
+    /// The part of the string before the separator (or the whole string if the separator is not present)
+    pub head: String,
+
+    /// The part of the string after the separator, or None if the separator is not present
+    pub tail: Option<String>
+}
+
+///
+/// Tool that splits a string at the first occurrence of a separator
+///
+pub struct SplitOnceTool {
+}
+
+impl SplitOnceTool {
+    ///
+    /// Creates a new split-once tool
+    ///
+    pub fn new() -> SplitOnceTool {
+        SplitOnceTool { }
+    }
+
+    ///
+    /// Splits a string at the first occurrence of a separator
+    ///
+    pub fn split_once(string: &str, separator: &str) -> SplitOnceResult {
+        match string.find(separator) {
+            Some(pos)   => SplitOnceResult {
+                head: String::from(&string[0..pos]),
+                tail: Some(String::from(&string[pos+separator.len()..]))
+            },
+
+            None        => SplitOnceResult {
+                head: String::from(string),
+                tail: None
+            }
+        }
+    }
+}
+
+impl Tool for SplitOnceTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<SplitOnceParameters>(input);
+
+        match parameters {
+            Ok(ref parameters) if parameters.separator.len() == 0 => {
+                // An empty separator would match everywhere
+                Err(json![{
+                    "error": "The separator passed to split-once must not be empty"
+                }])
+            },
+
+            Ok(parameters) => {
+                Ok(to_value(SplitOnceTool::split_once(&parameters.string, &parameters.separator)).unwrap())
+            },
+
+            Err(erm) => {
+                Err(json![{
+                    "error":        "Parameters to split-once must be of the form { \"string\": <string>, \"separator\": <string> }",
+                    "description":  erm.description()
+                }])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn can_split_at_separator() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<SplitOnceParameters, SplitOnceResult>::from(Box::new(SplitOnceTool::new()));
+
+        let result  = tool.invoke(SplitOnceParameters::new("key=value=more", "="), &env);
+
+        assert!(result == Ok(SplitOnceResult { head: String::from("key"), tail: Some(String::from("value=more")) }));
+    }
+
+    #[test]
+    fn tail_is_null_if_separator_is_absent() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<SplitOnceParameters, Value>::from(Box::new(SplitOnceTool::new()));
+
+        let result  = tool.invoke(SplitOnceParameters::new("key", "="), &env);
+
+        assert!(result == Ok(json![{ "head": "key", "tail": null }]));
+    }
+
+    #[test]
+    fn empty_separator_is_error() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<SplitOnceParameters, Value>::from(Box::new(SplitOnceTool::new()));
+
+        assert!(tool.invoke(SplitOnceParameters::new("key", ""), &env).is_err());
+    }
+}
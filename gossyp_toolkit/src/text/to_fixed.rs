@@ -0,0 +1,195 @@
+//!
+//! The to-fixed tool formats a number as a string with a fixed number of decimal places
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+/// The largest number of digits that to-fixed will put after the decimal point
+pub const MAX_FIXED_DIGITS: i64 = 100;
+
+///
+/// Parameters that can be passed to the to-fixed tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct ToFixedParameters {
+    /// The number to format
+    pub value: Value,
+
+    /// The number of digits to put after the decimal point
+    pub digits: i64
+}
+
+impl ToFixedParameters {
+    pub fn new(value: Value, digits: i64) -> ToFixedParameters {
+        ToFixedParameters { value: value, digits: digits }
+    }
+}
+
+///
+/// Tool that formats a number with exactly `digits` decimal places
+///
+/// Rounding works on the shortest decimal representation of the number, so `1.005` rounds to
+/// `1.01` even though the nearest floating point value is slightly less than that. Halves are
+/// rounded away from zero, so `-0.5` with no digits becomes `-1`. `digits` must be between 0 and
+/// `MAX_FIXED_DIGITS`.
+///
+pub struct ToFixedTool {
+}
+
+impl ToFixedTool {
+    ///
+    /// Creates a new to-fixed tool
+    ///
+    pub fn new() -> ToFixedTool {
+        ToFixedTool { }
+    }
+
+    ///
+    /// Formats a number with a fixed number of decimal places
+    ///
+    pub fn to_fixed(value: &Value, digits: i64) -> Result<String, Value> {
+        if digits < 0 || digits > MAX_FIXED_DIGITS {
+            return Err(json![{
+                "error":    "The number of digits passed to to-fixed must be between 0 and 100",
+                "digits":   digits
+            }]);
+        }
+
+        let number = match value {
+            &Value::Number(ref number)  => number,
+            _                           => return Err(json![{
+                "error":    "Value passed to to-fixed must be a number",
+                "value":    value
+            }])
+        };
+
+        // Display for f64 never uses an exponent, so this is always a plain decimal string
+        let decimal = if number.is_f64() { format!("{}", number.as_f64().unwrap()) } else { format!("{}", number) };
+
+        Ok(ToFixedTool::round_decimal(&decimal, digits as usize))
+    }
+
+    ///
+    /// Rounds a string of the form `-123.456` to a number of decimal places
+    ///
+    fn round_decimal(decimal: &str, digits: usize) -> String {
+        let negative            = decimal.starts_with('-');
+        let unsigned            = if negative { &decimal[1..] } else { decimal };
+        let (integer, fraction) = match unsigned.find('.') {
+            Some(pos)   => (&unsigned[0..pos], &unsigned[pos+1..]),
+            None        => (unsigned, "")
+        };
+
+        // Work on the digits with the decimal point removed, padding the fraction with zeros
+        let mut kept    = integer.bytes().chain(fraction.bytes().take(digits)).collect::<Vec<_>>();
+        kept.extend((fraction.len()..digits).map(|_| b'0'));
+
+        if fraction.len() > digits && fraction.as_bytes()[digits] >= b'5' {
+            // Round up, carrying into the earlier digits
+            let mut carry = true;
+            for digit in kept.iter_mut().rev() {
+                if *digit == b'9' {
+                    *digit = b'0';
+                } else {
+                    *digit += 1;
+                    carry = false;
+                    break;
+                }
+            }
+
+            if carry {
+                kept.insert(0, b'1');
+            }
+        }
+
+        let integer_len = kept.len() - digits;
+        let mut result  = String::new();
+
+        // Don't produce '-0.00' when a small negative number rounds to zero
+        if negative && kept.iter().any(|digit| *digit != b'0') {
+            result.push('-');
+        }
+
+        result.push_str(&String::from_utf8_lossy(&kept[0..integer_len]));
+        if digits > 0 {
+            result.push('.');
+            result.push_str(&String::from_utf8_lossy(&kept[integer_len..]));
+        }
+
+        result
+    }
+}
+
+impl Tool for ToFixedTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<ToFixedParameters>(input);
+
+        match parameters {
+            Ok(ToFixedParameters { value, digits }) => Ok(Value::String(ToFixedTool::to_fixed(&value, digits)?)),
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to to-fixed must be of the form { \"value\": <number>, \"digits\": <number> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn rounds_half_up() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<ToFixedParameters, String>::from(Box::new(ToFixedTool::new()));
+
+        assert!(tool.invoke(ToFixedParameters::new(json![ 1.005 ], 2), &env) == Ok(String::from("1.01")));
+        assert!(tool.invoke(ToFixedParameters::new(json![ 9.996 ], 2), &env) == Ok(String::from("10.00")));
+        assert!(tool.invoke(ToFixedParameters::new(json![ -2.5 ], 0), &env) == Ok(String::from("-3")));
+        assert!(tool.invoke(ToFixedParameters::new(json![ 1.234 ], 2), &env) == Ok(String::from("1.23")));
+    }
+
+    #[test]
+    fn pads_with_zeros() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<ToFixedParameters, String>::from(Box::new(ToFixedTool::new()));
+
+        assert!(tool.invoke(ToFixedParameters::new(json![ 1.5 ], 3), &env) == Ok(String::from("1.500")));
+        assert!(tool.invoke(ToFixedParameters::new(json![ 42 ], 2), &env) == Ok(String::from("42.00")));
+        assert!(tool.invoke(ToFixedParameters::new(json![ -0.001 ], 2), &env) == Ok(String::from("0.00")));
+    }
+
+    #[test]
+    fn non_numeric_value_is_error() {
+        let env     = EmptyEnvironment::new();
+        let tool    = ToFixedTool::new();
+
+        assert!(tool.invoke_json(json![{ "value": "1.5", "digits": 2 }], &env) == Err(json![{
+            "error":    "Value passed to to-fixed must be a number",
+            "value":    "1.5"
+        }]));
+    }
+
+    #[test]
+    fn negative_digits_is_error() {
+        let env     = EmptyEnvironment::new();
+        let tool    = ToFixedTool::new();
+
+        assert!(tool.invoke_json(json![{ "value": 1.5, "digits": -1 }], &env).is_err());
+    }
+
+    #[test]
+    fn too_many_digits_is_error() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<ToFixedParameters, String>::from(Box::new(ToFixedTool::new()));
+
+        assert!(tool.invoke(ToFixedParameters::new(json![ 1.5 ], MAX_FIXED_DIGITS), &env).map(|fixed| fixed.len()) == Ok(102));
+        assert!(tool.invoke(ToFixedParameters::new(json![ 1.5 ], MAX_FIXED_DIGITS+1), &env).is_err());
+        assert!(tool.invoke(ToFixedParameters::new(json![ 1.5 ], i64::max_value()), &env).is_err());
+    }
+}
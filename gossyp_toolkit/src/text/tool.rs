@@ -0,0 +1,8 @@
+pub const SPLIT_ONCE: &'static str      = "split-once";
+pub const NORMALIZE: &'static str       = "normalize";
+pub const TREE: &'static str            = "tree";
+pub const PARSE_ARGS: &'static str      = "parse-args";
+pub const EXPAND_ENV: &'static str      = "expand-env";
+pub const TO_FIXED: &'static str        = "to-fixed";
+pub const NORMALIZE_TEXT: &'static str  = "normalize-text";
+pub const COMMON_PREFIX: &'static str   = "common-prefix";
@@ -0,0 +1,173 @@
+//!
+//! The tree tool renders a value as an indented tree, which is easier to read than JSON when it's deeply nested
+//!
+
+use std::result::Result;
+use std::error::Error;
+use serde_json::*;
+use gossyp_base::*;
+
+/// The number of spaces each level of the tree is indented by if no indent is specified
+pub const DEFAULT_TREE_INDENT: usize = 2;
+
+///
+/// Parameters that can be passed to the tree tool
+///
+#[derive(Serialize, Deserialize)]
+pub struct TreeParameters {
+    /// The value to render
+    pub value: Value,
+
+    /// The number of spaces to indent each level of the tree by
+    pub indent: Option<usize>
+}
+
+impl TreeParameters {
+    pub fn new(value: Value, indent: Option<usize>) -> TreeParameters {
+        TreeParameters { value: value, indent: indent }
+    }
+}
+
+///
+/// Tool that renders a value as an indented tree
+///
+/// Each field of an object is shown on its own line as `key:` and each item of an array as
+/// `[index]:`. Values that aren't objects or arrays (and empty objects and arrays) are shown as
+/// JSON after the label. For example, `{ "a": [ 1, { "b": true } ] }` is rendered as:
+///
+/// ```text
+/// a:
+///   [0]: 1
+///   [1]:
+///     b: true
+/// ```
+///
+pub struct TreeTool {
+}
+
+impl TreeTool {
+    ///
+    /// Creates a new tree tool
+    ///
+    pub fn new() -> TreeTool {
+        TreeTool { }
+    }
+
+    ///
+    /// Returns true if a value is shown on the same line as its label
+    ///
+    fn is_leaf(value: &Value) -> bool {
+        match value {
+            &Value::Object(ref fields)  => fields.len() == 0,
+            &Value::Array(ref items)    => items.len() == 0,
+            _                           => true
+        }
+    }
+
+    ///
+    /// Renders a value as a tree
+    ///
+    pub fn render(value: &Value, indent: usize) -> String {
+        let mut lines = vec![];
+
+        // Values still to render, along with their label and depth. This is a stack rather than
+        // a recursive call so that very deeply nested values can't overflow the call stack
+        let mut pending: Vec<(Option<String>, &Value, usize)> = vec![ (None, value, 0) ];
+
+        while let Some((label, value, depth)) = pending.pop() {
+            let prefix = " ".repeat(indent*depth);
+
+            if TreeTool::is_leaf(value) {
+                // Leaves are shown as JSON
+                let json = to_string(value).unwrap_or_else(|_| String::from("<Error>"));
+
+                match label {
+                    Some(label) => lines.push(format!("{}{}: {}", prefix, label, json)),
+                    None        => lines.push(json)
+                }
+            } else {
+                // The children of the root value are not indented
+                let child_depth = match label {
+                    Some(label) => { lines.push(format!("{}{}:", prefix, label)); depth+1 },
+                    None        => depth
+                };
+
+                // Stack the children in reverse so they're rendered in order
+                match value {
+                    &Value::Object(ref fields) => {
+                        for (key, field) in fields.iter().rev() {
+                            pending.push((Some(key.clone()), field, child_depth));
+                        }
+                    },
+
+                    &Value::Array(ref items) => {
+                        for (index, item) in items.iter().enumerate().rev() {
+                            pending.push((Some(format!("[{}]", index)), item, child_depth));
+                        }
+                    },
+
+                    _ => ()
+                }
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl Tool for TreeTool {
+    fn invoke_json(&self, input: Value, _environment: &Environment) -> Result<Value, Value> {
+        let parameters = from_value::<TreeParameters>(input);
+
+        match parameters {
+            Ok(TreeParameters { value, indent }) => {
+                Ok(Value::String(TreeTool::render(&value, indent.unwrap_or(DEFAULT_TREE_INDENT))))
+            },
+
+            Err(erm) => Err(json![{
+                "error":        "Parameters to tree must be of the form { \"value\": <value>, \"indent\": <number> }",
+                "description":  erm.description()
+            }])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gossyp_base::basic::*;
+
+    #[test]
+    fn can_render_nested_object() {
+        let env     = EmptyEnvironment::new();
+        let tool    = TypedTool::<TreeParameters, String>::from(Box::new(TreeTool::new()));
+
+        let result  = tool.invoke(TreeParameters::new(json![{ "a": [ 1, { "b": true } ], "c": "x", "d": {} }], None), &env);
+
+        assert!(result == Ok(String::from("a:\n  [0]: 1\n  [1]:\n    b: true\nc: \"x\"\nd: {}")));
+    }
+
+    #[test]
+    fn can_change_indent() {
+        assert!(TreeTool::render(&json![{ "a": { "b": 1 } }], 4) == "a:\n    b: 1");
+    }
+
+    #[test]
+    fn scalar_is_rendered_as_json() {
+        assert!(TreeTool::render(&json![ "x" ], 2) == "\"x\"");
+    }
+
+    #[test]
+    fn can_render_deeply_nested_value() {
+        let mut value = json![ 1 ];
+        for _ in 0..100000 {
+            value = Value::Array(vec![ value ]);
+        }
+
+        let rendered = TreeTool::render(&value, 0);
+        assert!(rendered.lines().count() == 100000);
+
+        // Dropping a deeply nested value is itself recursive, so leak it rather than overflowing the stack
+        ::std::mem::forget(value);
+    }
+}
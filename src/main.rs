@@ -3,20 +3,103 @@ extern crate gossyp_base;
 extern crate gossyp_toolkit;
 extern crate gossyp_lang;
 
+use std::result::Result;
 use serde_json::*;
 
 use gossyp_base::*;
 use gossyp_base::basic::*;
 use gossyp_toolkit::io::*;
 use gossyp_toolkit::io::tool::*;
+use gossyp_toolkit::introspect::IntrospectTools;
+use gossyp_lang::lex::LexTools;
 use gossyp_lang::script::*;
 use gossyp_lang::script::tool::*;
 
+///
+/// Evaluates a line entered at the REPL
+///
+fn evaluate_line(line: String, main_env: &DynamicEnvironment) -> Result<Value, Value> {
+    let lex_line        = main_env.get_typed_tool::<String, Value>(LEX_SCRIPT).unwrap();
+    let parse_script    = main_env.get_json_tool(PARSE_SCRIPT).unwrap();
+    let eval_script     = main_env.get_json_tool(EVAL_SCRIPT).unwrap();
+
+    lex_line.invoke(line, main_env)
+        .and_then(|lexed| parse_script.invoke_json(lexed, main_env))
+        .and_then(|parsed| eval_script.invoke_json(parsed, main_env))
+}
+
+///
+/// Evaluates a line entered at the REPL, adding it to the transcript if one is being recorded
+///
+fn evaluate_and_record_line(line: String, main_env: &DynamicEnvironment) -> Result<Value, Value> {
+    let record_transcript   = TypedTool::<RecordTranscriptParameters, ()>::from(get_tool_or_error(main_env, RECORD_TRANSCRIPT)?);
+    let result              = evaluate_line(line.clone(), main_env);
+
+    record_transcript.invoke(RecordTranscriptParameters::new(&line, result.clone()), main_env)?;
+
+    result
+}
+
+///
+/// Performs a REPL meta-command (a line starting with ':')
+///
+/// `:record <path>` starts recording a transcript of the session to a file, and `:record` on
+/// its own stops recording. `:load <path>` replays the inputs from a transcript.
+///
+fn meta_command(line: &str, main_env: &DynamicEnvironment) -> Result<Value, Value> {
+    let print_result    = |result: Result<Value, Value>| display_result(result, main_env);
+    let mut parts       = line.trim().splitn(2, char::is_whitespace);
+    let command         = parts.next().unwrap_or("");
+    let argument        = parts.next().map(|argument| argument.trim()).unwrap_or("");
+
+    match (command, argument) {
+        (":record", "")     => get_tool_or_error(main_env, STOP_TRANSCRIPT)?.invoke_json(Value::Null, main_env),
+        (":record", path)   => get_tool_or_error(main_env, START_TRANSCRIPT)?.invoke_json(json![ path ], main_env),
+
+        (":load", path)     => {
+            let read_transcript = TypedTool::<&str, Vec<String>>::from(get_tool_or_error(main_env, READ_TRANSCRIPT)?);
+
+            for input in read_transcript.invoke(path, main_env)? {
+                print_result(evaluate_and_record_line(input, main_env));
+            }
+
+            Ok(Value::Null)
+        },
+
+        _                   => Err(json![{
+            "error":    "Unknown command",
+            "command":  command
+        }])
+    }
+}
+
+///
+/// Displays the result of evaluating a line
+///
+fn display_result(result: Result<Value, Value>, main_env: &DynamicEnvironment) {
+    let print_string    = main_env.get_typed_tool::<String, ()>(PRINT).unwrap();
+    let print_value     = main_env.get_typed_tool::<Value, ()>(PRINT).unwrap();
+
+    match result {
+        Ok(Value::Null) => { },
+        Ok(not_null)    => { print_value.invoke(not_null, main_env).unwrap(); },
+        Err(erm)        => {
+            print_string.invoke(String::from("*** Error: "), main_env).unwrap();
+            print_value.invoke(erm, main_env).unwrap();
+        }
+    }
+
+    print_string.invoke(String::from("\n"), main_env).unwrap();
+}
+
 fn main() {
     // Start up
     let main_env = DynamicEnvironment::new();
     main_env.import(IoTools::new_stdio());
+    main_env.import(TranscriptTools::new());
+    main_env.import(LexTools::new());
     main_env.import(ScriptTools::new());
+    main_env.import(IntrospectTools::new().with_category("lex", LexTools::new()).with_category("script", ScriptTools::new()));
 
     // Display a header
     let print_string = main_env.get_typed_tool::<String, ()>("print").unwrap();
@@ -31,9 +114,6 @@ fn main() {
         let print_string    = main_env.get_typed_tool::<String, ()>(PRINT).unwrap();
         let print_value     = main_env.get_typed_tool::<Value, ()>(PRINT).unwrap();
         let read_line       = main_env.get_typed_tool::<(), ReadLineResult>(READ_LINE).unwrap();
-        let lex_line        = main_env.get_typed_tool::<String, Value>(LEX_SCRIPT).unwrap();
-        let parse_script    = main_env.get_json_tool(PARSE_SCRIPT).unwrap();
-        let eval_script     = main_env.get_json_tool(EVAL_SCRIPT).unwrap();
         let display_prompt  = main_env.get_typed_tool::<(), ()>("display-prompt");
 
         // Display a prompt
@@ -48,21 +128,14 @@ fn main() {
         match next_line {
             Ok(result) => {
                 // Evaluate the result
-                let eval_result = lex_line.invoke(result.line, &main_env)
-                    .and_then(|lexed| parse_script.invoke_json(lexed, &main_env))
-                    .and_then(|parsed| eval_script.invoke_json(parsed, &main_env));
+                let eval_result = if result.line.trim_left().starts_with(":") {
+                    meta_command(&result.line, &main_env)
+                } else {
+                    evaluate_and_record_line(result.line, &main_env)
+                };
 
                 // Print it out
-                match eval_result {
-                    Ok(Value::Null) => { },
-                    Ok(not_null)    => { print_value.invoke(not_null, &main_env).unwrap(); },
-                    Err(erm)        => {
-                        print_string.invoke(String::from("*** Error: "), &main_env).unwrap();
-                        print_value.invoke(erm, &main_env).unwrap();
-                    }
-                }
-
-                print_string.invoke(String::from("\n"), &main_env).unwrap();
+                display_result(eval_result, &main_env);
 
                 // Stop on EOF
                 if result.eof {